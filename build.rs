@@ -0,0 +1,78 @@
+use std::{env, path::Path, process::Command};
+
+use serde_json::Value;
+
+// Looks up the versions of the runtime crates that got resolved for this
+// build, so they can be shown in the About tab. They are queried from cargo,
+// as the lock file isn't part of the repository.
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let metadata = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .args(["metadata", "--format-version", "1", "--offline"])
+        .current_dir(&manifest_dir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice::<Value>(&output.stdout).ok())
+        .unwrap_or_default();
+
+    // Cargo writes the lock file before running build scripts, and it changes
+    // whenever the resolved versions do.
+    let workspace_root = metadata["workspace_root"].as_str().unwrap_or(&manifest_dir);
+    println!(
+        "cargo:rerun-if-changed={}",
+        Path::new(workspace_root).join("Cargo.lock").display(),
+    );
+
+    let root = metadata["resolve"]["root"].as_str();
+    let auto_splitting = root.and_then(|id| dependency(&metadata, id, "livesplit-auto-splitting"));
+    let wasmtime = auto_splitting.and_then(|id| dependency(&metadata, id, "wasmtime"));
+    for (id, var) in [
+        (auto_splitting, "LIVESPLIT_AUTO_SPLITTING_VERSION"),
+        (wasmtime, "WASMTIME_VERSION"),
+    ] {
+        let version = id
+            .and_then(|id| version(&metadata, id))
+            .unwrap_or_else(|| "Unknown".into());
+        println!("cargo:rustc-env={var}={version}");
+    }
+}
+
+fn package<'a>(metadata: &'a Value, id: &str) -> Option<&'a Value> {
+    metadata["packages"]
+        .as_array()?
+        .iter()
+        .find(|package| package["id"] == id)
+}
+
+/// Finds the package the package with the id depends on. Several versions of
+/// a package can be part of the build, so this follows the resolved
+/// dependency graph instead of looking the package up by its name.
+fn dependency<'a>(metadata: &'a Value, id: &str, name: &str) -> Option<&'a str> {
+    metadata["resolve"]["nodes"]
+        .as_array()?
+        .iter()
+        .find(|node| node["id"] == id)?["dependencies"]
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .find(|&dependency| package(metadata, dependency).is_some_and(|p| p["name"] == name))
+}
+
+fn version(metadata: &Value, id: &str) -> Option<String> {
+    let package = package(metadata, id)?;
+    let mut version = package["version"].as_str()?.to_owned();
+
+    // Git dependencies don't bump their version, so the commit is more useful.
+    if let Some((_, commit)) = package["source"]
+        .as_str()
+        .and_then(|source| source.strip_prefix("git+"))
+        .and_then(|source| source.rsplit_once('#'))
+    {
+        version.push_str(" (");
+        version.push_str(commit.get(..7).unwrap_or(commit));
+        version.push(')');
+    }
+
+    Some(version)
+}
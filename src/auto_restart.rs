@@ -0,0 +1,150 @@
+use std::time::{Duration, Instant};
+
+/// How long a restarted auto splitter needs to run without trapping for the
+/// retries to be reset.
+const STABLE_DURATION: Duration = Duration::from_secs(60);
+
+/// Restarts the auto splitter after it traps, waiting twice as long with every
+/// consecutive retry.
+pub struct AutoRestart {
+    pub enabled: bool,
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub delay: Duration,
+    retries: u32,
+    gave_up: bool,
+    scheduled: Option<Instant>,
+    last_restart: Option<Instant>,
+}
+
+pub enum Trapped {
+    Ignored,
+    Scheduled(Duration),
+    GaveUp,
+}
+
+impl Default for AutoRestart {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_retries: 5,
+            delay: Duration::from_secs(1),
+            retries: 0,
+            gave_up: false,
+            scheduled: None,
+            last_restart: None,
+        }
+    }
+}
+
+impl AutoRestart {
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Schedules a restart after the auto splitter trapped.
+    pub fn trapped(&mut self, now: Instant) -> Trapped {
+        if !self.enabled || self.gave_up || self.scheduled.is_some() {
+            return Trapped::Ignored;
+        }
+        if self
+            .last_restart
+            .is_some_and(|last| now.duration_since(last) >= STABLE_DURATION)
+        {
+            self.retries = 0;
+        }
+        if self.retries >= self.max_retries {
+            self.gave_up = true;
+            return Trapped::GaveUp;
+        }
+        let delay = self.delay.saturating_mul(2u32.saturating_pow(self.retries));
+        self.retries += 1;
+        self.scheduled = Some(now + delay);
+        Trapped::Scheduled(delay)
+    }
+
+    /// Returns whether the scheduled restart is due.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        if self.scheduled.is_some_and(|scheduled| now >= scheduled) {
+            self.scheduled = None;
+            self.last_restart = Some(now);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forgets about all the previous retries, such as when a new auto
+    /// splitter is loaded.
+    pub fn reset(&mut self) {
+        self.retries = 0;
+        self.gave_up = false;
+        self.scheduled = None;
+        self.last_restart = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn enabled() -> AutoRestart {
+        AutoRestart {
+            enabled: true,
+            max_retries: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let mut restart = enabled();
+        let now = Instant::now();
+
+        assert!(
+            matches!(restart.trapped(now), Trapped::Scheduled(d) if d == Duration::from_secs(1))
+        );
+        assert!(matches!(restart.trapped(now), Trapped::Ignored));
+        assert!(!restart.poll(now));
+        let now = now + Duration::from_secs(1);
+        assert!(restart.poll(now));
+
+        assert!(
+            matches!(restart.trapped(now), Trapped::Scheduled(d) if d == Duration::from_secs(2))
+        );
+        let now = now + Duration::from_secs(2);
+        assert!(restart.poll(now));
+
+        assert!(matches!(restart.trapped(now), Trapped::GaveUp));
+        assert!(matches!(restart.trapped(now), Trapped::Ignored));
+
+        restart.reset();
+        assert!(matches!(restart.trapped(now), Trapped::Scheduled(_)));
+    }
+
+    #[test]
+    fn test_stable() {
+        let mut restart = enabled();
+        let now = Instant::now();
+
+        restart.trapped(now);
+        let now = now + Duration::from_secs(1);
+        assert!(restart.poll(now));
+        restart.trapped(now);
+        let now = now + Duration::from_secs(2);
+        assert!(restart.poll(now));
+        assert_eq!(restart.retries(), 2);
+
+        let now = now + STABLE_DURATION;
+        assert!(
+            matches!(restart.trapped(now), Trapped::Scheduled(d) if d == Duration::from_secs(1))
+        );
+        assert_eq!(restart.retries(), 1);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut restart = AutoRestart::default();
+        assert!(matches!(restart.trapped(Instant::now()), Trapped::Ignored));
+    }
+}
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use hdrhistogram::Histogram;
+
+/// How many ticks after loading the auto splitter are not measured, as they
+/// are usually dominated by attaching to the game and scanning its memory.
+const WARM_UP_TICKS: u64 = 600;
+/// How many ticks are measured after the warm-up.
+const SAMPLE_TICKS: u64 = 3000;
+/// How much slower the p99 tick time needs to be than the one of the previous
+/// build to be considered a regression.
+const REGRESSION_FACTOR: f64 = 1.2;
+/// Differences below this are considered noise, no matter how large they are
+/// relative to the tick time.
+const MIN_REGRESSION: Duration = Duration::from_micros(20);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Percentiles {
+    pub p50: Duration,
+    pub p99: Duration,
+}
+
+/// The tick time percentiles of the new build, along with the ones of the
+/// previous build if there was one.
+pub struct Comparison {
+    pub before: Option<Percentiles>,
+    pub after: Percentiles,
+}
+
+impl Comparison {
+    pub fn is_regression(&self) -> bool {
+        self.before.is_some_and(|before| {
+            self.after.p99.as_secs_f64() > before.p99.as_secs_f64() * REGRESSION_FACTOR
+                && self.after.p99 - before.p99 >= MIN_REGRESSION
+        })
+    }
+}
+
+/// Measures the tick times of each build of the auto splitter after a
+/// warm-up, so reloading a build compares it against the previous one.
+pub struct Benchmark {
+    ticks: u64,
+    samples: Histogram<u64>,
+    baseline: Option<Percentiles>,
+    result: Option<Percentiles>,
+}
+
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self {
+            ticks: 0,
+            samples: Histogram::new(1).unwrap(),
+            baseline: None,
+            result: None,
+        }
+    }
+}
+
+impl Benchmark {
+    /// Starts measuring a new build. If it's compared to the previous one,
+    /// that one's measurement becomes the baseline.
+    pub fn restart(&mut self, compare: bool) {
+        self.baseline = if compare {
+            self.result.take().or(self.baseline)
+        } else {
+            None
+        };
+        self.result = None;
+        self.ticks = 0;
+        self.samples.clear();
+    }
+
+    /// The tick time percentiles of the current build, once enough ticks
+    /// were measured.
+    pub fn result(&self) -> Option<Percentiles> {
+        self.result
+    }
+
+    /// Records the duration of a tick and returns the comparison once enough
+    /// ticks were measured.
+    pub fn record(&mut self, tick_time: Duration) -> Option<Comparison> {
+        self.ticks += 1;
+        if self.ticks <= WARM_UP_TICKS || self.result.is_some() {
+            return None;
+        }
+        self.samples += tick_time.as_nanos() as u64;
+        if self.samples.len() < SAMPLE_TICKS {
+            return None;
+        }
+        let after = Percentiles {
+            p50: Duration::from_nanos(self.samples.value_at_percentile(50.0)),
+            p99: Duration::from_nanos(self.samples.value_at_percentile(99.0)),
+        };
+        self.result = Some(after);
+        Some(Comparison {
+            before: self.baseline,
+            after,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn measure(benchmark: &mut Benchmark, tick_time: Duration) -> Comparison {
+        (0..WARM_UP_TICKS + SAMPLE_TICKS)
+            .find_map(|_| benchmark.record(tick_time))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_regression() {
+        let mut benchmark = Benchmark::default();
+        let first = measure(&mut benchmark, Duration::from_micros(100));
+        assert!(first.before.is_none());
+        assert!(!first.is_regression());
+        assert!(benchmark.record(Duration::from_secs(1)).is_none());
+
+        benchmark.restart(true);
+        let second = measure(&mut benchmark, Duration::from_micros(200));
+        assert_eq!(second.before, Some(first.after));
+        assert!(second.is_regression());
+
+        benchmark.restart(true);
+        assert!(!measure(&mut benchmark, Duration::from_micros(210)).is_regression());
+
+        benchmark.restart(false);
+        assert!(measure(&mut benchmark, Duration::from_micros(100))
+            .before
+            .is_none());
+    }
+}
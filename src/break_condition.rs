@@ -0,0 +1,382 @@
+use std::{cmp::Ordering, iter::Peekable, str::CharIndices};
+
+/// The state of the timer that a condition can refer to by name.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Field {
+    SplitIndex,
+    Tick,
+    /// The game time in seconds.
+    GameTime,
+    TimerState,
+    GameTimeState,
+}
+
+pub const FIELDS: [(&str, Field); 5] = [
+    ("split_index", Field::SplitIndex),
+    ("tick", Field::Tick),
+    ("game_time", Field::GameTime),
+    ("timer_state", Field::TimerState),
+    ("game_time_state", Field::GameTimeState),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Number(f64),
+    String(Box<str>),
+    /// A variable that isn't set.
+    Missing,
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(value) => *value,
+            Value::Number(value) => *value != 0.0,
+            Value::String(value) => !value.is_empty(),
+            Value::Missing => false,
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(value) => Some(*value),
+            Value::String(value) => value.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Strings that look like numbers are compared as numbers, as that's how
+    /// auto splitters usually report them.
+    fn compare(&self, other: &Value) -> Option<Ordering> {
+        if let (Some(a), Some(b)) = (self.as_number(), other.as_number()) {
+            return a.partial_cmp(&b);
+        }
+        match (self, other) {
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+/// Provides the values a condition gets evaluated against.
+pub trait Scope {
+    fn field(&self, field: Field) -> Value;
+    fn variable(&self, key: &str) -> Option<&str>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Field(Field),
+    Variable(Box<str>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, Operator, Box<Expr>),
+}
+
+impl Expr {
+    fn evaluate(&self, scope: &impl Scope) -> Value {
+        match self {
+            Expr::Literal(value) => value.clone(),
+            Expr::Field(field) => scope.field(*field),
+            Expr::Variable(key) => scope
+                .variable(key)
+                .map_or(Value::Missing, |value| Value::String(value.into())),
+            Expr::Not(expr) => Value::Bool(!expr.evaluate(scope).is_truthy()),
+            Expr::And(a, b) => {
+                Value::Bool(a.evaluate(scope).is_truthy() && b.evaluate(scope).is_truthy())
+            }
+            Expr::Or(a, b) => {
+                Value::Bool(a.evaluate(scope).is_truthy() || b.evaluate(scope).is_truthy())
+            }
+            Expr::Compare(a, operator, b) => {
+                let (a, b) = (a.evaluate(scope), b.evaluate(scope));
+                // Comparisons with variables that aren't set are never true.
+                if a == Value::Missing || b == Value::Missing {
+                    return Value::Bool(false);
+                }
+                Value::Bool(match a.compare(&b) {
+                    Some(ordering) => match operator {
+                        Operator::Eq => ordering.is_eq(),
+                        Operator::Ne => ordering.is_ne(),
+                        Operator::Lt => ordering.is_lt(),
+                        Operator::Le => ordering.is_le(),
+                        Operator::Gt => ordering.is_gt(),
+                        Operator::Ge => ordering.is_ge(),
+                    },
+                    None => *operator == Operator::Ne,
+                })
+            }
+        }
+    }
+}
+
+/// A condition like `split_index == 2 && variable("IGT") > "100"` that
+/// pauses the auto splitter once it becomes true.
+pub struct BreakCondition {
+    pub source: Box<str>,
+    expr: Expr,
+    /// Whether the condition was true after the last tick. The auto splitter
+    /// only pauses when the condition becomes true, so it can be resumed
+    /// while the condition stays true.
+    was_true: bool,
+}
+
+impl BreakCondition {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: source.char_indices().peekable(),
+            len: source.len(),
+        };
+        let expr = parser.or()?;
+        parser.skip_whitespace();
+        if let Some((index, c)) = parser.chars.next() {
+            return Err(format!("Unexpected '{c}' at position {index}."));
+        }
+        Ok(Self {
+            source: source.into(),
+            expr,
+            was_true: false,
+        })
+    }
+
+    /// Evaluates the condition and returns whether it just became true.
+    pub fn check(&mut self, scope: &impl Scope) -> bool {
+        let is_true = self.expr.evaluate(scope).is_truthy();
+        !std::mem::replace(&mut self.was_true, is_true) && is_true
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    len: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.len, |&(index, _)| index)
+    }
+
+    /// Consumes the token if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let mut lookahead = self.chars.clone();
+        for expected in token.chars() {
+            if lookahead.next().map(|(_, c)| c) != Some(expected) {
+                return false;
+            }
+        }
+        self.chars = lookahead;
+        true
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected '{token}' at position {}.",
+                self.position()
+            ))
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat("||") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.comparison()?;
+        while self.eat("&&") {
+            expr = Expr::And(Box::new(expr), Box::new(self.comparison()?));
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let expr = self.unary()?;
+        // The longer operators need to be tried first.
+        for (token, operator) in [
+            ("==", Operator::Eq),
+            ("!=", Operator::Ne),
+            ("<=", Operator::Le),
+            (">=", Operator::Ge),
+            ("<", Operator::Lt),
+            (">", Operator::Gt),
+        ] {
+            if self.eat(token) {
+                return Ok(Expr::Compare(
+                    Box::new(expr),
+                    operator,
+                    Box::new(self.unary()?),
+                ));
+            }
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.or()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+
+        let position = self.position();
+        match self.chars.peek().map(|&(_, c)| c) {
+            Some('"') => Ok(Expr::Literal(Value::String(self.string()?.into()))),
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let mut number = String::new();
+                while let Some((_, c)) = self.chars.next_if(|&(i, c)| {
+                    c.is_ascii_digit() || c == '.' || (c == '-' && i == position)
+                }) {
+                    number.push(c);
+                }
+                number
+                    .parse()
+                    .map(|number| Expr::Literal(Value::Number(number)))
+                    .map_err(|_| format!("Invalid number at position {position}."))
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some((_, c)) = self
+                    .chars
+                    .next_if(|&(_, c)| c.is_alphanumeric() || c == '_')
+                {
+                    name.push(c);
+                }
+                match name.as_str() {
+                    "true" => Ok(Expr::Literal(Value::Bool(true))),
+                    "false" => Ok(Expr::Literal(Value::Bool(false))),
+                    "variable" => {
+                        self.expect("(")?;
+                        self.skip_whitespace();
+                        let key = self.string()?;
+                        self.expect(")")?;
+                        Ok(Expr::Variable(key.into()))
+                    }
+                    _ => FIELDS
+                        .iter()
+                        .find(|(field_name, _)| *field_name == name)
+                        .map(|&(_, field)| Expr::Field(field))
+                        .ok_or_else(|| format!("Unknown name \"{name}\" at position {position}.")),
+                }
+            }
+            Some(c) => Err(format!("Unexpected '{c}' at position {position}.")),
+            None => Err("Unexpected end of the condition.".into()),
+        }
+    }
+
+    /// Parses a string literal in double quotes. Backslashes escape the next
+    /// character.
+    fn string(&mut self) -> Result<String, String> {
+        let position = self.position();
+        if self.chars.next_if(|&(_, c)| c == '"').is_none() {
+            return Err(format!("Expected a string at position {position}."));
+        }
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(string),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, c)) => string.push(c),
+                    None => break,
+                },
+                Some((_, c)) => string.push(c),
+                None => break,
+            }
+        }
+        Err(format!("Unterminated string at position {position}."))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    struct TestScope {
+        split_index: usize,
+        variables: HashMap<&'static str, &'static str>,
+    }
+
+    impl Scope for TestScope {
+        fn field(&self, field: Field) -> Value {
+            match field {
+                Field::SplitIndex => Value::Number(self.split_index as f64),
+                Field::TimerState => Value::String("Running".into()),
+                _ => Value::Missing,
+            }
+        }
+
+        fn variable(&self, key: &str) -> Option<&str> {
+            self.variables.get(key).copied()
+        }
+    }
+
+    #[test]
+    fn test_condition() {
+        let mut scope = TestScope {
+            split_index: 2,
+            variables: HashMap::from([("IGT", "150.5"), ("Level", "Castle")]),
+        };
+        let evaluate = |source: &str, scope: &TestScope| {
+            BreakCondition::parse(source)
+                .unwrap()
+                .expr
+                .evaluate(scope)
+                .is_truthy()
+        };
+
+        assert!(evaluate(
+            r#"split_index == 2 && variable("IGT") > "100""#,
+            &scope
+        ));
+        // Numbers are compared numerically, even when they are strings.
+        assert!(evaluate(r#"variable("IGT") < 1000"#, &scope));
+        assert!(evaluate(
+            r#"timer_state == "Running" && !(variable("Level") == "Town")"#,
+            &scope
+        ));
+        assert!(!evaluate(r#"variable("Missing") != "x""#, &scope));
+        assert!(evaluate(r#"split_index < 0 || variable("Level")"#, &scope));
+
+        let mut condition = BreakCondition::parse("split_index >= 3").unwrap();
+        assert!(!condition.check(&scope));
+        scope.split_index = 3;
+        assert!(condition.check(&scope));
+        // It only breaks again after the condition became false in between.
+        assert!(!condition.check(&scope));
+
+        assert!(BreakCondition::parse("split_index ==").is_err());
+        assert!(BreakCondition::parse("foo == 1").is_err());
+        assert!(BreakCondition::parse(r#"variable("IGT"#).is_err());
+        assert!(BreakCondition::parse("(tick > 1").is_err());
+        assert!(BreakCondition::parse("tick > 1 )").is_err());
+    }
+}
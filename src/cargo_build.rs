@@ -0,0 +1,150 @@
+use std::{
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use livesplit_auto_splitting::LogLevel;
+
+pub const TARGET: &str = "wasm32-wasip1";
+
+pub enum BuildEvent {
+    Output(String, LogLevel),
+    /// The build finished. Contains the WASM file that got built if the build
+    /// succeeded.
+    Finished(Option<PathBuf>),
+}
+
+/// A `cargo build` of an auto splitter running in the background.
+pub struct CargoBuild {
+    events: Receiver<BuildEvent>,
+}
+
+impl CargoBuild {
+    pub fn start(workspace: &Path) -> std::io::Result<Self> {
+        let mut child = Command::new("cargo")
+            .args([
+                "build",
+                "--release",
+                "--message-format=json",
+                "--target",
+                TARGET,
+            ])
+            .current_dir(workspace)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let (sender, events) = mpsc::channel();
+
+        let stderr = child.stderr.take().unwrap();
+        let stderr_sender = sender.clone();
+        let stderr_thread = thread::spawn(move || {
+            // Cargo reports its progress and its own errors on stderr.
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                let level = if line.trim_start().starts_with("error") {
+                    LogLevel::Error
+                } else if line.trim_start().starts_with("warning") {
+                    LogLevel::Warning
+                } else {
+                    LogLevel::Info
+                };
+                let _ = stderr_sender.send(BuildEvent::Output(line, level));
+            }
+        });
+
+        let stdout = child.stdout.take().unwrap();
+        thread::spawn(move || {
+            let mut artifact = None;
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if let Some(event) = parse_message(&line, &mut artifact) {
+                    let _ = sender.send(event);
+                }
+            }
+            let _ = stderr_thread.join();
+            let succeeded = child.wait().is_ok_and(|status| status.success());
+            let _ = sender.send(BuildEvent::Finished(artifact.filter(|_| succeeded)));
+        });
+
+        Ok(Self { events })
+    }
+
+    /// Returns the next event of the build if there is one.
+    pub fn poll(&self) -> Option<BuildEvent> {
+        match self.events.try_recv() {
+            Ok(event) => Some(event),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(BuildEvent::Finished(None)),
+        }
+    }
+}
+
+/// Parses a JSON message of cargo. Compiler messages turn into output, while
+/// the path of the last built WASM file is stored in `artifact`.
+fn parse_message(line: &str, artifact: &mut Option<PathBuf>) -> Option<BuildEvent> {
+    let message: serde_json::Value = serde_json::from_str(line).ok()?;
+    match message["reason"].as_str()? {
+        "compiler-message" => {
+            let rendered = message["message"]["rendered"].as_str()?;
+            let level = match message["message"]["level"].as_str()? {
+                "error" | "error: internal compiler error" => LogLevel::Error,
+                "warning" => LogLevel::Warning,
+                _ => LogLevel::Info,
+            };
+            Some(BuildEvent::Output(rendered.trim_end().to_owned(), level))
+        }
+        "compiler-artifact" => {
+            if let Some(wasm) = message["filenames"]
+                .as_array()?
+                .iter()
+                .filter_map(|f| f.as_str())
+                .find(|f| f.ends_with(".wasm"))
+            {
+                *artifact = Some(wasm.into());
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        let mut artifact = None;
+
+        let event = parse_message(
+            r#"{"reason":"compiler-message","message":{"level":"warning","rendered":"warning: unused variable\n"}}"#,
+            &mut artifact,
+        );
+        assert!(matches!(
+            event,
+            Some(BuildEvent::Output(message, LogLevel::Warning)) if message == "warning: unused variable"
+        ));
+
+        let event = parse_message(
+            r#"{"reason":"compiler-artifact","filenames":["/target/wasm32-wasip1/release/splitter.wasm"]}"#,
+            &mut artifact,
+        );
+        assert!(event.is_none());
+        assert_eq!(
+            artifact.as_deref(),
+            Some(Path::new("/target/wasm32-wasip1/release/splitter.wasm")),
+        );
+
+        let event = parse_message(
+            r#"{"reason":"compiler-artifact","filenames":["/target/release/libasr.rlib"]}"#,
+            &mut artifact,
+        );
+        assert!(event.is_none());
+        assert!(artifact.is_some());
+
+        assert!(parse_message("Not JSON", &mut artifact).is_none());
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    collections::HashSet,
+    fmt, fs,
+    path::Path,
+    process,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use livesplit_auto_splitting::settings;
+use time::UtcOffset;
+
+use crate::{
+    build_runtime, console, diagnostics, failure::Failure, fmt_std_duration,
+    module_info::ModuleInfo, DebuggerTimer, RuntimeConfig,
+};
+
+/// How long the single update may take before it gets interrupted.
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Validates the auto splitter without a user interface or a game: it gets
+/// compiled, instantiated against a timer that isn't shown anywhere and
+/// updated once, so it registers its settings. The exit code is the one of
+/// the first failure.
+pub fn run(path: &Path, runtime_config: RuntimeConfig) -> process::ExitCode {
+    let timer = DebuggerTimer::new(UtcOffset::UTC);
+    let result = check(path, runtime_config, &timer);
+
+    for log in &timer.0.read().unwrap().logs {
+        print(log);
+    }
+
+    match result {
+        Ok(()) => {
+            print("The auto splitter passed all checks.");
+            process::ExitCode::SUCCESS
+        }
+        Err((failure, e)) => {
+            print(format_args!("{}\n{e:?}", failure.to_str()));
+            failure.exit_code().into()
+        }
+    }
+}
+
+/// Prints a line of the output. If the output got closed, the exit code still
+/// tells the result, so the check continues.
+fn print(line: impl fmt::Display) {
+    let _ = console::print_line(line);
+}
+
+fn check(
+    path: &Path,
+    runtime_config: RuntimeConfig,
+    timer: &DebuggerTimer,
+) -> Result<(), (Failure, anyhow::Error)> {
+    let data = fs::read(path)
+        .context("Failed loading the auto splitter from the file system.")
+        .map_err(|e| (Failure::Compile, e))?;
+
+    let info = ModuleInfo::parse(&data)
+        .context("The auto splitter is not a valid WebAssembly module.")
+        .map_err(|e| (Failure::Compile, e))?;
+    // The lints only know the runtime the debugger got built with, so
+    // whether the imports and exports actually work is left to the
+    // instantiation.
+    for diagnostic in diagnostics::lint(&info, runtime_config.optimize) {
+        print(format_args!(
+            "{}: {}",
+            diagnostic.severity.to_str(),
+            diagnostic.message
+        ));
+    }
+
+    let runtime = build_runtime(runtime_config).map_err(|e| (Failure::Compile, e))?;
+    let (module, compile_time) = timed(|| runtime.compile(&data));
+    let module = module
+        .context("Failed loading the auto splitter.")
+        .map_err(|e| (Failure::Compile, e))?;
+    print(format_args!(
+        "Compiled in {}.",
+        fmt_std_duration(compile_time)
+    ));
+
+    let (auto_splitter, instantiate_time) = timed(|| module.instantiate(timer.clone(), None, None));
+    let auto_splitter = auto_splitter
+        .context("Failed starting the auto splitter.")
+        .map_err(|e| (Failure::Instantiation, e))?;
+    print(format_args!(
+        "Instantiated in {}.",
+        fmt_std_duration(instantiate_time)
+    ));
+
+    let interrupt = auto_splitter.interrupt_handle();
+    let (finished, wait_for_update) = mpsc::channel::<()>();
+    thread::spawn(move || {
+        if wait_for_update.recv_timeout(UPDATE_TIMEOUT) == Err(mpsc::RecvTimeoutError::Timeout) {
+            interrupt.interrupt();
+        }
+    });
+    let (result, update_time) = timed(|| auto_splitter.lock().update());
+    drop(finished);
+    let (failure, message) = if update_time >= UPDATE_TIMEOUT {
+        (
+            Failure::WatchdogTimeout,
+            format!(
+                "The update didn't finish within {}.",
+                fmt_std_duration(UPDATE_TIMEOUT),
+            ),
+        )
+    } else {
+        (Failure::Trap, "Failed executing the auto splitter.".into())
+    };
+    result.context(message).map_err(|e| (failure, e))?;
+    print(format_args!(
+        "Updated once in {}.",
+        fmt_std_duration(update_time)
+    ));
+
+    let widgets = auto_splitter.settings_widgets();
+    print(format_args!(
+        "Registered {} setting widgets.",
+        widgets.len()
+    ));
+    let problems = settings_problems(&widgets);
+    if !problems.is_empty() {
+        return Err((Failure::Validation, anyhow::anyhow!(problems.join("\n"))));
+    }
+
+    Ok(())
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Finds settings that can't work as intended, like settings sharing the
+/// same key or choices that default to an option that doesn't exist.
+fn settings_problems(widgets: &[settings::Widget]) -> Vec<String> {
+    let mut problems = duplicates(
+        widgets
+            .iter()
+            .filter(|widget| !matches!(widget.kind, settings::WidgetKind::Title { .. }))
+            .map(|widget| &*widget.key),
+    )
+    .into_iter()
+    .map(|key| format!("Multiple settings use the key \"{key}\"."))
+    .collect::<Vec<_>>();
+
+    for widget in widgets {
+        if let settings::WidgetKind::Choice {
+            default_option_key,
+            options,
+            ..
+        } = &widget.kind
+        {
+            if !options
+                .iter()
+                .any(|option| option.key == *default_option_key)
+            {
+                problems.push(format!(
+                    "The choice \"{}\" defaults to the option \"{default_option_key}\", which it doesn't have.",
+                    widget.key,
+                ));
+            }
+            for key in duplicates(options.iter().map(|option| &*option.key)) {
+                problems.push(format!(
+                    "The choice \"{}\" has multiple options with the key \"{key}\".",
+                    widget.key,
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+/// The keys that occur more than once, each reported once.
+fn duplicates<'a>(keys: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    for key in keys {
+        if !seen.insert(key) && !duplicates.contains(&key) {
+            duplicates.push(key);
+        }
+    }
+    duplicates
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_duplicates() {
+        assert_eq!(
+            duplicates(["a", "b", "a", "c", "a", "b"].into_iter()),
+            ["a", "b"],
+        );
+        assert!(duplicates(["a", "b", "c"].into_iter()).is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+/// Attaches to the console the debugger got started from, so the command line
+/// modes can print to it. Release builds on Windows are GUI applications that
+/// don't get a console on their own.
+pub fn attach() {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        // This fails if there is no console to attach to, like when the
+        // debugger got started from the file explorer, in which case there's
+        // nobody to print to anyway.
+        unsafe {
+            AttachConsole(ATTACH_PARENT_PROCESS);
+        }
+    }
+}
+
+/// Prints a line to stdout. Unlike `println!` this doesn't panic if stdout got
+/// closed, like when the output is piped into a program that exited early.
+/// Returns whether the line got printed.
+pub fn print_line(line: impl fmt::Display) -> bool {
+    writeln!(io::stdout().lock(), "{line}").is_ok()
+}
+
+/// Prints a line to stderr, which keeps it out of the output that gets
+/// processed, like the JSON Lines of the headless mode.
+pub fn print_error_line(line: impl fmt::Display) -> bool {
+    writeln!(io::stderr().lock(), "{line}").is_ok()
+}
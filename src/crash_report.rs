@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    io::{self, Seek, Write},
+    path::Path,
+};
+
+use livesplit_auto_splitting::settings;
+use zip::{result::ZipResult, write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    settings_json::settings_map_to_json,
+    trap_stats::{self, TrapStats},
+};
+
+/// Everything needed to analyze a crash of an auto splitter, to be attached to
+/// a bug report. The report owns a copy of all the data, so it can be written
+/// without holding any locks.
+pub struct CrashReport {
+    /// General information about the auto splitter, the runtime and the
+    /// debugger.
+    pub info: String,
+    /// The logs, one line per message.
+    pub logs: String,
+    pub settings_map: Option<settings::Map>,
+    pub traps: TrapStats,
+    pub memory: Option<Vec<u8>>,
+}
+
+impl CrashReport {
+    /// Bundles the report into a zip archive.
+    pub fn write_zip(&self, writer: impl Write + Seek) -> ZipResult<()> {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(writer);
+        self.write_files(&mut zip, |zip, name| Ok(zip.start_file(name, options)?))?;
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// Writes the files of the report into a folder, which gets created if
+    /// it doesn't exist yet.
+    pub fn write_folder(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)?;
+        let mut folder = Folder { path, file: None };
+        self.write_files(&mut folder, |folder, name| folder.start_file(name))?;
+        folder.finish()
+    }
+
+    /// Writes each file of the report after calling `start_file` with its
+    /// name.
+    fn write_files<W: Write>(
+        &self,
+        writer: &mut W,
+        mut start_file: impl FnMut(&mut W, &str) -> io::Result<()>,
+    ) -> io::Result<()> {
+        start_file(writer, "info.txt")?;
+        writer.write_all(self.info.as_bytes())?;
+
+        start_file(writer, "logs.txt")?;
+        writer.write_all(self.logs.as_bytes())?;
+
+        start_file(writer, "traps.txt")?;
+        for kind in trap_stats::KINDS {
+            writeln!(writer, "{}: {}", kind.to_str(), self.traps.count(kind))?;
+            if let Some(last) = self.traps.last(kind) {
+                writeln!(writer, "Last seen at tick {}:\n{}", last.tick, last.message)?;
+            }
+            writeln!(writer)?;
+        }
+
+        if let Some(settings_map) = &self.settings_map {
+            start_file(writer, "settings_map.json")?;
+            serde_json::to_writer_pretty(&mut *writer, &settings_map_to_json(settings_map))
+                .map_err(io::Error::from)?;
+        }
+
+        if let Some(memory) = &self.memory {
+            start_file(writer, "memory_dump.bin")?;
+            writer.write_all(memory)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the files of a report into a folder, one at a time.
+struct Folder<'a> {
+    path: &'a Path,
+    file: Option<io::BufWriter<fs::File>>,
+}
+
+impl Folder<'_> {
+    fn start_file(&mut self, name: &str) -> io::Result<()> {
+        self.finish()?;
+        self.file = Some(io::BufWriter::new(fs::File::create(self.path.join(name))?));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.file.take() {
+            Some(mut file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Write for Folder<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.file {
+            Some(file) => file.write(buf),
+            None => Err(io::ErrorKind::NotConnected.into()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.file {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
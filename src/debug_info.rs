@@ -0,0 +1,234 @@
+use std::{collections::HashMap, ops::Range};
+
+use anyhow::Context;
+use gimli::{AttributeValue, EndianSlice, LittleEndian, Operation, UnitOffset};
+use wasmparser::{Parser, Payload};
+
+type Reader<'a> = EndianSlice<'a, LittleEndian>;
+
+/// How many levels of pointers, typedefs and the like are followed to name a
+/// type.
+const MAX_TYPE_DEPTH: usize = 8;
+/// How many bytes of a variable are shown as its value.
+const MAX_VALUE_SIZE: u64 = 16;
+
+/// A static or global variable of the auto splitter that lives in its linear
+/// memory.
+pub struct GlobalVariable {
+    /// The name including the namespaces, like `my_splitter::WATCHERS`.
+    pub name: Box<str>,
+    pub type_name: Box<str>,
+    pub address: u64,
+    pub size: Option<u64>,
+}
+
+impl GlobalVariable {
+    /// The range of the memory of the auto splitter that is shown as the
+    /// value of the variable. Large variables are cut off.
+    pub fn value_range(&self) -> Option<Range<usize>> {
+        let start = usize::try_from(self.address).ok()?;
+        let size = self.size?.min(MAX_VALUE_SIZE);
+        Some(start..start.checked_add(size as usize)?)
+    }
+
+    /// Formats the value of the variable from its bytes. Primitive types are
+    /// shown as such, everything else as hex.
+    pub fn fmt_value(&self, bytes: &[u8]) -> String {
+        macro_rules! primitive {
+            ($($name:literal => $ty:ty),* $(,)?) => {
+                match &*self.type_name {
+                    $($name => {
+                        if let Ok(bytes) = bytes.try_into() {
+                            return <$ty>::from_le_bytes(bytes).to_string();
+                        }
+                    })*
+                    _ => {}
+                }
+            };
+        }
+        primitive!(
+            "u8" => u8, "u16" => u16, "u32" => u32, "u64" => u64, "u128" => u128, "usize" => u32,
+            "i8" => i8, "i16" => i16, "i32" => i32, "i64" => i64, "i128" => i128, "isize" => i32,
+            "f32" => f32, "f64" => f64,
+        );
+        match (&*self.type_name, bytes) {
+            ("bool", &[byte]) if byte <= 1 => return (byte == 1).to_string(),
+            (name, &[a, b, c, d]) if name.starts_with(['*', '&']) => {
+                return format!("{:#X}", u32::from_le_bytes([a, b, c, d]));
+            }
+            _ => {}
+        }
+
+        let mut hex = bytes
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if self.size.is_some_and(|size| size > bytes.len() as u64) {
+            hex.push_str(" …");
+        }
+        hex
+    }
+}
+
+/// Reads the global variables from the DWARF debug information of the
+/// module. Modules without debug information have no variables.
+pub fn global_variables(module: &[u8]) -> anyhow::Result<Vec<GlobalVariable>> {
+    let mut sections = HashMap::new();
+    for payload in Parser::new(0).parse_all(module) {
+        if let Payload::CustomSection(reader) = payload? {
+            if reader.name().starts_with(".debug_") {
+                sections.insert(reader.name(), reader.data());
+            }
+        }
+    }
+
+    let dwarf = gimli::Dwarf::load(|id| {
+        Ok::<_, gimli::Error>(EndianSlice::new(
+            sections.get(id.name()).copied().unwrap_or_default(),
+            LittleEndian,
+        ))
+    })?;
+
+    let mut variables = Vec::new();
+    let mut units = dwarf.units();
+    while let Some(header) = units
+        .next()
+        .context("Failed reading the debug information.")?
+    {
+        let unit = dwarf.unit(header)?;
+        let mut entries = unit.entries();
+        let mut depth = 0;
+        // The names of the namespaces the current entry is nested in.
+        let mut namespaces = Vec::<(isize, String)>::new();
+        while let Some((delta, entry)) = entries.next_dfs()? {
+            depth += delta;
+            namespaces.retain(|&(namespace_depth, _)| namespace_depth < depth);
+            let name = || -> gimli::Result<Option<String>> {
+                Ok(match entry.attr_value(gimli::DW_AT_name)? {
+                    Some(name) => Some(dwarf.attr_string(&unit, name)?.to_string_lossy().into()),
+                    None => None,
+                })
+            };
+
+            match entry.tag() {
+                gimli::DW_TAG_namespace => {
+                    if let Some(name) = name()? {
+                        namespaces.push((depth, name));
+                    }
+                }
+                gimli::DW_TAG_variable => {
+                    let Some(AttributeValue::Exprloc(expression)) =
+                        entry.attr_value(gimli::DW_AT_location)?
+                    else {
+                        continue;
+                    };
+                    // Only variables at a fixed address are global ones.
+                    let mut operations = expression.operations(unit.encoding());
+                    let (Some(Operation::Address { address }), None) =
+                        (operations.next()?, operations.next()?)
+                    else {
+                        continue;
+                    };
+                    let Some(name) = name()? else { continue };
+
+                    let (type_name, size) = match entry.attr_value(gimli::DW_AT_type)? {
+                        Some(AttributeValue::UnitRef(offset)) => {
+                            type_info(&dwarf, &unit, offset, 0)?
+                        }
+                        _ => ("?".into(), None),
+                    };
+
+                    let mut full_name = String::new();
+                    for (_, namespace) in &namespaces {
+                        full_name.push_str(namespace);
+                        full_name.push_str("::");
+                    }
+                    full_name.push_str(&name);
+
+                    variables.push(GlobalVariable {
+                        name: full_name.into(),
+                        type_name: type_name.into(),
+                        address,
+                        size,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    variables.sort_by_key(|variable| variable.address);
+    Ok(variables)
+}
+
+/// The name and the size of a type.
+fn type_info(
+    dwarf: &gimli::Dwarf<Reader<'_>>,
+    unit: &gimli::Unit<Reader<'_>>,
+    offset: UnitOffset,
+    depth: usize,
+) -> gimli::Result<(String, Option<u64>)> {
+    let entry = unit.entry(offset)?;
+    let size = entry
+        .attr_value(gimli::DW_AT_byte_size)?
+        .and_then(|size| size.udata_value());
+    if let Some(name) = entry.attr_value(gimli::DW_AT_name)? {
+        return Ok((
+            dwarf.attr_string(unit, name)?.to_string_lossy().into(),
+            size,
+        ));
+    }
+
+    let inner = match entry.attr_value(gimli::DW_AT_type)? {
+        Some(AttributeValue::UnitRef(offset)) if depth < MAX_TYPE_DEPTH => {
+            Some(type_info(dwarf, unit, offset, depth + 1)?)
+        }
+        _ => None,
+    };
+    let inner_name = inner.as_ref().map_or("?", |(name, _)| name);
+    Ok(match entry.tag() {
+        gimli::DW_TAG_pointer_type => (
+            format!("*{inner_name}"),
+            size.or(Some(unit.encoding().address_size as u64)),
+        ),
+        gimli::DW_TAG_array_type => (format!("[{inner_name}]"), size),
+        _ => inner.unwrap_or_else(|| ("?".into(), size)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_no_debug_info() {
+        let module = b"\0asm\x01\0\0\0";
+        assert!(global_variables(module).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fmt_value() {
+        let variable = |type_name: &str, address, size| GlobalVariable {
+            name: "VARIABLE".into(),
+            type_name: type_name.into(),
+            address,
+            size: Some(size),
+        };
+        let memory = (0..64).collect::<Vec<u8>>();
+
+        let value = |variable: &GlobalVariable| {
+            variable.fmt_value(&memory[variable.value_range().unwrap()])
+        };
+        assert_eq!(value(&variable("u16", 1, 2)), "513");
+        assert_eq!(value(&variable("bool", 1, 1)), "true");
+        assert_eq!(value(&variable("*u8", 4, 4)), "0x7060504");
+        assert_eq!(value(&variable("Watchers", 0, 3)), "00 01 02");
+        assert_eq!(
+            value(&variable("[u8]", 48, 20)),
+            "30 31 32 33 34 35 36 37 38 39 3A 3B 3C 3D 3E 3F …",
+        );
+        assert_eq!(variable("u32", 62, 4).value_range(), Some(62..66));
+        assert_eq!(variable("[u8]", 8, 100).value_range(), Some(8..24));
+    }
+}
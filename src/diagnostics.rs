@@ -0,0 +1,138 @@
+use crate::{
+    fmt_bytes,
+    module_info::{self, ModuleInfo},
+};
+
+/// The exports the runtime requires the auto splitter to have, along with
+/// their kinds.
+const REQUIRED_EXPORTS: [(&str, &str); 2] = [("update", "function"), ("memory", "memory")];
+/// Data segments above this size usually embed files or large tables that
+/// could be loaded or computed at runtime instead.
+const LARGE_DATA_SEGMENT: usize = 1 << 20;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Severity::Info => "Info",
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+        }
+    }
+}
+
+/// A likely mistake in the auto splitter, found by looking at its module.
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub title: &'static str,
+    pub message: String,
+}
+
+/// Looks for common mistakes in the module of an auto splitter. The errors
+/// prevent it from running at all. The diagnostics are sorted by their
+/// severity, the most severe first.
+pub fn lint(info: &ModuleInfo, optimize: bool) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (name, kind) in REQUIRED_EXPORTS {
+        if !info
+            .exports
+            .iter()
+            .any(|export| &*export.name == name && export.kind == kind)
+        {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                title: "Missing Export",
+                message: format!(
+                    "The auto splitter doesn't export the {kind} \"{name}\", which the runtime requires."
+                ),
+            });
+        }
+    }
+
+    // The known runtime functions may lag behind the runtime, so only the
+    // instantiation can tell for sure whether an import is missing.
+    for (module, import) in info.unknown_imports() {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            title: "Unknown Import",
+            message: if module == module_info::RUNTIME_MODULE {
+                format!(
+                    "The {} \"{}\" is not known to be provided by the runtime. {} If the auto splitter fails to instantiate, it was either removed from the runtime or the debugger needs to be updated.",
+                    import.kind, import.name, info.unknown_import_origin(&import.name),
+                )
+            } else {
+                format!(
+                    "The {} \"{}\" is imported from the unknown module \"{module}\".",
+                    import.kind, import.name,
+                )
+            },
+        });
+    }
+
+    for (index, &size) in info.data_segments.iter().enumerate() {
+        if size > LARGE_DATA_SEGMENT {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                title: "Large Data Segment",
+                message: format!(
+                    "Data segment {index} is {} large. It likely embeds a file or a table that could be loaded or computed at runtime instead.",
+                    fmt_bytes(size as u64),
+                ),
+            });
+        }
+    }
+
+    let has_debug_info = info
+        .custom_sections
+        .iter()
+        .any(|section| &*section.name == ".debug_info");
+    if !optimize && !has_debug_info {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Info,
+            title: "No Debug Info",
+            message: "The optimizations are turned off for debugging, but the auto splitter doesn't contain any debug info, so variables, source locations and backtraces can't be shown. Build it with debug info to make use of them.".into(),
+        });
+    }
+
+    diagnostics.sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.severity));
+    diagnostics
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lint() {
+        #[rustfmt::skip]
+        let data = [
+            // Header
+            0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00,
+            // Type section: () -> ()
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            // Function section: a single function of type 0
+            0x03, 0x02, 0x01, 0x00,
+            // Export section: update: func 0
+            0x07, 0x0A, 0x01, 0x06, b'u', b'p', b'd', b'a', b't', b'e', 0x00, 0x00,
+            // Code section: an empty function body
+            0x0A, 0x04, 0x01, 0x02, 0x00, 0x0B,
+        ];
+        let info = ModuleInfo::parse(&data).unwrap();
+
+        let diagnostics = lint(&info, true);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(diagnostics[0].message.contains("\"memory\""));
+
+        let diagnostics = lint(&info, false);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[1].title, "No Debug Info");
+    }
+}
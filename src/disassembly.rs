@@ -0,0 +1,106 @@
+/// The WebAssembly text format of a module, split up into its functions.
+pub struct Disassembly {
+    pub functions: Vec<Function>,
+    pub query: String,
+    /// The query the matches were searched for and the indices of the
+    /// functions that matched it.
+    matches: Option<(String, Vec<usize>)>,
+}
+
+pub struct Function {
+    pub name: Box<str>,
+    pub text: String,
+}
+
+impl Disassembly {
+    pub fn new(wasm: &[u8]) -> anyhow::Result<Self> {
+        let text = wasmprinter::print_bytes(wasm)?;
+        Ok(Self {
+            functions: split_functions(&text),
+            query: String::new(),
+            matches: None,
+        })
+    }
+
+    /// The indices of the functions whose name or text contains the query.
+    pub fn search(&mut self) -> &[usize] {
+        if !matches!(&self.matches, Some((query, _)) if *query == self.query) {
+            let matches = self
+                .functions
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| f.name.contains(&*self.query) || f.text.contains(&*self.query))
+                .map(|(index, _)| index)
+                .collect();
+            self.matches = Some((self.query.clone(), matches));
+        }
+        self.matches.as_ref().map_or(&[], |(_, matches)| matches)
+    }
+}
+
+fn split_functions(text: &str) -> Vec<Function> {
+    let mut functions = Vec::new();
+    let mut current: Option<Function> = None;
+
+    for line in text.lines() {
+        // Items of the module are indented by exactly two spaces.
+        let is_item = line.starts_with("  (") || line == "  )" || line == ")";
+        if let Some(function) = &mut current {
+            if !is_item || line == "  )" {
+                function
+                    .text
+                    .push_str(line.strip_prefix("  ").unwrap_or(line));
+                function.text.push('\n');
+                if line == "  )" {
+                    functions.extend(current.take());
+                }
+                continue;
+            }
+            functions.extend(current.take());
+        }
+        if let Some(rest) = line.strip_prefix("  (func ") {
+            let name = match rest.split_whitespace().next() {
+                Some(name) if name.starts_with('$') || name.starts_with("(;") => name,
+                _ => "(;?;)",
+            };
+            current = Some(Function {
+                name: name.into(),
+                text: format!("{}\n", &line[2..]),
+            });
+        }
+    }
+    functions.extend(current);
+
+    functions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_functions() {
+        let text = "\
+(module
+  (type (;0;) (func))
+  (func $first (;0;) (type 0)
+    nop
+  )
+  (func (;1;) (type 0)
+    i32.const 1
+    drop
+  )
+  (export \"update\" (func 1))
+)
+";
+        let functions = split_functions(text);
+        assert_eq!(functions.len(), 2);
+        assert_eq!(&*functions[0].name, "$first");
+        assert_eq!(functions[0].text, "(func $first (;0;) (type 0)\n  nop\n)\n");
+        assert_eq!(&*functions[1].name, "(;1;)");
+        assert_eq!(
+            functions[1].text,
+            "(func (;1;) (type 0)\n  i32.const 1\n  drop\n)\n",
+        );
+    }
+}
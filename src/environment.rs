@@ -0,0 +1,45 @@
+use std::env::consts;
+
+pub const DEBUGGER_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const LIVESPLIT_AUTO_SPLITTING_VERSION: &str = env!("LIVESPLIT_AUTO_SPLITTING_VERSION");
+pub const WASMTIME_VERSION: &str = env!("WASMTIME_VERSION");
+
+pub fn os() -> String {
+    format!("{} ({})", consts::OS, consts::ARCH)
+}
+
+/// The CPU features relevant to the code generated by wasmtime that are
+/// available at runtime.
+#[allow(unused_mut)]
+pub fn cpu_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        macro_rules! detect {
+            ($($feature:tt),*) => {
+                $(if std::arch::is_x86_feature_detected!($feature) {
+                    features.push($feature);
+                })*
+            };
+        }
+        detect!(
+            "sse3", "ssse3", "sse4.1", "sse4.2", "popcnt", "lzcnt", "bmi1", "bmi2", "fma", "avx",
+            "avx2", "avx512f"
+        );
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        macro_rules! detect {
+            ($($feature:tt),*) => {
+                $(if std::arch::is_aarch64_feature_detected!($feature) {
+                    features.push($feature);
+                })*
+            };
+        }
+        detect!("neon", "lse", "crc", "fp16");
+    }
+
+    features
+}
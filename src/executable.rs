@@ -0,0 +1,131 @@
+use std::{fs::File, io::Read, path::Path};
+
+/// The architecture an executable got compiled for, as determined by its
+/// header.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Architecture {
+    pub name: &'static str,
+    pub pointer_size: u8,
+}
+
+impl Architecture {
+    pub fn read(path: &Path) -> Option<Self> {
+        let mut header = Vec::new();
+        File::open(path)
+            .ok()?
+            .take(4096)
+            .read_to_end(&mut header)
+            .ok()?;
+        Self::parse(&header)
+    }
+
+    /// Parses the header of an ELF, PE or Mach-O file.
+    pub fn parse(header: &[u8]) -> Option<Self> {
+        let u16_le = |offset: usize| {
+            Some(u16::from_le_bytes(
+                header.get(offset..offset + 2)?.try_into().ok()?,
+            ))
+        };
+        let u32_le = |offset: usize| {
+            Some(u32::from_le_bytes(
+                header.get(offset..offset + 4)?.try_into().ok()?,
+            ))
+        };
+
+        if header.starts_with(b"\x7FELF") {
+            let pointer_size = match *header.get(4)? {
+                1 => 4,
+                2 => 8,
+                _ => return None,
+            };
+            let machine = match header.get(5)? {
+                1 => u16_le(18)?,
+                _ => u16::from_be_bytes(header.get(18..20)?.try_into().ok()?),
+            };
+            let name = match machine {
+                0x03 => "x86",
+                0x3E => "x86-64",
+                0x28 => "ARM",
+                0xB7 => "AArch64",
+                _ => "Unknown",
+            };
+            Some(Self { name, pointer_size })
+        } else if header.starts_with(b"MZ") {
+            let pe = u32_le(0x3C)? as usize;
+            if header.get(pe..pe + 4)? != b"PE\0\0" {
+                return None;
+            }
+            let (name, pointer_size) = match u16_le(pe + 4)? {
+                0x014C => ("x86", 4),
+                0x8664 => ("x86-64", 8),
+                0x01C4 => ("ARM", 4),
+                0xAA64 => ("AArch64", 8),
+                _ => return None,
+            };
+            Some(Self { name, pointer_size })
+        } else {
+            let (pointer_size, cpu_type) = match u32_le(0)? {
+                0xFEEDFACE => (4, u32_le(4)?),
+                0xFEEDFACF => (8, u32_le(4)?),
+                _ => return None,
+            };
+            // The 64-bit variants have the ABI64 flag set.
+            let name = match cpu_type & !0x0100_0000 {
+                7 => {
+                    if pointer_size == 8 {
+                        "x86-64"
+                    } else {
+                        "x86"
+                    }
+                }
+                12 => {
+                    if pointer_size == 8 {
+                        "AArch64"
+                    } else {
+                        "ARM"
+                    }
+                }
+                _ => "Unknown",
+            };
+            Some(Self { name, pointer_size })
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        8 * self.pointer_size as u32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let mut elf = b"\x7FELF\x02\x01\x01".to_vec();
+        elf.resize(18, 0);
+        elf.extend_from_slice(&0x3Eu16.to_le_bytes());
+        assert_eq!(
+            Architecture::parse(&elf),
+            Some(Architecture {
+                name: "x86-64",
+                pointer_size: 8
+            }),
+        );
+
+        let mut pe = b"MZ".to_vec();
+        pe.resize(0x3C, 0);
+        pe.extend_from_slice(&0x40u32.to_le_bytes());
+        pe.extend_from_slice(b"PE\0\0");
+        pe.extend_from_slice(&0x014Cu16.to_le_bytes());
+        assert_eq!(
+            Architecture::parse(&pe),
+            Some(Architecture {
+                name: "x86",
+                pointer_size: 4
+            }),
+        );
+
+        assert_eq!(Architecture::parse(b"#!/bin/sh"), None);
+    }
+}
@@ -0,0 +1,63 @@
+/// Why the auto splitter failed when running non-interactively. Each failure
+/// has its own exit code, so build pipelines can tell them apart. The codes
+/// start at 3, as 1 is used for generic errors and 2 for invalid arguments.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Failure {
+    Compile,
+    Instantiation,
+    Trap,
+    WatchdogTimeout,
+    /// The auto splitter registers invalid settings.
+    Validation,
+    /// The timer events of the auto splitter don't match the scenario.
+    Scenario,
+}
+
+impl Failure {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Failure::Compile => 3,
+            Failure::Instantiation => 4,
+            Failure::Trap => 5,
+            Failure::WatchdogTimeout => 6,
+            Failure::Validation => 7,
+            Failure::Scenario => 8,
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Failure::Compile => "The auto splitter failed to compile.",
+            Failure::Instantiation => "The auto splitter failed to instantiate.",
+            Failure::Trap => "The auto splitter trapped.",
+            Failure::WatchdogTimeout => {
+                "The auto splitter got interrupted for exceeding the maximum tick duration."
+            }
+            Failure::Validation => "The auto splitter failed validation.",
+            Failure::Scenario => "The timer events didn't match the scenario.",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        let failures = [
+            Failure::Compile,
+            Failure::Instantiation,
+            Failure::Trap,
+            Failure::WatchdogTimeout,
+            Failure::Validation,
+            Failure::Scenario,
+        ];
+        for (i, a) in failures.iter().enumerate() {
+            assert!(a.exit_code() > 2);
+            for b in &failures[i + 1..] {
+                assert_ne!(a.exit_code(), b.exit_code());
+            }
+        }
+    }
+}
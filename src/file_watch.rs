@@ -0,0 +1,149 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher as _};
+
+/// How long a file needs to stay unchanged before it is considered fully
+/// written.
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(300);
+
+/// Detects modifications of a file, waiting for the file to stop changing
+/// before reporting them.
+#[derive(Default)]
+pub struct FileWatch {
+    /// The modification time of the version of the file that was loaded.
+    loaded: Option<SystemTime>,
+    /// The latest modification time and when it was first seen.
+    pending: Option<(SystemTime, Instant)>,
+    /// Notifies about changes of the file. If the file system can't be
+    /// watched, the modification time of the file is checked on every poll
+    /// instead.
+    watcher: Option<Watcher>,
+}
+
+struct Watcher {
+    path: PathBuf,
+    changed: Arc<AtomicBool>,
+    _watcher: RecommendedWatcher,
+}
+
+impl Watcher {
+    fn new(path: &Path) -> notify::Result<Self> {
+        let changed = Arc::new(AtomicBool::new(true));
+        let mut watcher = notify::recommended_watcher({
+            let changed = changed.clone();
+            let file_name = path.file_name().map(ToOwned::to_owned);
+            move |event: notify::Result<notify::Event>| {
+                // Errors may mean that events got lost, so they are treated
+                // as changes too.
+                if event.map_or(true, |event| {
+                    event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name() == file_name.as_deref())
+                }) {
+                    changed.store(true, atomic::Ordering::Relaxed);
+                }
+            }
+        })?;
+
+        // Watching the directory instead of the file itself keeps working
+        // when the file gets replaced rather than written to.
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            path: path.to_owned(),
+            changed,
+            _watcher: watcher,
+        })
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).ok().and_then(|m| m.modified().ok())
+}
+
+impl FileWatch {
+    /// Marks the current version of the file as loaded.
+    pub fn loaded(&mut self, path: &Path) {
+        self.loaded = modified_time(path);
+        self.pending = None;
+        if self.watcher.as_ref().is_none_or(|w| w.path != path) {
+            self.watcher = Watcher::new(path).ok();
+        }
+    }
+
+    /// Checks the file for modifications. Returns `true` once the file got
+    /// modified and stayed unchanged for a while.
+    pub fn poll(&mut self, path: &Path, now: Instant) -> bool {
+        let changed = match &self.watcher {
+            Some(watcher) if watcher.path == path => {
+                watcher.changed.swap(false, atomic::Ordering::Relaxed)
+            }
+            _ => true,
+        };
+        if !changed && self.pending.is_none() {
+            return false;
+        }
+        self.update(modified_time(path), now)
+    }
+
+    fn update(&mut self, modified: Option<SystemTime>, now: Instant) -> bool {
+        let Some(modified) = modified.filter(|&m| Some(m) > self.loaded) else {
+            self.pending = None;
+            return false;
+        };
+        match self.pending {
+            Some((pending, since)) if pending == modified => {
+                if now.duration_since(since) < DEBOUNCE_DURATION {
+                    return false;
+                }
+                self.loaded = Some(modified);
+                self.pending = None;
+                true
+            }
+            _ => {
+                self.pending = Some((modified, now));
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_debounce() {
+        let mut watch = FileWatch::default();
+        let first = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let second = first + Duration::from_secs(1);
+        let now = Instant::now();
+
+        assert!(!watch.update(Some(first), now));
+        assert!(!watch.update(Some(second), now + DEBOUNCE_DURATION));
+        assert!(!watch.update(Some(second), now + DEBOUNCE_DURATION));
+        assert!(watch.update(Some(second), now + 2 * DEBOUNCE_DURATION));
+        assert!(!watch.update(Some(second), now + 3 * DEBOUNCE_DURATION));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let mut watch = FileWatch::default();
+        let now = Instant::now();
+        assert!(!watch.update(None, now));
+        assert!(!watch.update(None, now + DEBOUNCE_DURATION));
+    }
+}
@@ -0,0 +1,65 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How long the debugger itself takes to draw its user interface, to tell
+/// hitches of the debugger apart from the ones of the auto splitter.
+#[derive(Default)]
+pub struct FrameStats {
+    /// When the frames of the last second started.
+    frames: VecDeque<Instant>,
+    avg_frame_secs: f64,
+    pub last_frame: Duration,
+    pub slowest_frame: Duration,
+}
+
+impl FrameStats {
+    /// Records a frame starting. The frame time is the CPU time eframe spent
+    /// on the previous frame, if it's known.
+    pub fn frame(&mut self, now: Instant, frame_time: Option<f32>) {
+        while self
+            .frames
+            .front()
+            .is_some_and(|&start| now.duration_since(start) >= Duration::from_secs(1))
+        {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(now);
+
+        if let Some(frame_time) = frame_time {
+            self.last_frame = Duration::from_secs_f32(frame_time);
+            self.slowest_frame = self.slowest_frame.max(self.last_frame);
+            self.avg_frame_secs = 0.95 * self.avg_frame_secs + 0.05 * frame_time as f64;
+        }
+    }
+
+    /// How often the user interface got repainted within the last second.
+    pub fn repaints_per_second(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn avg_frame_time(&self) -> Duration {
+        Duration::from_secs_f64(self.avg_frame_secs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_repaints_per_second() {
+        let mut stats = FrameStats::default();
+        let start = Instant::now();
+        for i in 0..120 {
+            stats.frame(start + Duration::from_millis(i * 10), Some(0.002));
+        }
+        assert_eq!(stats.repaints_per_second(), 100);
+        assert_eq!(stats.last_frame, Duration::from_secs_f32(0.002));
+
+        stats.frame(start + Duration::from_secs(5), Some(0.02));
+        assert_eq!(stats.repaints_per_second(), 1);
+        assert_eq!(stats.slowest_frame, Duration::from_secs_f32(0.02));
+    }
+}
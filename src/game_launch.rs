@@ -0,0 +1,79 @@
+use std::{
+    io,
+    path::PathBuf,
+    process::{Command, ExitStatus},
+    thread,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How to launch the game an auto splitter is for.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameLaunch {
+    pub executable: PathBuf,
+    /// The command line arguments, separated by whitespace. Arguments
+    /// containing whitespace can be quoted.
+    pub arguments: String,
+    /// Whether to launch the game whenever the auto splitter gets loaded.
+    pub launch_on_load: bool,
+}
+
+impl GameLaunch {
+    /// Launches the game and returns its process id. The game is waited for on
+    /// a separate thread, so it gets reaped once it exits instead of lingering
+    /// as a zombie process. The callback is called with how it exited.
+    pub fn launch(
+        &self,
+        on_exit: impl FnOnce(io::Result<ExitStatus>) + Send + 'static,
+    ) -> io::Result<u32> {
+        let mut command = Command::new(&self.executable);
+        command.args(split_arguments(&self.arguments));
+        // Lots of games expect to be started from their own directory.
+        if let Some(directory) = self.executable.parent() {
+            if !directory.as_os_str().is_empty() {
+                command.current_dir(directory);
+            }
+        }
+        let mut child = command.spawn()?;
+        let id = child.id();
+        thread::Builder::new()
+            .name("Game".into())
+            .spawn(move || on_exit(child.wait()))?;
+        Ok(id)
+    }
+}
+
+fn split_arguments(arguments: &str) -> Vec<String> {
+    let mut split = Vec::new();
+    let mut current = None::<String>;
+    let mut quote = None;
+    for c in arguments.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.get_or_insert_with(String::new).push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            None if c.is_whitespace() => split.extend(current.take()),
+            None => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    split.extend(current);
+    split
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_arguments() {
+        assert_eq!(
+            split_arguments(r#"-windowed  --config "My Config.ini" '' -x"#),
+            ["-windowed", "--config", "My Config.ini", "", "-x"],
+        );
+        assert!(split_arguments("   ").is_empty());
+    }
+}
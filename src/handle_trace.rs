@@ -0,0 +1,78 @@
+use std::{cmp::Reverse, collections::VecDeque};
+
+use livesplit_auto_splitting::time::OffsetDateTime;
+
+/// How many growths are kept before the oldest ones get discarded.
+const CAPACITY: usize = 1000;
+
+/// A tick that ended with more handles than it started with.
+pub struct Growth {
+    pub tick: u64,
+    pub time: OffsetDateTime,
+    /// How many handles were created and not freed within the tick.
+    pub count: u64,
+}
+
+/// Traces the ticks in which the auto splitter's handles grow. The runtime
+/// only reports the total amount of handles, so the handles can't be
+/// attributed to anything more specific than the tick they were created in.
+#[derive(Default)]
+pub struct HandleTrace {
+    pub enabled: bool,
+    pub growths: VecDeque<Growth>,
+}
+
+impl HandleTrace {
+    pub fn record(&mut self, before: u64, after: u64, tick: u64, time: OffsetDateTime) {
+        if !self.enabled || after <= before {
+            return;
+        }
+        if self.growths.len() >= CAPACITY {
+            self.growths.pop_front();
+        }
+        self.growths.push_back(Growth {
+            tick,
+            time,
+            count: after - before,
+        });
+    }
+
+    /// The amount of handles created across all the recorded growths.
+    pub fn total(&self) -> u64 {
+        self.growths.iter().map(|growth| growth.count).sum()
+    }
+
+    /// The growths with the most handles created first. Ticks creating the
+    /// same amount of handles are ordered from newest to oldest.
+    pub fn top(&self) -> Vec<&Growth> {
+        let mut growths = self.growths.iter().rev().collect::<Vec<_>>();
+        growths.sort_by_key(|growth| Reverse(growth.count));
+        growths
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let time = OffsetDateTime::UNIX_EPOCH;
+        let mut trace = HandleTrace::default();
+        trace.record(1, 2, 1, time);
+        assert!(trace.growths.is_empty());
+
+        trace.enabled = true;
+        trace.record(1, 2, 2, time);
+        trace.record(2, 2, 3, time);
+        trace.record(2, 1, 4, time);
+        trace.record(1, 4, 5, time);
+        trace.record(4, 5, 6, time);
+
+        assert_eq!(trace.total(), 5);
+        assert_eq!(
+            trace.top().iter().map(|g| g.tick).collect::<Vec<_>>(),
+            [5, 6, 2],
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+
+/// A call of one of the timer's host functions.
+#[derive(Debug, PartialEq)]
+pub struct Call {
+    pub name: &'static str,
+    /// Distinguishes calls of the same function that are about different
+    /// things, like the key of a variable.
+    pub key: Option<Box<str>>,
+    pub args: Box<str>,
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.name, self.args)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Change<'a> {
+    New(&'a Call),
+    Missing(&'a Call),
+    Changed { before: &'a Call, after: &'a Call },
+}
+
+/// The host calls of the current tick and the one before it.
+#[derive(Default)]
+pub struct HostCalls {
+    pub previous: Vec<Call>,
+    pub current: Vec<Call>,
+}
+
+impl HostCalls {
+    pub fn start_tick(&mut self) {
+        std::mem::swap(&mut self.previous, &mut self.current);
+        self.current.clear();
+    }
+
+    pub fn record(&mut self, name: &'static str, key: Option<&str>, args: String) {
+        self.current.push(Call {
+            name,
+            key: key.map(Into::into),
+            args: args.into(),
+        });
+    }
+
+    /// Returns how the calls of the current tick differ from the ones of the
+    /// previous tick, along with how many calls stayed the same.
+    pub fn diff(&self) -> (Vec<Change<'_>>, usize) {
+        diff(&self.previous, &self.current)
+    }
+}
+
+/// Matches up the calls of the same function with the same key in the order
+/// they were made.
+fn diff<'a>(previous: &'a [Call], current: &'a [Call]) -> (Vec<Change<'a>>, usize) {
+    let mut matched = vec![false; previous.len()];
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+    for after in current {
+        let before = previous.iter().enumerate().position(|(i, before)| {
+            !matched[i] && before.name == after.name && before.key == after.key
+        });
+        match before {
+            Some(i) => {
+                matched[i] = true;
+                if previous[i].args == after.args {
+                    unchanged += 1;
+                } else {
+                    changes.push(Change::Changed {
+                        before: &previous[i],
+                        after,
+                    });
+                }
+            }
+            None => changes.push(Change::New(after)),
+        }
+    }
+    changes.extend(
+        previous
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(before, _)| Change::Missing(before)),
+    );
+    (changes, unchanged)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let mut calls = HostCalls::default();
+        calls.record("timer_get_state", None, "Running".into());
+        calls.record("timer_set_variable", Some("IGT"), "\"IGT\", \"1\"".into());
+        calls.record(
+            "timer_set_variable",
+            Some("Level"),
+            "\"Level\", \"1\"".into(),
+        );
+        calls.start_tick();
+        calls.record("timer_get_state", None, "Running".into());
+        calls.record("timer_set_variable", Some("IGT"), "\"IGT\", \"2\"".into());
+        calls.record("timer_split", None, String::new());
+
+        let (changes, unchanged) = calls.diff();
+        assert_eq!(unchanged, 1);
+        assert_eq!(
+            changes,
+            [
+                Change::Changed {
+                    before: &calls.previous[1],
+                    after: &calls.current[1],
+                },
+                Change::New(&calls.current[2]),
+                Change::Missing(&calls.previous[2]),
+            ],
+        );
+        assert_eq!(
+            calls.current[1].to_string(),
+            "timer_set_variable(\"IGT\", \"2\")"
+        );
+    }
+}
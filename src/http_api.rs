@@ -0,0 +1,530 @@
+use std::{
+    io::{self, Read},
+    net::{IpAddr, SocketAddr, TcpListener},
+    sync::{
+        atomic::{self, AtomicUsize},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use livesplit_auto_splitting::settings;
+use socket2::{Domain, Protocol, Socket, Type};
+use tiny_http::{Header, Method, Response, Server};
+
+/// The port the HTTP API listens on by default.
+pub const DEFAULT_PORT: u16 = 9087;
+/// Bodies are only ever small JSON values, so anything larger is rejected.
+const MAX_BODY_SIZE: usize = 64 << 10;
+/// Requests whose body took longer than this to arrive are rejected.
+const BODY_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a single read from a connection may block. Without it, a client
+/// that stops sending would block the thread reading its request forever.
+/// The server reads small bodies itself before handing out the request, so
+/// for those the connection just gets closed. Idle connections that are kept
+/// alive get closed after it as well.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// How many bodies may be read at the same time. Each of them is read on its
+/// own thread, so a client that never sends its body only blocks that thread.
+const MAX_PENDING_BODIES: usize = 8;
+
+pub enum Endpoint {
+    Timer,
+    Variables,
+    Stats,
+    /// The log messages, starting at the index.
+    Logs {
+        since: usize,
+    },
+    Settings,
+    Reload,
+    Restart,
+    Kill,
+    SetSetting {
+        key: Box<str>,
+        value: settings::Value,
+    },
+}
+
+/// An HTTP server for querying the state of the debugger and
+/// controlling it. The requests are received and parsed on a separate thread,
+/// but handled on the UI thread, so they can access all the state of the
+/// debugger.
+pub struct HttpApi {
+    server: Arc<Server>,
+    requests: mpsc::Receiver<ApiRequest>,
+    pub address: SocketAddr,
+    /// The token that requests need to send as a bearer token. It's only
+    /// required if the API is reachable from other machines.
+    pub token: Option<Box<str>>,
+}
+
+pub struct ApiRequest {
+    request: tiny_http::Request,
+    pub endpoint: Endpoint,
+}
+
+impl HttpApi {
+    /// Starts listening on the address. Only the local machine can reach the
+    /// API unless a different address than the loopback address is used, in
+    /// which case a token is generated that the requests need to send. The
+    /// callback is called whenever a request is ready to be handled.
+    pub fn start(
+        ip: IpAddr,
+        port: u16,
+        on_request: impl Fn() + Send + Sync + 'static,
+    ) -> io::Result<Self> {
+        let address = SocketAddr::new(ip, port);
+        let server =
+            Arc::new(Server::from_listener(listen(address)?, None).map_err(io::Error::other)?);
+        let token = (!ip.is_loopback()).then(generate_token).transpose()?;
+        let (sender, requests) = mpsc::channel();
+
+        let access = Access {
+            loopback: ip.is_loopback(),
+            token: token.clone(),
+        };
+        let worker_server = server.clone();
+        thread::Builder::new()
+            .name("HTTP API".into())
+            .spawn(move || {
+                let pending = Arc::new(AtomicUsize::new(0));
+                let on_request = Arc::new(on_request);
+                while let Ok(request) = worker_server.recv() {
+                    accept(request, &access, &pending, &sender, &on_request);
+                }
+            })?;
+
+        Ok(Self {
+            server,
+            requests,
+            address,
+            token,
+        })
+    }
+
+    /// Returns the next request to handle if there is one. Invalid requests
+    /// are responded to directly by the server's thread.
+    pub fn poll(&self) -> Option<ApiRequest> {
+        self.requests.try_recv().ok()
+    }
+}
+
+impl Drop for HttpApi {
+    fn drop(&mut self) {
+        // Stops the thread receiving the requests, which drops the last other
+        // reference to the server.
+        self.server.unblock();
+    }
+}
+
+impl ApiRequest {
+    pub fn respond(self, status: u16, body: serde_json::Value) {
+        respond(self.request, status, body);
+    }
+}
+
+/// Who is allowed to use the API.
+struct Access {
+    loopback: bool,
+    token: Option<Box<str>>,
+}
+
+fn accept(
+    mut request: tiny_http::Request,
+    access: &Access,
+    pending: &Arc<AtomicUsize>,
+    sender: &mpsc::Sender<ApiRequest>,
+    on_request: &Arc<impl Fn() + Send + Sync + 'static>,
+) {
+    if let Err((status, message)) = check_access(request.method(), request.headers(), access) {
+        return respond(request, status, serde_json::json!({ "error": message }));
+    }
+    if request.body_length().is_some_and(|len| len > MAX_BODY_SIZE) {
+        return respond(
+            request,
+            413,
+            serde_json::json!({ "error": "The body of the request is too large." }),
+        );
+    }
+
+    let has_body = match request.body_length() {
+        Some(len) => len != 0,
+        None => find_header(request.headers(), "Transfer-Encoding").is_some(),
+    };
+    if !has_body {
+        let endpoint = route(request.method(), request.url(), "");
+        return forward(request, endpoint, sender, &**on_request);
+    }
+
+    if pending.fetch_add(1, atomic::Ordering::Relaxed) >= MAX_PENDING_BODIES {
+        pending.fetch_sub(1, atomic::Ordering::Relaxed);
+        return respond(
+            request,
+            503,
+            serde_json::json!({ "error": "Too many requests are in progress." }),
+        );
+    }
+    let (thread_pending, sender, on_request) =
+        (pending.clone(), sender.clone(), on_request.clone());
+    let spawned = thread::Builder::new()
+        .name("HTTP API Request".into())
+        .spawn(move || {
+            let mut body = String::new();
+            let read = Deadline {
+                reader: request.as_reader(),
+                deadline: Instant::now() + BODY_TIMEOUT,
+            }
+            .take(MAX_BODY_SIZE as u64 + 1)
+            .read_to_string(&mut body);
+            let endpoint = match read {
+                // The socket reports its read timeout as either of them,
+                // depending on the platform.
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+                    ) =>
+                {
+                    Err((408, "The body of the request took too long to arrive."))
+                }
+                Err(_) => Err((400, "The body of the request is invalid.")),
+                Ok(_) if body.len() > MAX_BODY_SIZE => {
+                    Err((413, "The body of the request is too large."))
+                }
+                Ok(_) => route(request.method(), request.url(), &body),
+            };
+            forward(request, endpoint, &sender, &*on_request);
+            thread_pending.fetch_sub(1, atomic::Ordering::Relaxed);
+        });
+    if spawned.is_err() {
+        pending.fetch_sub(1, atomic::Ordering::Relaxed);
+    }
+}
+
+/// Creates the listener of the server with a read timeout, which the
+/// connections it accepts inherit, as the server has no way to set one.
+fn listen(address: SocketAddr) -> io::Result<TcpListener> {
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::STREAM,
+        Some(Protocol::TCP),
+    )?;
+    socket.set_read_timeout(Some(READ_TIMEOUT))?;
+    // Like the standard library does, so restarting the API doesn't fail
+    // while the connections of the previous one linger.
+    #[cfg(not(windows))]
+    socket.set_reuse_address(true)?;
+    socket.bind(&address.into())?;
+    socket.listen(128)?;
+    Ok(socket.into())
+}
+
+/// Fails reads once the deadline passed. As each read blocks for at most the
+/// read timeout, a body that arrives too slowly is given up on shortly after
+/// the deadline.
+struct Deadline<R> {
+    reader: R,
+    deadline: Instant,
+}
+
+impl<R: Read> Read for Deadline<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if Instant::now() >= self.deadline {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.reader.read(buf)
+    }
+}
+
+/// Hands the request over to the UI thread if it's valid and responds to it
+/// directly otherwise.
+fn forward(
+    request: tiny_http::Request,
+    endpoint: Result<Endpoint, (u16, &'static str)>,
+    sender: &mpsc::Sender<ApiRequest>,
+    on_request: &impl Fn(),
+) {
+    match endpoint {
+        Ok(endpoint) => {
+            if sender.send(ApiRequest { request, endpoint }).is_ok() {
+                on_request();
+            }
+        }
+        Err((status, message)) => respond(request, status, serde_json::json!({ "error": message })),
+    }
+}
+
+fn respond(request: tiny_http::Request, status: u16, body: serde_json::Value) {
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap());
+    // The client disconnecting is none of our concern.
+    let _ = request.respond(response);
+}
+
+fn find_header<'a>(headers: &'a [Header], name: &'static str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+/// Makes sure that web pages can't use the API. Browsers send an `Origin`
+/// header with their cross-site requests and can't send JSON to other sites
+/// without asking them first, which the API never allows. Checking the `Host`
+/// protects against web pages that make their own domain resolve to the
+/// loopback address.
+fn check_access(
+    method: &Method,
+    headers: &[Header],
+    access: &Access,
+) -> Result<(), (u16, &'static str)> {
+    if find_header(headers, "Origin").is_some() {
+        return Err((403, "Requests from web pages are not allowed."));
+    }
+
+    if access.loopback {
+        let host = find_header(headers, "Host").unwrap_or_default();
+        let host = match host.strip_prefix('[') {
+            Some(rest) => rest.split_once(']').map_or(rest, |(host, _)| host),
+            None => host.split_once(':').map_or(host, |(host, _)| host),
+        };
+        let is_loopback = host.eq_ignore_ascii_case("localhost")
+            || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback());
+        if !is_loopback {
+            return Err((403, "The host of the request is not the local machine."));
+        }
+    }
+
+    if let Some(token) = &access.token {
+        let authorized = find_header(headers, "Authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|value| constant_time_eq(value.as_bytes(), token.as_bytes()));
+        if !authorized {
+            return Err((401, "The request needs to send the token of the API."));
+        }
+    }
+
+    if *method == Method::Post
+        && !find_header(headers, "Content-Type")
+            .is_some_and(|value| value.starts_with("application/json"))
+    {
+        return Err((
+            415,
+            "The Content-Type of the request needs to be application/json.",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Generates a random token from the randomness of the operating system.
+fn generate_token() -> io::Result<Box<str>> {
+    let mut bytes = [0; 16];
+    getrandom::getrandom(&mut bytes).map_err(io::Error::other)?;
+    Ok(bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>()
+        .into())
+}
+
+/// Compares the bytes in a time that only depends on their length, so the
+/// token can't be guessed byte by byte from how long the responses take.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn route(method: &Method, url: &str, body: &str) -> Result<Endpoint, (u16, &'static str)> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let path = path.trim_end_matches('/');
+
+    if let Some(key) = path.strip_prefix("/settings/") {
+        if *method != Method::Post {
+            return Err((405, "Settings can only be changed with POST."));
+        }
+        let value = serde_json::from_str(body)
+            .ok()
+            .and_then(|value| json_to_value(&value))
+            .ok_or((
+                400,
+                "The body needs to be a JSON boolean, number or string.",
+            ))?;
+        return Ok(Endpoint::SetSetting {
+            key: percent_decode(key)
+                .ok_or((400, "The key is invalid."))?
+                .into(),
+            value,
+        });
+    }
+
+    let endpoint = match path {
+        "/timer" => Endpoint::Timer,
+        "/variables" => Endpoint::Variables,
+        "/stats" => Endpoint::Stats,
+        "/logs" => Endpoint::Logs {
+            since: query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("since="))
+                .map_or(Ok(0), str::parse)
+                .map_err(|_| (400, "The since parameter needs to be an index."))?,
+        },
+        "/settings" => Endpoint::Settings,
+        "/reload" => Endpoint::Reload,
+        "/restart" => Endpoint::Restart,
+        "/kill" => Endpoint::Kill,
+        _ => return Err((404, "There is no such endpoint.")),
+    };
+
+    let is_action = matches!(
+        endpoint,
+        Endpoint::Reload | Endpoint::Restart | Endpoint::Kill,
+    );
+    match (method, is_action) {
+        (Method::Get, false) | (Method::Post, true) => Ok(endpoint),
+        (_, false) => Err((405, "This endpoint only supports GET.")),
+        (_, true) => Err((405, "This endpoint only supports POST.")),
+    }
+}
+
+fn json_to_value(value: &serde_json::Value) -> Option<settings::Value> {
+    Some(match value {
+        serde_json::Value::Bool(v) => settings::Value::Bool(*v),
+        serde_json::Value::Number(v) => match v.as_i64() {
+            Some(v) => settings::Value::I64(v),
+            None => settings::Value::F64(v.as_f64()?),
+        },
+        serde_json::Value::String(v) => settings::Value::String(v.as_str().into()),
+        _ => return None,
+    })
+}
+
+fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let [first, remaining @ ..] = rest {
+        if *first == b'%' {
+            let hex = std::str::from_utf8(remaining.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &remaining[2..];
+        } else {
+            bytes.push(*first);
+            rest = remaining;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        assert!(matches!(
+            route(&Method::Get, "/logs?since=12", ""),
+            Ok(Endpoint::Logs { since: 12 }),
+        ));
+        assert!(matches!(
+            route(&Method::Post, "/settings/use%20igt", "true"),
+            Ok(Endpoint::SetSetting { key, value: settings::Value::Bool(true) }) if &*key == "use igt",
+        ));
+        assert!(matches!(route(&Method::Get, "/reload", ""), Err((405, _))));
+        assert!(matches!(
+            route(&Method::Post, "/settings/key", "[]"),
+            Err((400, _))
+        ));
+        assert!(matches!(route(&Method::Get, "/unknown", ""), Err((404, _))));
+    }
+
+    #[test]
+    fn test_token() {
+        let token = generate_token().unwrap();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, generate_token().unwrap());
+        assert!(constant_time_eq(token.as_bytes(), token.as_bytes()));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secrets"));
+    }
+
+    #[test]
+    fn test_deadline() {
+        let mut reader = Deadline {
+            reader: &b"body"[..],
+            deadline: Instant::now() + Duration::from_secs(60),
+        };
+        let mut body = String::new();
+        reader.read_to_string(&mut body).unwrap();
+        assert_eq!(body, "body");
+
+        reader.deadline = Instant::now();
+        let e = reader.read(&mut [0; 4]).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_check_access() {
+        let header = |field: &str, value: &str| Header::from_bytes(field, value).unwrap();
+        let local = Access {
+            loopback: true,
+            token: None,
+        };
+        let json = header("Content-Type", "application/json");
+
+        assert!(check_access(&Method::Get, &[header("Host", "127.0.0.1:9087")], &local).is_ok());
+        assert!(check_access(&Method::Get, &[header("Host", "[::1]:9087")], &local).is_ok());
+        assert!(matches!(
+            check_access(&Method::Get, &[header("Host", "evil.example:9087")], &local),
+            Err((403, _)),
+        ));
+        assert!(matches!(
+            check_access(
+                &Method::Post,
+                &[
+                    header("Host", "localhost"),
+                    header("Origin", "https://evil.example"),
+                    json.clone()
+                ],
+                &local,
+            ),
+            Err((403, _)),
+        ));
+        assert!(matches!(
+            check_access(
+                &Method::Post,
+                &[
+                    header("Host", "localhost"),
+                    header("Content-Type", "text/plain")
+                ],
+                &local
+            ),
+            Err((415, _)),
+        ));
+        assert!(check_access(
+            &Method::Post,
+            &[header("Host", "localhost"), json.clone()],
+            &local
+        )
+        .is_ok());
+
+        let remote = Access {
+            loopback: false,
+            token: Some("secret".into()),
+        };
+        assert!(matches!(
+            check_access(&Method::Get, &[header("Host", "debugger.lan")], &remote),
+            Err((401, _)),
+        ));
+        assert!(check_access(
+            &Method::Get,
+            &[
+                header("Host", "debugger.lan"),
+                header("Authorization", "Bearer secret")
+            ],
+            &remote,
+        )
+        .is_ok());
+    }
+}
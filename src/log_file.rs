@@ -0,0 +1,105 @@
+use std::{
+    fs,
+    io::{self, BufWriter, Write},
+    iter,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+
+/// Appends log messages to a file. The file is written on a separate thread,
+/// so logging never has to wait for the disk.
+pub struct LogFile {
+    path: PathBuf,
+    sender: mpsc::Sender<String>,
+    thread: thread::JoinHandle<()>,
+    /// The error that stopped the thread from writing to the file.
+    error: Arc<Mutex<Option<io::Error>>>,
+}
+
+impl LogFile {
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        let (sender, receiver) = mpsc::channel::<String>();
+        let error = Arc::new(Mutex::new(None));
+        let thread = thread::Builder::new().name("Log File".into()).spawn({
+            let error = error.clone();
+            move || {
+                let mut writer = BufWriter::new(file);
+                while let Ok(line) = receiver.recv() {
+                    // Everything that queued up in the meantime is written
+                    // before flushing.
+                    let result = iter::once(line)
+                        .chain(receiver.try_iter())
+                        .try_for_each(|line| writeln!(writer, "{line}"))
+                        .and_then(|()| writer.flush());
+                    if let Err(e) = result {
+                        *error.lock().unwrap() = Some(e);
+                        return;
+                    }
+                }
+            }
+        })?;
+        Ok(Self {
+            path,
+            sender,
+            thread,
+            error,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Queues a line to be written to the file.
+    pub fn write(&self, line: String) {
+        // The thread only stops after an error, which is reported separately.
+        let _ = self.sender.send(line);
+    }
+
+    /// Returns the error that stopped the file from being written, if there
+    /// was one.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error.lock().unwrap().take()
+    }
+
+    /// Waits for all the queued lines to be written and closes the file.
+    /// Dropping the log file instead closes it in the background.
+    pub fn close(self) -> io::Result<()> {
+        drop(self.sender);
+        let _ = self.thread.join();
+        match self.error.lock().unwrap().take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_write() {
+        let path =
+            std::env::temp_dir().join(format!("asr-debugger-log-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let log_file = LogFile::open(path.clone()).unwrap();
+        log_file.write("first".into());
+        log_file.write("second".into());
+        assert!(log_file.take_error().is_none());
+        log_file.close().unwrap();
+
+        let log_file = LogFile::open(path.clone()).unwrap();
+        log_file.write("third".into());
+        log_file.close().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\nsecond\nthird\n");
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,179 @@
+use livesplit_auto_splitting::LogLevel;
+use regex::Regex;
+
+use crate::{LogMessage, LogSource, LogType};
+
+/// All the log levels, from the most to the least severe.
+pub const LEVELS: [LogLevel; 5] = [
+    LogLevel::Error,
+    LogLevel::Warning,
+    LogLevel::Info,
+    LogLevel::Debug,
+    LogLevel::Trace,
+];
+
+/// All the sources a log message can originate from.
+pub const SOURCES: [LogSource; 5] = [
+    LogSource::Debugger,
+    LogSource::Runtime,
+    LogSource::Timer,
+    LogSource::AutoSplitter,
+    LogSource::Build,
+];
+
+/// Decides which log messages are shown in the Logs tab.
+pub struct LogFilter {
+    pub query: String,
+    pub use_regex: bool,
+    show_levels: [bool; LEVELS.len()],
+    show_sources: [bool; SOURCES.len()],
+    compiled: Option<(String, Result<Regex, regex::Error>)>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            use_regex: false,
+            show_levels: [true; LEVELS.len()],
+            show_sources: [true; SOURCES.len()],
+            compiled: None,
+        }
+    }
+}
+
+fn level_index(level: LogLevel) -> usize {
+    match level {
+        LogLevel::Error => 0,
+        LogLevel::Warning => 1,
+        LogLevel::Info => 2,
+        LogLevel::Debug => 3,
+        LogLevel::Trace => 4,
+    }
+}
+
+fn source_index(source: LogSource) -> usize {
+    match source {
+        LogSource::Debugger => 0,
+        LogSource::Runtime => 1,
+        LogSource::Timer => 2,
+        LogSource::AutoSplitter => 3,
+        LogSource::Build => 4,
+    }
+}
+
+impl LogFilter {
+    /// Whether messages of the given level are shown.
+    pub fn show_level_mut(&mut self, level: LogLevel) -> &mut bool {
+        &mut self.show_levels[level_index(level)]
+    }
+
+    /// Whether messages from the given source are shown.
+    pub fn show_source_mut(&mut self, source: LogSource) -> &mut bool {
+        &mut self.show_sources[source_index(source)]
+    }
+
+    /// Compiles the query as a regular expression if it changed since the last
+    /// call. Returns the error if the query is not a valid regular expression.
+    pub fn prepare(&mut self) -> Option<&regex::Error> {
+        if !self.use_regex || self.query.is_empty() {
+            return None;
+        }
+        if !matches!(&self.compiled, Some((query, _)) if *query == self.query) {
+            self.compiled = Some((self.query.clone(), Regex::new(&self.query)));
+        }
+        match &self.compiled {
+            Some((_, Err(e))) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Whether the message is shown. The messages the auto splitter prints
+    /// itself have no level of their own, so they are only filtered by their
+    /// source.
+    pub fn matches(&self, log: &LogMessage) -> bool {
+        if !self.show_sources[source_index(log.ty.source())] {
+            return false;
+        }
+        if log.ty != LogType::AutoSplitterMessage && !self.show_levels[level_index(log.ty.level())]
+        {
+            return false;
+        }
+        if self.query.is_empty() {
+            return true;
+        }
+        if self.use_regex {
+            match &self.compiled {
+                Some((query, Ok(regex))) if *query == self.query => regex.is_match(&log.message),
+                _ => true,
+            }
+        } else {
+            log.message.contains(&*self.query)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn log(message: &str, ty: LogType) -> LogMessage {
+        LogMessage {
+            time: livesplit_auto_splitting::time::OffsetDateTime::UNIX_EPOCH,
+            tick: 0,
+            message: message.into(),
+            ty,
+            count: 1,
+        }
+    }
+
+    #[test]
+    fn test_substring() {
+        let filter = LogFilter {
+            query: "oad".into(),
+            ..Default::default()
+        };
+        assert!(filter.matches(&log("Loading", LogType::AutoSplitterMessage)));
+        assert!(!filter.matches(&log("Splitted.", LogType::AutoSplitterMessage)));
+    }
+
+    #[test]
+    fn test_regex() {
+        let mut filter = LogFilter {
+            query: "^Split+ed".into(),
+            use_regex: true,
+            ..Default::default()
+        };
+        assert!(filter.prepare().is_none());
+        assert!(filter.matches(&log("Splitted.", LogType::AutoSplitterMessage)));
+        assert!(!filter.matches(&log("Not Splitted.", LogType::AutoSplitterMessage)));
+
+        filter.query = "(".into();
+        assert!(filter.prepare().is_some());
+        assert!(filter.matches(&log("Anything", LogType::AutoSplitterMessage)));
+    }
+
+    #[test]
+    fn test_sources() {
+        let mut filter = LogFilter::default();
+        *filter.show_source_mut(LogSource::Runtime) = false;
+        *filter.show_source_mut(LogSource::Timer) = false;
+        assert!(filter.matches(&log("Foo", LogType::AutoSplitterMessage)));
+        assert!(filter.matches(&log("Foo", LogType::Debugger(LogLevel::Error))));
+        assert!(!filter.matches(&log("Foo", LogType::Runtime(LogLevel::Error))));
+        assert!(!filter.matches(&log("Splitted.", LogType::TimerAction)));
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut filter = LogFilter::default();
+        *filter.show_level_mut(LogLevel::Debug) = false;
+        assert!(filter.matches(&log("Foo", LogType::Runtime(LogLevel::Error))));
+        assert!(!filter.matches(&log("Foo", LogType::Runtime(LogLevel::Debug))));
+        assert!(filter.matches(&log("Foo", LogType::AutoSplitterMessage)));
+
+        *filter.show_level_mut(LogLevel::Info) = false;
+        assert!(!filter.matches(&log("Foo", LogType::Debugger(LogLevel::Info))));
+        assert!(filter.matches(&log("Foo", LogType::AutoSplitterMessage)));
+    }
+}
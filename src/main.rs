@@ -1,40 +1,128 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
 use std::{
-    fmt, fs,
-    path::PathBuf,
+    cell::RefCell,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Write as _},
+    fs,
+    hash::Hash,
+    io::{self, Write},
+    iter, mem,
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
+    process, ptr,
+    rc::Rc,
     sync::{
-        atomic::{AtomicU64, AtomicUsize},
-        Arc, Mutex, RwLock,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize},
+        Arc, Mutex, OnceLock, RwLock,
     },
     thread,
-    time::{Duration, Instant, SystemTime},
+    time::{Duration, Instant},
 };
 
 use anyhow::Context;
 use arc_swap::ArcSwapOption;
 use atomic::Atomic;
-use clap::Parser;
+use auto_restart::AutoRestart;
+use benchmark::Benchmark;
+use break_condition::BreakCondition;
+use cargo_build::{BuildEvent, CargoBuild};
+use clap::{Parser, Subcommand, ValueEnum};
 use clear_vec::{Clear, ClearVec};
+use crash_report::CrashReport;
+use disassembly::Disassembly;
 use eframe::{
-    egui::{self, Color32, ComboBox, Grid, Label, RichText, Visuals},
+    egui::{self, Color32, ComboBox, Grid, Label, RichText},
     emath::Align,
     App, Frame,
 };
 use egui_dock::{DockArea, DockState, NodeIndex, Style};
 use egui_file::FileDialog;
-use egui_plot::{Bar, BarChart, Legend, Plot, VLine};
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints, VLine};
+use executable::Architecture;
+use failure::Failure;
+use file_watch::FileWatch;
+use frame_stats::FrameStats;
+use handle_trace::HandleTrace;
 use hdrhistogram::Histogram;
-use indexmap::IndexMap;
+use host_calls::HostCalls;
+use http_api::{ApiRequest, Endpoint, HttpApi};
+use indexmap::{IndexMap, IndexSet};
 use livesplit_auto_splitting::{
     settings, time, wasi_path, AutoSplitter, CompiledAutoSplitter, Config, ExecutionGuard,
     LogLevel, Runtime, Timer, TimerState,
 };
+use log_file::LogFile;
+use log_filter::LogFilter;
+use memory_view::MemoryView;
+use module_info::ModuleInfo;
+use pointer_path::PointerPath;
+use preferences::Preferences;
+use process_candidates::ProcessCandidates;
+use process_maps::ProcessMapsCache;
+use remote::Remote;
+use run_history::RunHistory;
+use scenario::{Scenario, ScenarioRun, TimerEvent};
+use session::Session;
+use settings_history::SettingsHistory;
+use state_transitions::Transitions;
+use suspicious_actions::SuspiciousActions;
+use thread_options::ThreadOptions;
+use tick_heatmap::TickHeatmap;
+use tick_scheduler::{CatchUp, TickScheduler};
 use time::UtcOffset;
+use trap_stats::{TrapKind, TrapStats};
 
+mod auto_restart;
+mod benchmark;
+mod break_condition;
+mod cargo_build;
+mod check;
 mod clear_vec;
+mod console;
+mod crash_report;
+mod debug_info;
+mod diagnostics;
+mod disassembly;
+mod environment;
+mod executable;
+mod failure;
 mod file_filter;
+mod file_watch;
+mod frame_stats;
+mod game_launch;
+mod handle_trace;
+mod host_calls;
+mod http_api;
+mod log_file;
+mod log_filter;
+mod memory_diff;
+mod memory_view;
+mod module_info;
+mod pointer_path;
+mod preferences;
+mod process_candidates;
+mod process_maps;
+mod remote;
+mod run_history;
+mod scenario;
+mod session;
+mod settings_history;
+mod settings_json;
+mod sig_scan;
+mod state_transitions;
+mod stats_history;
+mod suspicious_actions;
+#[cfg(feature = "otlp")]
+mod telemetry;
+mod thread_options;
+mod tick_heatmap;
+mod tick_scheduler;
+mod timer_script;
+mod trap_stats;
+mod variable_history;
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 enum Tab {
     Main,
     Statistics,
@@ -44,54 +132,267 @@ enum Tab {
     SettingsMap,
     Processes,
     Performance,
+    Module,
+    Diagnostics,
+    Disassembly,
+    Memory,
+    MemoryDiff,
+    Runtime,
+    Run,
+    About,
 }
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     #[arg(short, long)]
     debug: bool,
+    /// Starts the HTTP API on the port.
+    #[arg(long)]
+    http_port: Option<u16>,
+    /// The address the HTTP API listens on. Use 0.0.0.0 to allow controlling
+    /// the debugger from other machines, in which case the requests need to
+    /// send a token. It's printed to stderr in the headless mode and can be
+    /// copied in the Main tab otherwise.
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    http_address: IpAddr,
+    /// Raises the priority of the runtime threads, so other programs affect
+    /// the tick times less.
+    #[arg(long)]
+    high_priority: bool,
+    /// Pins the runtime threads to the CPU core with the index.
+    #[arg(long)]
+    pin_core: Option<usize>,
+    /// Runs the debugger without a user interface. The HTTP API gets started
+    /// for controlling it and the log messages are printed to stdout.
+    #[arg(long)]
+    headless: bool,
+    /// How the headless mode prints what happens. The JSON output prints the
+    /// loads, log messages, timer actions, traps and tick statistics as JSON
+    /// Lines.
+    #[arg(long, value_enum, default_value_t = Output::Text)]
+    output: Output,
+    /// How many seconds apart the tick statistics are printed in the JSON
+    /// output.
+    #[arg(long, default_value = "10", value_parser = parse_seconds)]
+    stats_interval: Duration,
+    /// Stops the headless mode as soon as the auto splitter fails to compile
+    /// or instantiate, traps or gets interrupted by the watchdog, with an
+    /// exit code specific to the failure: 3 for compiling, 4 for
+    /// instantiating, 5 for traps and 6 for the watchdog.
+    #[arg(long)]
+    exit_on_failure: bool,
+    /// Stops the headless mode after running for this many seconds, with the
+    /// exit code 0 if nothing failed and the one of the failure otherwise.
+    #[arg(long, value_parser = parse_seconds)]
+    run_for: Option<Duration>,
+    /// Checks the timer events of the auto splitter against the scenario file
+    /// and replays its inputs. The headless mode stops as soon as the
+    /// scenario fails, with the exit code 8, and once every expected timer
+    /// event happened otherwise, with the exit code 0. With `--run-for` it
+    /// keeps running until then and fails if an expected event is still
+    /// missing.
+    #[arg(long, requires = "wasm_path")]
+    scenario: Option<PathBuf>,
+    /// Interrupts updates of the auto splitter that take longer than this
+    /// many milliseconds.
+    #[arg(long)]
+    max_tick_duration: Option<u64>,
+    /// Connects to a debugger running elsewhere, like in headless mode on the
+    /// machine the game runs on, instead of running an auto splitter. The
+    /// address is the one of its HTTP API, such as 192.168.0.10:9087.
+    #[arg(long, conflicts_with_all = ["headless", "wasm_path"])]
+    connect: Option<String>,
+    /// The token of the HTTP API of the debugger to connect to.
+    #[arg(long, requires = "connect")]
+    token: Option<String>,
+    /// Exports the tracing spans to the OTLP collector at the endpoint, such
+    /// as http://localhost:4317.
+    #[cfg(feature = "otlp")]
+    #[arg(long)]
+    otlp_endpoint: Option<String>,
     wasm_path: Option<PathBuf>,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Compiles and instantiates the auto splitter, updates it once and
+    /// validates its settings, without a user interface or a game. The exit
+    /// code is 0 if it passes and the failure's exit code otherwise: 3 for
+    /// compiling, 4 for instantiating, 5 for traps, 6 for the watchdog and 7
+    /// for validation.
+    Check {
+        #[arg(short, long)]
+        debug: bool,
+        wasm_path: PathBuf,
+    },
+}
+
+#[derive(Copy, Clone, PartialEq, ValueEnum)]
+enum Output {
+    Text,
+    Json,
+}
+
+struct HeadlessOptions {
+    output: Output,
+    stats_interval: Duration,
+    exit_on_failure: bool,
+    run_for: Option<Duration>,
+    /// Whether the timer events are checked against a scenario.
+    scenario: bool,
+    /// Set when Ctrl+C got pressed.
+    interrupted: Arc<AtomicBool>,
+}
+
+impl Args {
+    fn thread_options(&self) -> ThreadOptions {
+        ThreadOptions {
+            high_priority: self.high_priority,
+            core: self.pin_core,
+        }
+    }
+
+    fn headless_options(&self, interrupted: Arc<AtomicBool>) -> HeadlessOptions {
+        HeadlessOptions {
+            output: self.output,
+            stats_interval: self.stats_interval.max(Duration::from_millis(100)),
+            exit_on_failure: self.exit_on_failure,
+            run_for: self.run_for,
+            scenario: self.scenario.is_some(),
+            interrupted,
+        }
+    }
+}
+
+/// Parses an amount of seconds, rejecting negative, infinite and NaN ones.
+fn parse_seconds(text: &str) -> Result<Duration, String> {
+    let seconds = text.parse::<f64>().map_err(|e| e.to_string())?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| "The amount of seconds needs to be a positive finite number.".into())
+}
+
 const TEXT_COLOR: Color32 = Color32::from_gray(230);
 const TIME_COLOR: Color32 = Color32::from_gray(180);
+const LIGHT_TEXT_COLOR: Color32 = Color32::from_gray(30);
+const LIGHT_TIME_COLOR: Color32 = Color32::from_gray(90);
 
 // Based on the default VSCode terminal colors.
-#[allow(unused)]
 const BLUE_COLOR: Color32 = Color32::from_rgb(0x29, 0xB8, 0xDB);
 const GREEN_COLOR: Color32 = Color32::from_rgb(0x23, 0xD1, 0x8B);
 const RED_COLOR: Color32 = Color32::from_rgb(0xF3, 0x5E, 0x5E);
 const YELLOW_COLOR: Color32 = Color32::from_rgb(0xF5, 0xF5, 0x37);
+const MAGENTA_COLOR: Color32 = Color32::from_rgb(0xD6, 0x70, 0xD6);
+const LIGHT_BLUE_COLOR: Color32 = Color32::from_rgb(0x04, 0x51, 0xA5);
+const LIGHT_RED_COLOR: Color32 = Color32::from_rgb(0xCD, 0x31, 0x31);
+const LIGHT_YELLOW_COLOR: Color32 = Color32::from_rgb(0x94, 0x98, 0x00);
+const LIGHT_MAGENTA_COLOR: Color32 = Color32::from_rgb(0xBC, 0x05, 0xBC);
 
+const TRACE_COLOR: Color32 = Color32::from_gray(140);
+const DEBUG_COLOR: Color32 = BLUE_COLOR;
 const INFO_COLOR: Color32 = GREEN_COLOR;
 const WARN_COLOR: Color32 = YELLOW_COLOR;
 const ERROR_COLOR: Color32 = RED_COLOR;
+const CHANGED_COLOR: Color32 = BLUE_COLOR;
+/// How many bytes of each difference are shown in the list of differences of
+/// two memory dumps.
+const MEMORY_DIFF_PREVIEW_LEN: usize = 8;
+/// How many rows of a difference are shown in the hex view at most.
+const MEMORY_DIFF_MAX_ROWS: usize = 64;
 
-fn main() {
+/// How long it takes for the highlight of a changed variable to fade out.
+const CHANGED_FADE_DURATION: Duration = Duration::from_secs(2);
+/// How many process attach and detach events are kept in the history.
+const PROCESS_EVENT_CAPACITY: usize = 100;
+/// How many functions are shown in the size profile of the module.
+const MODULE_FUNCTION_ROWS: usize = 200;
+/// How many of the ticks with the most handles created are shown.
+const HANDLE_GROWTH_ROWS: usize = 20;
+/// How many ticks the jitter is shown for.
+const TICK_JITTER_CAPACITY: usize = 1000;
+/// How often the user interface is repainted when nothing changes.
+const IDLE_REPAINT_INTERVAL: Duration = Duration::from_millis(250);
+/// How long shutting down waits for the runtime thread to stop.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+/// The exit code of the headless mode when it gets stopped with Ctrl+C, as is
+/// common for programs stopped by SIGINT.
+const INTERRUPTED_EXIT_CODE: u8 = 130;
+/// The exit code of the headless mode when nobody reads its output anymore,
+/// as is common for programs stopped by SIGPIPE.
+const OUTPUT_CLOSED_EXIT_CODE: u8 = 141;
+/// How long after saving the artifacts of a trap further traps are not saved,
+/// so an auto splitter that traps on every tick doesn't fill up the disk.
+const TRAP_ARTIFACT_INTERVAL: Duration = Duration::from_secs(60);
+
+fn main() -> process::ExitCode {
     let time_zone = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
 
     let args = Args::parse();
 
-    let shared_state = Arc::new(SharedState {
-        auto_splitter: ArcSwapOption::new(None),
-        memory_usage: AtomicUsize::new(0),
-        handles: AtomicU64::new(0),
-        tick_rate: Mutex::new(std::time::Duration::ZERO),
-        slowest_tick: Mutex::new(std::time::Duration::ZERO),
-        avg_tick_secs: Atomic::new(0.0),
-        tick_times: Mutex::new(Histogram::new(1).unwrap()),
-        processes: Mutex::new(ClearVec::new()),
+    if let Some(Command::Check { debug, wasm_path }) = &args.command {
+        console::attach();
+        return check::run(
+            wasm_path,
+            RuntimeConfig {
+                optimize: !debug,
+                ..Default::default()
+            },
+        );
+    }
+
+    if let Some(address) = args.connect {
+        return run_remote(address.into(), args.token.map(Into::into));
+    }
+
+    let (shared_state, timer) = start_runtime(time_zone, args.thread_options());
+
+    // Ctrl+C shuts the debugger down the same way as closing it, so the
+    // auto splitter gets interrupted and everything gets saved.
+    let interrupted = Arc::new(AtomicBool::new(false));
+    if let Err(e) = ctrlc::set_handler({
+        let interrupted = interrupted.clone();
+        let shared_state = shared_state.clone();
+        move || {
+            interrupted.store(true, atomic::Ordering::Relaxed);
+            if let Some(ctx) = shared_state.ui_context.get() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        }
+    }) {
+        timer.0.write().unwrap().log(
+            format!("Failed to handle Ctrl+C: {e}").into(),
+            LogType::Debugger(LogLevel::Warning),
+        );
+    }
+
+    #[cfg(feature = "otlp")]
+    let _telemetry = args.otlp_endpoint.as_deref().and_then(|endpoint| {
+        telemetry::Telemetry::start(endpoint)
+            .map_err(|e| {
+                timer
+                    .0
+                    .write()
+                    .unwrap()
+                    .log(format!("{e:?}").into(), LogType::Debugger(LogLevel::Error))
+            })
+            .ok()
     });
-    let timer = DebuggerTimer::new(time_zone);
 
-    thread::Builder::new()
-        .name("Auto Splitter Thread".into())
-        .spawn({
-            let timer = timer.clone();
-            let shared_state = shared_state.clone();
-            move || runtime_thread(shared_state, timer.clone())
-        })
-        .unwrap();
+    if args.headless {
+        console::attach();
+        let options = args.headless_options(interrupted);
+        if options.output == Output::Json {
+            // Enabled before loading the auto splitter, so its load is part
+            // of the output.
+            timer.0.write().unwrap().output_events = Some(Vec::new());
+        }
+        return run_headless(
+            AppState::from_args(Preferences::default(), args, shared_state, timer),
+            options,
+        );
+    }
 
     let mut options = eframe::NativeOptions::default();
     options.viewport.inner_size = Some((1250.0, 800.0).into());
@@ -100,11 +401,20 @@ fn main() {
         "Auto Splitting Runtime Debugger",
         options,
         Box::new(move |cc| {
-            cc.egui_ctx.set_visuals(Visuals::dark());
-            let mut style = (*cc.egui_ctx.style()).clone();
-            style.visuals.override_text_color = Some(TEXT_COLOR);
-            cc.egui_ctx.set_style(style);
-            cc.egui_ctx.set_zoom_factor(1.15);
+            let preferences: Preferences = cc
+                .storage
+                .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+                .unwrap_or_default();
+            apply_theme(
+                &cc.egui_ctx,
+                preferences.theme.is_dark(cc.integration_info.system_theme),
+            );
+            cc.egui_ctx.set_zoom_factor(preferences.zoom.clamp(
+                *preferences::ZOOM_RANGE.start(),
+                *preferences::ZOOM_RANGE.end(),
+            ));
+
+            let _ = shared_state.ui_context.set(cc.egui_ctx.clone());
 
             let mut dock_state = DockState::new(vec![Tab::Main]);
             let tree = dock_state.main_surface_mut();
@@ -115,42 +425,234 @@ fn main() {
                 (1.0 - 2.0 * side_percentage) / (1.0 - side_percentage),
                 vec![Tab::SettingsGUI],
             );
-            tree.split_below(mid, 0.7, vec![Tab::Processes, Tab::Performance]);
-            tree.split_below(right, 0.5, vec![Tab::Variables, Tab::SettingsMap]);
-            tree.split_below(left, 0.5, vec![Tab::Statistics]);
-
-            let optimize = !args.debug;
+            tree.split_below(
+                mid,
+                0.7,
+                vec![
+                    Tab::Processes,
+                    Tab::Performance,
+                    Tab::Module,
+                    Tab::Diagnostics,
+                    Tab::Disassembly,
+                    Tab::Memory,
+                    Tab::MemoryDiff,
+                ],
+            );
+            tree.split_below(
+                right,
+                0.5,
+                vec![Tab::Variables, Tab::SettingsMap, Tab::Runtime],
+            );
+            tree.split_below(left, 0.5, vec![Tab::Statistics, Tab::Run, Tab::About]);
 
-            let mut app = Box::new(Debugger {
+            Ok(Box::new(Debugger {
                 dock_state,
-                state: AppState {
-                    path: None,
-                    script_path: None,
-                    module_modified_time: None,
-                    script_modified_time: None,
-                    optimize,
-                    open_file_dialog: None,
-                    module: None,
-                    shared_state,
-                    timer,
-                    runtime: build_runtime(optimize),
-                },
-            });
+                instances: vec![AppState::from_args(preferences, args, shared_state, timer)],
+                selected: 0,
+                ab_test: None,
+                frame_stats: FrameStats::default(),
+            }))
+        }),
+    )
+    .unwrap();
 
-            if let Some(path) = args.wasm_path {
-                app.state.load(Load::File(path));
-            }
+    process::ExitCode::SUCCESS
+}
+
+fn run_remote(address: Box<str>, token: Option<Box<str>>) -> process::ExitCode {
+    let mut options = eframe::NativeOptions::default();
+    options.viewport.inner_size = Some((1250.0, 800.0).into());
+
+    eframe::run_native(
+        &format!("Auto Splitting Runtime Debugger ({address})"),
+        options,
+        Box::new(move |cc| {
+            let preferences: Preferences = cc
+                .storage
+                .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+                .unwrap_or_default();
+            apply_theme(
+                &cc.egui_ctx,
+                preferences.theme.is_dark(cc.integration_info.system_theme),
+            );
 
-            Ok(app)
+            let ctx = cc.egui_ctx.clone();
+            let remote = Remote::connect(address, token, move || ctx.request_repaint())?;
+            Ok(Box::new(RemoteDebugger { remote }))
         }),
     )
     .unwrap();
+
+    process::ExitCode::SUCCESS
+}
+
+fn run_headless(mut state: AppState, options: HeadlessOptions) -> process::ExitCode {
+    // The token is kept out of the output, as that is often stored.
+    if let Some(token) = state.http_api.as_ref().and_then(|api| api.token.as_deref()) {
+        console::print_error_line(format_args!("The token of the HTTP API is {token}."));
+    }
+    let start = Instant::now();
+    let mut printed = 0;
+    let mut next_stats = start + options.stats_interval;
+    // Printing fails once the output got closed, like when it's piped into a
+    // program that exited.
+    let mut output_open = true;
+    loop {
+        let now = Instant::now();
+        state.update(now);
+
+        let time_is_up = options
+            .run_for
+            .is_some_and(|run_for| now.duration_since(start) >= run_for);
+
+        // Shutting down before printing means its log messages are printed
+        // too.
+        let stop = if let Some(failure) = state.failure().filter(|_| options.exit_on_failure) {
+            Some((
+                failure.to_str().into(),
+                LogLevel::Error,
+                failure.exit_code(),
+            ))
+        } else if options.interrupted.load(atomic::Ordering::Relaxed) {
+            Some(("Interrupted.".into(), LogLevel::Info, INTERRUPTED_EXIT_CODE))
+        } else if let Some(stop) = options
+            .scenario
+            .then(|| state.check_scenario(time_is_up, options.run_for.is_some()))
+            .flatten()
+        {
+            Some(stop)
+        } else if time_is_up {
+            Some((
+                "Finished running.".into(),
+                LogLevel::Info,
+                state.failure().map_or(0, Failure::exit_code),
+            ))
+        } else {
+            None
+        };
+        if let Some((message, level, _)) = &stop {
+            state
+                .timer
+                .0
+                .write()
+                .unwrap()
+                .log(message.clone(), LogType::Debugger(*level));
+            state.shutdown(None);
+        }
+
+        if options.output == Output::Json {
+            let events = state
+                .timer
+                .0
+                .write()
+                .unwrap()
+                .output_events
+                .as_mut()
+                .map(mem::take)
+                .unwrap_or_default();
+            output_open &= events.iter().all(console::print_line);
+            if now >= next_stats {
+                next_stats += options.stats_interval;
+                let mut stats = state.stats_json();
+                if let Some(stats) = stats.as_object_mut() {
+                    let timer = state.timer.0.read().unwrap();
+                    stats.insert("event".into(), "stats".into());
+                    stats.insert(
+                        "timestamp".into(),
+                        fmt_timestamp(time::OffsetDateTime::now_utc().to_offset(timer.time_zone))
+                            .into(),
+                    );
+                    stats.insert("tick".into(), timer.tick_index.into());
+                }
+                output_open &= console::print_line(stats);
+            }
+        } else {
+            let timer = state.timer.0.read().unwrap();
+            if timer.logs.len() < printed {
+                printed = 0;
+            }
+            output_open &= timer.logs[printed..].iter().all(console::print_line);
+            printed = timer.logs.len();
+        }
+
+        if let Some((_, _, exit_code)) = stop {
+            return exit_code.into();
+        }
+        if !output_open {
+            state.shutdown(None);
+            return OUTPUT_CLOSED_EXIT_CODE.into();
+        }
+
+        thread::sleep(Duration::from_millis(16));
+    }
+}
+
+/// Creates the state of an auto splitter and starts the threads running it.
+fn start_runtime(
+    time_zone: UtcOffset,
+    thread_options: ThreadOptions,
+) -> (Arc<SharedState>, DebuggerTimer) {
+    let shared_state = Arc::new(SharedState {
+        auto_splitter: ArcSwapOption::new(None),
+        memory_usage: AtomicUsize::new(0),
+        peak_memory_usage: AtomicUsize::new(0),
+        memory_grow_count: AtomicU64::new(0),
+        handles: AtomicU64::new(0),
+        process_handles: AtomicU64::new(0),
+        handle_trace: Mutex::new(HandleTrace::default()),
+        tick_rate: Mutex::new(std::time::Duration::ZERO),
+        slowest_tick: Mutex::new(std::time::Duration::ZERO),
+        avg_tick_secs: Atomic::new(0.0),
+        tick_times: Mutex::new(Histogram::new(1).unwrap()),
+        tick_heatmap: Mutex::new(TickHeatmap::default()),
+        benchmark: Mutex::new(Benchmark::default()),
+        tick_jitter: Mutex::new(VecDeque::new()),
+        missed_ticks: AtomicU64::new(0),
+        catch_up: Mutex::new(CatchUp::Skip),
+        applied_thread_options: Mutex::new(ThreadOptions::default()),
+        processes: Mutex::new(ClearVec::new()),
+        memory_view: MemoryView::default(),
+        process_events: Mutex::new(VecDeque::new()),
+        paused: AtomicBool::new(false),
+        step: AtomicBool::new(false),
+        tick_start: Mutex::new(None),
+        max_tick_duration: Mutex::new(None),
+        trapped: AtomicBool::new(false),
+        trap_stats: Mutex::new(TrapStats::default()),
+        settings_history: Mutex::new(SettingsHistory::default()),
+        pointer_paths: Mutex::new(Vec::new()),
+        closed: AtomicBool::new(false),
+        runtime_stopped: AtomicBool::new(false),
+        ui_context: OnceLock::new(),
+    });
+    let timer = DebuggerTimer::new(time_zone);
+
+    thread::Builder::new()
+        .name("Auto Splitter Thread".into())
+        .spawn({
+            let timer = timer.clone();
+            let shared_state = shared_state.clone();
+            move || runtime_thread(shared_state, timer, thread_options)
+        })
+        .unwrap();
+
+    thread::Builder::new()
+        .name("Watchdog Thread".into())
+        .spawn({
+            let timer = timer.clone();
+            let shared_state = shared_state.clone();
+            move || watchdog_thread(shared_state, timer)
+        })
+        .unwrap();
+
+    (shared_state, timer)
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct ProcessInfo {
     path: String,
     pid: String,
+    raw_pid: u64,
 }
 
 impl Clear for ProcessInfo {
@@ -160,18 +662,122 @@ impl Clear for ProcessInfo {
     }
 }
 
+struct ProcessEvent {
+    time: time::OffsetDateTime,
+    tick: u64,
+    attached: bool,
+    pid: u64,
+    path: Box<str>,
+}
+
 struct SharedState {
     auto_splitter: ArcSwapOption<AutoSplitter<DebuggerTimer>>,
     tick_rate: Mutex<std::time::Duration>,
     slowest_tick: Mutex<std::time::Duration>,
     memory_usage: AtomicUsize,
+    /// The largest amount of memory used by the auto splitter since the peak
+    /// got reset. It persists across reloads.
+    peak_memory_usage: AtomicUsize,
+    /// The amount of times the memory of the auto splitter grew since it was
+    /// loaded.
+    memory_grow_count: AtomicU64,
     handles: AtomicU64,
+    /// How many of the handles are attached processes. The runtime doesn't
+    /// report the other kinds of handles separately.
+    process_handles: AtomicU64,
+    /// The ticks in which the handles that aren't attached processes grew.
+    handle_trace: Mutex<HandleTrace>,
     avg_tick_secs: Atomic<f64>,
     tick_times: Mutex<Histogram<u64>>,
+    /// The tick times over the wall clock time, which unlike the histogram
+    /// shows when the slow ticks happened.
+    tick_heatmap: Mutex<TickHeatmap>,
+    /// Compares the tick times of each build with the previous one.
+    benchmark: Mutex<Benchmark>,
+    /// How late each of the recent ticks started compared to when it was
+    /// scheduled.
+    tick_jitter: Mutex<VecDeque<(u64, Duration)>>,
+    /// The amount of ticks that got skipped or started a whole tick late.
+    missed_ticks: AtomicU64,
+    catch_up: Mutex<CatchUp>,
+    /// The thread options that got applied to the runtime thread, which lack
+    /// the requested ones that failed to be applied.
+    applied_thread_options: Mutex<ThreadOptions>,
     processes: Mutex<ClearVec<ProcessInfo>>,
+    /// The parts of the memory the Memory tab shows.
+    memory_view: MemoryView,
+    /// The history of processes getting attached and detached.
+    process_events: Mutex<VecDeque<ProcessEvent>>,
+    /// Whether the runtime thread stopped calling the update function.
+    paused: AtomicBool,
+    /// Requests a single tick to be executed while paused.
+    step: AtomicBool,
+    /// When the currently executing update started, if there is one.
+    tick_start: Mutex<Option<Instant>>,
+    /// The duration after which an update gets interrupted.
+    max_tick_duration: Mutex<Option<Duration>>,
+    /// Set whenever an update of the auto splitter fails.
+    trapped: AtomicBool,
+    trap_stats: Mutex<TrapStats>,
+    settings_history: Mutex<SettingsHistory>,
+    /// The values in the memory of the attached processes that are read after
+    /// every tick.
+    pointer_paths: Mutex<Vec<PointerPath>>,
+    /// Stops the threads once the auto splitter got closed.
+    closed: AtomicBool,
+    /// Set once the runtime thread stopped, so no update is executing
+    /// anymore.
+    runtime_stopped: AtomicBool,
+    /// Used to repaint the user interface whenever the auto splitter changes
+    /// something shown in it.
+    ui_context: OnceLock<egui::Context>,
 }
 
 impl SharedState {
+    /// Changes the settings map of the auto splitter and records the change
+    /// in the settings history.
+    fn change_settings_map(
+        &self,
+        timer: &DebuggerTimer,
+        origin: settings_history::Origin,
+        change: impl Fn(&settings::Map) -> settings::Map,
+    ) {
+        let Some(auto_splitter) = &*self.auto_splitter.load() else {
+            return;
+        };
+        let mut timer = timer.0.write().unwrap();
+        let time = time::OffsetDateTime::now_utc().to_offset(timer.time_zone);
+        let tick_index = timer.tick_index;
+        let mut history = self.settings_history.lock().unwrap();
+        loop {
+            let old = auto_splitter.settings_map();
+            let new = change(&old);
+            if auto_splitter.set_settings_map_if_unchanged(&old, new.clone()) {
+                // The changes the user makes are replayed when checking a
+                // golden run that gets recorded.
+                if let Some(run) = timer.scenario.as_mut().filter(|run| {
+                    run.is_recording() && origin != settings_history::Origin::Debugger
+                }) {
+                    if let serde_json::Value::Object(settings) =
+                        settings_json::settings_map_to_json(&new)
+                    {
+                        run.record_input(tick_index, scenario::InputKind::Settings(settings));
+                    }
+                }
+                // Whatever changed since the last tick was changed by the
+                // auto splitter itself.
+                history.record(
+                    old,
+                    settings_history::Origin::AutoSplitter,
+                    tick_index,
+                    time,
+                );
+                history.record(new, origin, tick_index, time);
+                break;
+            }
+        }
+    }
+
     fn kill_auto_splitter_if_it_doesnt_react(&self) {
         let Some(auto_splitter) = &*self.auto_splitter.load() else {
             return;
@@ -195,58 +801,87 @@ impl SharedState {
     }
 }
 
-fn runtime_thread(shared_state: Arc<SharedState>, timer: DebuggerTimer) {
+/// Interrupts updates that take longer than the maximum tick duration, so an
+/// auto splitter that is stuck in a loop doesn't hang the runtime thread.
+fn watchdog_thread(shared_state: Arc<SharedState>, timer: DebuggerTimer) {
+    while !shared_state.closed.load(atomic::Ordering::Relaxed) {
+        thread::sleep(Duration::from_millis(5));
+
+        let Some(max_tick_duration) = *shared_state.max_tick_duration.lock().unwrap() else {
+            continue;
+        };
+        let mut tick_start = shared_state.tick_start.lock().unwrap();
+        if !tick_start.is_some_and(|start| start.elapsed() > max_tick_duration) {
+            continue;
+        }
+        // Only interrupt each update once.
+        *tick_start = None;
+
+        if let Some(auto_splitter) = &*shared_state.auto_splitter.load() {
+            // The lock is held while interrupting, so the next update can't
+            // start before the interrupt is delivered. An interrupt that
+            // arrives after the slow update already finished is discarded
+            // when the next update starts.
+            auto_splitter.interrupt_handle().interrupt();
+            drop(tick_start);
+            timer.0.write().unwrap().log(
+                format!(
+                    "The update took longer than {}. Interrupting the auto splitter.",
+                    fmt_std_duration(max_tick_duration),
+                )
+                .into(),
+                LogType::Debugger(LogLevel::Warning),
+            );
+        }
+    }
+}
+
+fn runtime_thread(
+    shared_state: Arc<SharedState>,
+    timer: DebuggerTimer,
+    thread_options: ThreadOptions,
+) {
+    let mut applied = ThreadOptions::default();
+    if thread_options.high_priority {
+        match thread_options::raise_priority() {
+            Ok(()) => applied.high_priority = true,
+            Err(e) => timer
+                .0
+                .write()
+                .unwrap()
+                .log(format!("{e:?}").into(), LogType::Debugger(LogLevel::Error)),
+        }
+    }
+    if let Some(core) = thread_options.core {
+        match thread_options::pin_to_core(core) {
+            Ok(()) => applied.core = Some(core),
+            Err(e) => timer
+                .0
+                .write()
+                .unwrap()
+                .log(format!("{e:?}").into(), LogType::Debugger(LogLevel::Error)),
+        }
+    }
+    *shared_state.applied_thread_options.lock().unwrap() = applied;
+
+    let mut scheduler = TickScheduler::default();
     let mut next_tick = Instant::now();
-    loop {
+    while !shared_state.closed.load(atomic::Ordering::Relaxed) {
         let tick_rate = {
             if let Some(auto_splitter) = &*shared_state.auto_splitter.load() {
-                let mut auto_splitter_lock = auto_splitter.lock();
-                let now = Instant::now();
-                let res = auto_splitter_lock.update();
-                let time_of_tick = now.elapsed();
-                let memory_usage = auto_splitter_lock.memory().len();
-                {
-                    let mut processes = shared_state.processes.lock().unwrap();
-                    processes.clear();
-                    auto_splitter_lock.attached_processes().for_each(|process| {
-                        use std::fmt::Write;
-                        let element = processes.push();
-                        let _ = write!(element.pid, "{}", process.pid());
-                        element
-                            .path
-                            .push_str(process.path().unwrap_or("Unnamed Process"));
-                    });
-                }
-                let handles = auto_splitter_lock.handles();
-                drop(auto_splitter_lock);
-
-                shared_state
-                    .memory_usage
-                    .store(memory_usage, atomic::Ordering::Relaxed);
-                shared_state
-                    .handles
-                    .store(handles, atomic::Ordering::Relaxed);
-
+                if !shared_state.paused.load(atomic::Ordering::Relaxed)
+                    || shared_state.step.swap(false, atomic::Ordering::Relaxed)
                 {
-                    let mut slowest_tick = shared_state.slowest_tick.lock().unwrap();
-                    if time_of_tick > *slowest_tick {
-                        *slowest_tick = time_of_tick;
+                    run_tick(&shared_state, &timer, auto_splitter, next_tick);
+                } else if shared_state.memory_view.is_stale() {
+                    // The memory doesn't change while paused, so it only
+                    // needs to be captured when other regions get requested.
+                    if let Some(auto_splitter_lock) = auto_splitter.try_lock() {
+                        shared_state
+                            .memory_view
+                            .capture(auto_splitter_lock.memory());
                     }
                 }
-
-                *shared_state.tick_rate.lock().unwrap() = auto_splitter.tick_rate();
-                *shared_state.tick_times.lock().unwrap() += time_of_tick.as_nanos() as u64;
-                shared_state.avg_tick_secs.store(
-                    0.999 * shared_state.avg_tick_secs.load(atomic::Ordering::Relaxed)
-                        + 0.001 * time_of_tick.as_secs_f64(),
-                    atomic::Ordering::Relaxed,
-                );
-                if let Err(e) = res {
-                    timer.0.write().unwrap().log(
-                        format!("{:?}", e.context("Failed executing the auto splitter.")).into(),
-                        LogType::Runtime(LogLevel::Error),
-                    )
-                };
                 auto_splitter.tick_rate()
             } else {
                 shared_state.processes.lock().unwrap().clear();
@@ -258,44 +893,503 @@ fn runtime_thread(shared_state: Arc<SharedState>, timer: DebuggerTimer) {
         next_tick += tick_rate;
 
         let now = Instant::now();
-        if let Some(sleep_time) = next_tick.checked_duration_since(now) {
-            thread::sleep(sleep_time);
+        if next_tick > now {
+            scheduler.sleep_until(next_tick);
         } else {
             // In this case we missed the next tick already. This likely comes
-            // up when the operating system was suspended for a while. Instead
-            // of trying to catch up, we just reset the next tick to start from
-            // now.
-            next_tick = now;
+            // up when the operating system was suspended for a while.
+            let skipped;
+            (next_tick, skipped) = tick_scheduler::catch_up(
+                *shared_state.catch_up.lock().unwrap(),
+                next_tick,
+                now,
+                tick_rate,
+            );
+            shared_state
+                .missed_ticks
+                .fetch_add(skipped as u64, atomic::Ordering::Relaxed);
+        }
+    }
+    shared_state
+        .runtime_stopped
+        .store(true, atomic::Ordering::Relaxed);
+}
+
+/// Runs a single tick of the auto splitter, that was scheduled to start at
+/// `scheduled`.
+fn run_tick(
+    shared_state: &SharedState,
+    timer: &DebuggerTimer,
+    auto_splitter: &AutoSplitter<DebuggerTimer>,
+    scheduled: Instant,
+) {
+    let (tick_index, inputs) = {
+        let mut timer = timer.0.write().unwrap();
+        timer.tick_index += 1;
+        timer.host_calls.start_tick();
+        let tick_index = timer.tick_index;
+        let inputs = timer
+            .scenario
+            .as_mut()
+            .map(|run| run.take_inputs(tick_index))
+            .unwrap_or_default();
+        for input in &inputs {
+            if let scenario::InputKind::Timer(line) = input {
+                timer.log(
+                    format!("Replaying \"{line}\" from the scenario.").into(),
+                    LogType::Debugger(LogLevel::Debug),
+                );
+                // The scenario got validated when it was read.
+                for command in timer_script::parse(line).unwrap_or_default() {
+                    timer.perform_user_action(&command);
+                }
+            }
+        }
+        (tick_index, inputs)
+    };
+    for input in inputs {
+        if let scenario::InputKind::Settings(settings) = input {
+            let settings = settings_json::settings_map_from_json(&settings);
+            shared_state.change_settings_map(timer, settings_history::Origin::Debugger, |old| {
+                let mut new = old.clone();
+                for (key, value) in settings.iter() {
+                    new.insert(key.into(), value.clone());
+                }
+                new
+            });
+        }
+    }
+    let span = tracing::info_span!(
+        "tick",
+        tick = tick_index,
+        memory_usage = tracing::field::Empty,
+        error = tracing::field::Empty,
+    )
+    .entered();
+
+    let mut auto_splitter_lock = auto_splitter.lock();
+    let now = Instant::now();
+    {
+        let mut tick_jitter = shared_state.tick_jitter.lock().unwrap();
+        if tick_jitter.len() >= TICK_JITTER_CAPACITY {
+            tick_jitter.pop_front();
+        }
+        let jitter = now.saturating_duration_since(scheduled);
+        // Ticks that start a whole tick late only happen when catching up.
+        if jitter >= auto_splitter.tick_rate() {
+            shared_state
+                .missed_ticks
+                .fetch_add(1, atomic::Ordering::Relaxed);
+        }
+        tick_jitter.push_back((tick_index, jitter));
+    }
+    *shared_state.tick_start.lock().unwrap() = Some(now);
+    let res = tracing::info_span!("update").in_scope(|| auto_splitter_lock.update());
+    *shared_state.tick_start.lock().unwrap() = None;
+    let time_of_tick = now.elapsed();
+    let memory_usage = auto_splitter_lock.memory().len();
+    span.record("memory_usage", memory_usage);
+    shared_state
+        .memory_view
+        .capture(auto_splitter_lock.memory());
+    let mut process_events = Vec::<(bool, u64, Box<str>)>::new();
+    {
+        let mut processes = shared_state.processes.lock().unwrap();
+        for process in &*processes {
+            if !auto_splitter_lock
+                .attached_processes()
+                .any(|p| p.pid() == process.raw_pid)
+            {
+                process_events.push((false, process.raw_pid, process.path.as_str().into()));
+            }
+        }
+        for process in auto_splitter_lock.attached_processes() {
+            if !processes.iter().any(|p| p.raw_pid == process.pid()) {
+                process_events.push((
+                    true,
+                    process.pid(),
+                    process.path().unwrap_or("Unnamed Process").into(),
+                ));
+            }
+        }
+        processes.clear();
+        auto_splitter_lock.attached_processes().for_each(|process| {
+            use std::fmt::Write;
+            let element = processes.push();
+            let _ = write!(element.pid, "{}", process.pid());
+            element.raw_pid = process.pid();
+            element
+                .path
+                .push_str(process.path().unwrap_or("Unnamed Process"));
+        });
+    }
+    let handles = auto_splitter_lock.handles();
+    let process_handles = auto_splitter_lock.attached_processes().count() as u64;
+    drop(auto_splitter_lock);
+
+    for pointer_path in &mut *shared_state.pointer_paths.lock().unwrap() {
+        pointer_path.update();
+    }
+
+    let previous_memory_usage = shared_state
+        .memory_usage
+        .swap(memory_usage, atomic::Ordering::Relaxed);
+    shared_state
+        .peak_memory_usage
+        .fetch_max(memory_usage, atomic::Ordering::Relaxed);
+    let previous_handles = shared_state
+        .handles
+        .swap(handles, atomic::Ordering::Relaxed);
+    let previous_process_handles = shared_state
+        .process_handles
+        .swap(process_handles, atomic::Ordering::Relaxed);
+
+    {
+        let mut slowest_tick = shared_state.slowest_tick.lock().unwrap();
+        if time_of_tick > *slowest_tick {
+            *slowest_tick = time_of_tick;
+        }
+    }
+
+    *shared_state.tick_rate.lock().unwrap() = auto_splitter.tick_rate();
+    *shared_state.tick_times.lock().unwrap() += time_of_tick.as_nanos() as u64;
+    shared_state
+        .tick_heatmap
+        .lock()
+        .unwrap()
+        .record(now, time_of_tick);
+    let comparison = shared_state.benchmark.lock().unwrap().record(time_of_tick);
+    shared_state.avg_tick_secs.store(
+        0.999 * shared_state.avg_tick_secs.load(atomic::Ordering::Relaxed)
+            + 0.001 * time_of_tick.as_secs_f64(),
+        atomic::Ordering::Relaxed,
+    );
+
+    let mut timer = timer.0.write().unwrap();
+    if let Some(comparison) = comparison {
+        let after = comparison.after;
+        match comparison.before {
+            Some(before) => timer.log(
+                format!(
+                    "The p99 tick time went from {} to {} compared to the previous build (p50: {} to {}).",
+                    fmt_std_duration(before.p99),
+                    fmt_std_duration(after.p99),
+                    fmt_std_duration(before.p50),
+                    fmt_std_duration(after.p50),
+                )
+                .into(),
+                if comparison.is_regression() {
+                    LogType::Debugger(LogLevel::Warning)
+                } else {
+                    LogType::Debugger(LogLevel::Info)
+                },
+            ),
+            None => timer.log(
+                format!(
+                    "Measured a p99 tick time of {} (p50: {}). Reloaded builds get compared against it.",
+                    fmt_std_duration(after.p99),
+                    fmt_std_duration(after.p50),
+                )
+                .into(),
+                LogType::Debugger(LogLevel::Debug),
+            ),
+        }
+    }
+    // The memory starts out empty, so the initial allocation isn't considered
+    // growth.
+    if previous_memory_usage != 0 && memory_usage > previous_memory_usage {
+        shared_state
+            .memory_grow_count
+            .fetch_add(1, atomic::Ordering::Relaxed);
+        let tick = timer.tick_index;
+        timer.log(
+            format!(
+                "Memory grew from {} to {} at tick {tick}.",
+                fmt_bytes(previous_memory_usage as _),
+                fmt_bytes(memory_usage as _),
+            )
+            .into(),
+            LogType::Runtime(LogLevel::Info),
+        );
+    }
+    // The handles start out at zero, so the initial ones aren't considered
+    // growth.
+    if previous_handles != 0 {
+        shared_state.handle_trace.lock().unwrap().record(
+            previous_handles.saturating_sub(previous_process_handles),
+            handles.saturating_sub(process_handles),
+            timer.tick_index,
+            time::OffsetDateTime::now_utc().to_offset(timer.time_zone),
+        );
+    }
+    if !process_events.is_empty() {
+        let mut history = shared_state.process_events.lock().unwrap();
+        for (attached, pid, path) in process_events {
+            timer.log(
+                if attached {
+                    format!("Attached to process {path} (PID {pid}).")
+                } else {
+                    format!("Detached from process {path} (PID {pid}).")
+                }
+                .into(),
+                LogType::Runtime(LogLevel::Info),
+            );
+            if history.len() == PROCESS_EVENT_CAPACITY {
+                history.pop_front();
+            }
+            history.push_back(ProcessEvent {
+                time: time::OffsetDateTime::now_utc().to_offset(timer.time_zone),
+                tick: timer.tick_index,
+                attached,
+                pid,
+                path,
+            });
+        }
+    }
+    if let Err(e) = res {
+        shared_state.trapped.store(true, atomic::Ordering::Relaxed);
+        let message: Box<str> =
+            format!("{:?}", e.context("Failed executing the auto splitter.")).into();
+        span.record("error", &*message);
+        let kind = TrapKind::classify(&message);
+        shared_state
+            .trap_stats
+            .lock()
+            .unwrap()
+            .record(kind, timer.tick_index, message.clone());
+        timer.output_event(
+            "trap",
+            || serde_json::json!({ "kind": kind.to_str(), "message": &*message }),
+        );
+        timer.log(message, LogType::Runtime(LogLevel::Error));
+    };
+    {
+        let mut settings_history = shared_state.settings_history.lock().unwrap();
+        // The settings history already compares against the settings map of
+        // the next auto splitter if this one got replaced during the tick.
+        let is_current = shared_state
+            .auto_splitter
+            .load()
+            .as_deref()
+            .is_some_and(|current| ptr::eq(current, auto_splitter));
+        if is_current {
+            let changed = settings_history.record(
+                auto_splitter.settings_map(),
+                settings_history::Origin::AutoSplitter,
+                timer.tick_index,
+                time::OffsetDateTime::now_utc().to_offset(timer.time_zone),
+            );
+            let changes = settings_history.changes.len();
+            for change in settings_history.changes.range(changes - changed..) {
+                timer.log(
+                    format!(
+                        "The auto splitter changed its own setting \"{}\" from {} to {}.",
+                        change.key,
+                        change.before.as_deref().unwrap_or("<Not set>"),
+                        change.after.as_deref().unwrap_or("<Not set>"),
+                    )
+                    .into(),
+                    LogType::Runtime(LogLevel::Warning),
+                );
+            }
+        }
+    }
+    if let Some(mut condition) = timer.break_condition.take() {
+        if condition.check(&*timer) {
+            timer.break_requested = true;
+            let message = format!(
+                "Break at tick {}: The condition {} became true.",
+                timer.tick_index, condition.source,
+            );
+            timer.log(message.into(), LogType::Debugger(LogLevel::Info));
+        }
+        timer.break_condition = Some(condition);
+    }
+    if mem::take(&mut timer.break_requested) {
+        shared_state.paused.store(true, atomic::Ordering::Relaxed);
+    }
+    timer.record_variable_history();
+    timer.record_game_time();
+    if mem::take(&mut timer.changed) {
+        if let Some(ctx) = shared_state.ui_context.get() {
+            ctx.request_repaint();
         }
     }
 }
 
 struct Debugger {
     dock_state: DockState<Tab>,
-    state: AppState,
+    /// The auto splitters that are loaded side by side. Only the selected one
+    /// is shown, but all of them keep running.
+    instances: Vec<AppState>,
+    selected: usize,
+    ab_test: Option<AbTest>,
+    frame_stats: FrameStats,
+}
+
+/// The indices of the same auto splitter running with and without the
+/// optimizations of the runtime, whose timer actions are compared.
+#[derive(Copy, Clone)]
+struct AbTest {
+    optimized: usize,
+    unoptimized: usize,
 }
 
+/// How many ticks the timer actions of an A/B test may be apart, as both auto
+/// splitters tick independently of each other.
+const AB_TEST_TICK_TOLERANCE: u64 = 2;
+/// How many seconds the game times of an A/B test may be apart.
+const AB_TEST_GAME_TIME_TOLERANCE: f64 = 0.1;
+
 struct AppState {
+    /// Shared by all the auto splitters.
+    preferences: Rc<RefCell<Preferences>>,
     path: Option<PathBuf>,
     script_path: Option<PathBuf>,
-    module_modified_time: Option<SystemTime>,
-    script_modified_time: Option<SystemTime>,
-    optimize: bool,
+    module_watch: FileWatch,
+    script_watch: FileWatch,
+    /// The configuration the current runtime was created with.
+    runtime_config: RuntimeConfig,
+    /// The configuration that is being edited in the Runtime tab.
+    pending_runtime_config: RuntimeConfig,
     open_file_dialog: Option<(FileDialog, FileDialogInfo)>,
     module: Option<CompiledAutoSplitter>,
+    /// The most recently compiled modules, keyed by the hash of their file
+    /// contents and the configuration of the runtime that compiled them.
+    module_cache: IndexMap<(u64, RuntimeConfig), CompiledAutoSplitter>,
+    module_info: Option<ModuleInfo>,
+    /// The likely mistakes found in the module of the auto splitter.
+    diagnostics: Vec<diagnostics::Diagnostic>,
+    module_data: Option<Box<[u8]>>,
+    disassembly: Option<Result<Disassembly, String>>,
+    /// The global variables of the auto splitter, read from its debug
+    /// information once the Memory tab is shown.
+    global_variables: Option<Result<Vec<debug_info::GlobalVariable>, String>>,
+    /// Whether the statistics of the current build were added to the
+    /// statistics history.
+    stats_recorded: bool,
+    /// Why loading the auto splitter failed the last time it was loaded.
+    load_failure: Option<Failure>,
+    /// The amount of traps that were already considered for saving their
+    /// artifacts.
+    saved_traps: u64,
+    /// When the artifacts of a trap were last saved.
+    trap_artifacts_saved: Option<Instant>,
+    /// The address the memory view should scroll to.
+    memory_view_target: Option<u64>,
+    /// How long compiling the currently loaded module took.
+    compile_time: Option<Duration>,
+    /// How long instantiating the current auto splitter took.
+    instantiate_time: Option<Duration>,
+    /// Whether modified files get reloaded automatically.
+    auto_reload: bool,
+    /// A directory in which the most recently modified WASM file gets loaded
+    /// automatically.
+    watched_directory: Option<PathBuf>,
+    last_directory_scan: Option<Instant>,
+    /// The directory of the cargo workspace the auto splitter is built in.
+    cargo_workspace: Option<PathBuf>,
+    cargo_build: Option<CargoBuild>,
+    auto_restart: AutoRestart,
+    process_maps: ProcessMapsCache,
+    process_candidates: ProcessCandidates,
+    /// The column the attached processes are sorted by and whether they are
+    /// sorted in descending order.
+    process_sort: (ProcessColumn, bool),
+    /// The column the functions of the module are sorted by and whether they
+    /// are sorted in descending order.
+    function_sort: (FunctionColumn, bool),
+    scenario_path: Option<PathBuf>,
+    /// The session file that was last saved or restored. The session is
+    /// saved to it again when the debugger exits.
+    session_path: Option<PathBuf>,
+    performance_view: PerformanceView,
+    /// The architectures of the attached processes, determined from their
+    /// executables.
+    process_architectures: HashMap<u64, Option<Architecture>>,
+    /// The memory ranges of the attached processes that are selected for
+    /// dumping, by their process and start address.
+    selected_ranges: HashSet<(u64, u64)>,
+    pointer_path_draft: pointer_path::Draft,
+    sig_scan: sig_scan::SigScan,
+    memory_diff: memory_diff::MemoryDiff,
+    /// Whether to include a dump of the auto splitter's memory in crash
+    /// reports.
+    crash_report_memory: bool,
+    http_api: Option<HttpApi>,
+    http_address: IpAddr,
+    http_port: u16,
+    /// How the runtime thread is scheduled. New auto splitters are started
+    /// with the same options.
+    thread_options: ThreadOptions,
+    pinned_variables: IndexSet<Box<str>>,
+    /// The break condition as it's being typed, along with why it couldn't
+    /// be parsed.
+    break_condition_input: String,
+    break_condition_error: Option<String>,
+    log_filter: LogFilter,
+    relative_log_times: bool,
+    /// The anchor and the cursor of the range of selected log messages.
+    log_selection: Option<(usize, usize)>,
+    /// The script of timer actions entered in the Run tab, along with why it
+    /// couldn't be parsed and the execution of it.
+    timer_script: String,
+    timer_script_error: Option<String>,
+    timer_script_run: Option<timer_script::Running>,
+    /// The game time that is being entered in the Main tab.
+    game_time_input: String,
+    /// The key and the value of the variable that is being entered in the
+    /// Variables tab.
+    variable_input: (String, String),
     shared_state: Arc<SharedState>,
     timer: DebuggerTimer,
     runtime: livesplit_auto_splitting::Runtime,
 }
 
+#[derive(Copy, Clone, PartialEq)]
+enum ProcessColumn {
+    Pid,
+    Path,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum FunctionColumn {
+    Index,
+    Name,
+    Size,
+}
+
 enum FileDialogInfo {
     Wasm,
     Script,
     SettingsWidget(Arc<str>),
+    VariableHistory,
+    LogFile,
+    LogExport,
+    CrashReport,
+    TrapArtifacts,
+    CargoWorkspace,
+    WasmDirectory,
+    GameExecutable,
+    Scenario,
+    GoldenRun,
+    SaveSession,
+    OpenSession,
+    ProcessMemory(u64),
+    /// One of the two memory dumps to compare.
+    MemoryDump(usize),
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum PerformanceView {
+    TickTimes,
+    Heatmap,
+    Jitter,
 }
 
 struct TabViewer<'a> {
     state: &'a mut AppState,
+    frame_stats: &'a mut FrameStats,
 }
 
 impl egui_dock::TabViewer for TabViewer<'_> {
@@ -331,6 +1425,26 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                         });
                         ui.end_row();
 
+                        ui.label("WASM Directory").on_hover_text("A directory to watch, such as the target directory of a cargo workspace. The most recently modified WASM file in it gets loaded automatically.");
+                        ui.horizontal(|ui| {
+                            if ui.button("Watch").clicked() {
+                                let mut dialog = FileDialog::select_folder(
+                                    self.state.watched_directory.clone(),
+                                );
+                                dialog.open();
+                                self.state.open_file_dialog =
+                                    Some((dialog, FileDialogInfo::WasmDirectory));
+                            }
+                            if let Some(directory) = &self.state.watched_directory {
+                                let directory = directory.display().to_string();
+                                if ui.button("Stop").clicked() {
+                                    self.state.watched_directory = None;
+                                }
+                                ui.label(directory);
+                            }
+                        });
+                        ui.end_row();
+
                         ui.label("Script File")
                             .on_hover_text("A script file that by itself is run by the auto splitter. This is only necessary if the WASM file by itself is a script runtime.");
 
@@ -351,13 +1465,268 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                         });
                         ui.end_row();
 
-                        ui.label("Optimize").on_hover_text("Whether to optimize the WASM file. Don't activate this when you want to step through the source code.");
-                        if ui.checkbox(&mut self.state.optimize, "").changed() {
-                            self.state.runtime = build_runtime(self.state.optimize);
-                            self.state.load(Load::Reload);
+                        if let Some(path) = &self.state.path {
+                            let mut preferences = self.state.preferences.borrow_mut();
+                            let game = preferences.games.get_mut(path);
+                            let mut launch = false;
+
+                            ui.label("Game").on_hover_text("The executable of the game the auto splitter is for. It is remembered for each auto splitter.");
+                            ui.horizontal(|ui| {
+                                if ui.button("Open").clicked() {
+                                    let mut dialog = FileDialog::open_file(
+                                        game.as_ref().map(|g| g.executable.clone()),
+                                    );
+                                    dialog.open();
+                                    self.state.open_file_dialog =
+                                        Some((dialog, FileDialogInfo::GameExecutable));
+                                }
+                                if let Some(game) = &game {
+                                    launch = ui.button("Launch").clicked();
+                                    ui.label(
+                                        game.executable
+                                            .file_name()
+                                            .unwrap_or_default()
+                                            .to_string_lossy(),
+                                    )
+                                    .on_hover_text(game.executable.display().to_string());
+                                }
+                            });
+                            ui.end_row();
+
+                            if let Some(game) = game {
+                                ui.label("Game Arguments");
+                                ui.text_edit_singleline(&mut game.arguments);
+                                ui.end_row();
+
+                                ui.label("Launch on Load")
+                                    .on_hover_text("Whether to launch the game whenever the auto splitter is loaded.");
+                                ui.checkbox(&mut game.launch_on_load, "");
+                                ui.end_row();
+                            }
+
+                            drop(preferences);
+                            if launch {
+                                self.state.launch_game();
+                            }
+                        }
+
+                        ui.label("HTTP API").on_hover_text("A local HTTP server for querying the state of the debugger and controlling it. GET /timer, /variables, /stats, /logs?since=<index> and /settings to query the state. POST /reload, /restart and /kill to control the auto splitter and POST a JSON value to /settings/<key> to change a setting. POST requests need the Content-Type application/json and requests from web pages are rejected.");
+                        ui.horizontal(|ui| {
+                            if let Some(api) = &self.state.http_api {
+                                if ui.button("Stop").clicked() {
+                                    self.state.http_api = None;
+                                } else {
+                                    ui.label(format!("Listening on http://{}", api.address));
+                                    if let Some(token) = &api.token {
+                                        if ui
+                                            .button("Copy Token")
+                                            .on_hover_text("Copy the token that requests from other machines need to send to the clipboard.")
+                                            .clicked()
+                                        {
+                                            ui.ctx().copy_text(token.to_string());
+                                        }
+                                    }
+                                }
+                            } else {
+                                ui.add(egui::DragValue::new(&mut self.state.http_port).prefix("Port: "));
+                                if ui.button("Start").clicked() {
+                                    self.state.start_http_api();
+                                }
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Theme");
+                        let mut preferences = self.state.preferences.borrow_mut();
+                        ComboBox::from_id_source("theme")
+                            .selected_text(preferences.theme.to_str())
+                            .show_ui(ui, |ui| {
+                                for theme in preferences::THEMES {
+                                    ui.selectable_value(
+                                        &mut preferences.theme,
+                                        theme,
+                                        theme.to_str(),
+                                    );
+                                }
+                            });
+                        drop(preferences);
+                        ui.end_row();
+
+                        ui.label("UI Scale");
+                        // Changing the zoom while dragging would move the
+                        // slider away from the pointer, so it's only applied
+                        // once the value is settled.
+                        let mut zoom = ui.ctx().zoom_factor();
+                        let response = ui.add(
+                            egui::Slider::new(&mut zoom, preferences::ZOOM_RANGE)
+                                .step_by(0.05)
+                                .fixed_decimals(2)
+                                .suffix("×"),
+                        );
+                        if response.drag_stopped() || (response.changed() && !response.dragged())
+                        {
+                            ui.ctx().set_zoom_factor(zoom);
+                        }
+                        ui.end_row();
+
+                        ui.label("Auto Reload").on_hover_text("Whether to automatically reload the auto splitter and the script when their files change. Disable this to keep the state of the auto splitter while rebuilding it.");
+                        ui.checkbox(&mut self.state.auto_reload, "");
+                        ui.end_row();
+
+                        ui.label("Cargo Workspace").on_hover_text(format!("The directory of the cargo workspace of the auto splitter. Building it compiles it for {} and loads the resulting WASM file.", cargo_build::TARGET));
+                        ui.horizontal(|ui| {
+                            if ui.button("Open").clicked() {
+                                let mut dialog =
+                                    FileDialog::select_folder(self.state.cargo_workspace.clone());
+                                dialog.open();
+                                self.state.open_file_dialog =
+                                    Some((dialog, FileDialogInfo::CargoWorkspace));
+                            }
+                            if let Some(workspace) = &self.state.cargo_workspace {
+                                if self.state.cargo_build.is_some() {
+                                    ui.spinner();
+                                    ui.label("Building…");
+                                } else if ui.button("Build & Reload").clicked() {
+                                    match CargoBuild::start(workspace) {
+                                        Ok(build) => self.state.cargo_build = Some(build),
+                                        Err(e) => self.state.timer.0.write().unwrap().log(
+                                            format!("Failed to start cargo: {}", e).into(),
+                                            LogType::Build(LogLevel::Error),
+                                        ),
+                                    }
+                                }
+                                ui.label(workspace.display().to_string());
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Execution").on_hover_text("Pause the auto splitter or step through it tick by tick.");
+                        ui.horizontal(|ui| {
+                            let shared_state = &self.state.shared_state;
+                            if shared_state.paused.load(atomic::Ordering::Relaxed) {
+                                ui.label("Paused");
+                                if ui.button("Resume").clicked() {
+                                    shared_state.paused.store(false, atomic::Ordering::Relaxed);
+                                }
+                                if ui.button("Step").clicked() {
+                                    shared_state.step.store(true, atomic::Ordering::Relaxed);
+                                }
+                            } else {
+                                ui.label("Running");
+                                if ui.button("Pause").clicked() {
+                                    shared_state.paused.store(true, atomic::Ordering::Relaxed);
+                                }
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Break On").on_hover_text("Pause the auto splitter right after it performs any of the selected timer actions, so its memory, variables and settings can be inspected at that exact moment.");
+                        ui.horizontal(|ui| {
+                            let break_events = &mut self.state.timer.0.write().unwrap().break_events;
+                            for event in scenario::TIMER_EVENTS {
+                                let mut armed = break_events.contains(&event);
+                                if ui.checkbox(&mut armed, event.to_str()).changed() {
+                                    if armed {
+                                        break_events.push(event);
+                                    } else {
+                                        break_events.retain(|&e| e != event);
+                                    }
+                                }
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Break If").on_hover_text(format!(
+                            "Pause the auto splitter once the condition becomes true after a tick. Conditions compare {} and variable(\"Key\") with ==, !=, <, <=, > and >=, and combine them with &&, || and !. Values that look like numbers are compared as numbers.",
+                            break_condition::FIELDS.map(|(name, _)| name).join(", "),
+                        ));
+                        ui.vertical(|ui| {
+                            let response = ui.add(
+                                egui::TextEdit::singleline(&mut self.state.break_condition_input)
+                                    .hint_text(r#"split_index == 2 && variable("IGT") > "100""#)
+                                    .desired_width(f32::INFINITY),
+                            );
+                            if response.changed() {
+                                let input = self.state.break_condition_input.trim();
+                                let condition = if input.is_empty() {
+                                    Ok(None)
+                                } else {
+                                    BreakCondition::parse(input).map(Some)
+                                };
+                                let mut timer = self.state.timer.0.write().unwrap();
+                                match condition {
+                                    Ok(condition) => {
+                                        timer.break_condition = condition;
+                                        self.state.break_condition_error = None;
+                                    }
+                                    Err(e) => {
+                                        timer.break_condition = None;
+                                        self.state.break_condition_error = Some(e);
+                                    }
+                                }
+                            }
+                            if let Some(e) = &self.state.break_condition_error {
+                                ui.label(RichText::new(e).color(ERROR_COLOR));
+                            }
+                        });
+                        ui.end_row();
+
+                        if self.state.shared_state.trap_stats.lock().unwrap().total() != 0 {
+                            ui.label("Crash Report").on_hover_text("Bundles the logs, the settings map, the runtime configuration and the backtraces of the traps into a zip file to attach to bug reports.");
+                            ui.horizontal(|ui| {
+                                if ui.button("Save").clicked() {
+                                    let mut dialog = FileDialog::save_file(None)
+                                        .default_filename("crash_report.zip");
+                                    dialog.open();
+                                    self.state.open_file_dialog =
+                                        Some((dialog, FileDialogInfo::CrashReport));
+                                }
+                                ui.checkbox(&mut self.state.crash_report_memory, "Include Memory");
+                            });
+                            ui.end_row();
                         }
+
+                        ui.label("Trap Artifacts").on_hover_text("The folder in which the logs, the memory and the settings map are saved automatically whenever the auto splitter traps, with a subfolder per trap. Traps within a minute of the last saved one are not saved.");
+                        ui.horizontal(|ui| {
+                            let mut preferences = self.state.preferences.borrow_mut();
+                            if ui.button("Choose").clicked() {
+                                let mut dialog =
+                                    FileDialog::select_folder(preferences.trap_artifacts.clone());
+                                dialog.open();
+                                self.state.open_file_dialog =
+                                    Some((dialog, FileDialogInfo::TrapArtifacts));
+                            }
+                            match &preferences.trap_artifacts {
+                                Some(folder) => {
+                                    ui.label(folder.display().to_string());
+                                    if ui.button("Disable").clicked() {
+                                        preferences.trap_artifacts = None;
+                                    }
+                                }
+                                None => {
+                                    ui.label("Disabled");
+                                }
+                            }
+                        });
                         ui.end_row();
 
+                        if let Some(info) = &self.state.module_info {
+                            ui.label("Capabilities").on_hover_text("The functional areas of the runtime the auto splitter makes use of, based on its imports.");
+                            ui.horizontal_wrapped(|ui| {
+                                let mut any = false;
+                                for capability in module_info::CAPABILITIES {
+                                    if info.uses(capability) {
+                                        any = true;
+                                        ui.label(capability.name).on_hover_text(capability.description);
+                                    }
+                                }
+                                if !any {
+                                    ui.label("None");
+                                }
+                            });
+                            ui.end_row();
+                        }
+
                         {
                             let mut state = self.state.timer.0.write().unwrap();
 
@@ -374,12 +1743,42 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                             });
                             ui.end_row();
 
-                            ui.label("Game Time").on_hover_text("The currently specified game time.");
-                            ui.label(fmt_duration(state.game_time));
+                            ui.label("Game Time").on_hover_text("The currently specified game time. It can also be set by hand, to test how the auto splitter copes with another component driving the game time.");
+                            ui.horizontal(|ui| {
+                                ui.label(fmt_duration(state.game_time));
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut self.state.game_time_input)
+                                        .hint_text("1:23.456")
+                                        .desired_width(80.0),
+                                );
+                                let game_time = timer_script::parse_duration(&self.state.game_time_input);
+                                if ui.add_enabled(game_time.is_some(), egui::Button::new("Set")).clicked() {
+                                    if let Some(game_time) = game_time {
+                                        state.perform_user_action(&timer_script::Command::SetGameTime(game_time));
+                                    }
+                                }
+                            });
                             ui.end_row();
 
                             ui.label("Game Time State").on_hover_text("The current state of the game timer.");
-                            ui.label(state.game_time_state.to_str());
+                            ui.horizontal(|ui| {
+                                ui.label(state.game_time_state.to_str());
+                                if state.game_time_state == GameTimeState::Paused {
+                                    if ui.button("Resume").clicked() {
+                                        state.perform_user_action(&timer_script::Command::ResumeGameTime);
+                                    }
+                                } else if ui.button("Pause").clicked() {
+                                    state.perform_user_action(&timer_script::Command::PauseGameTime);
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label("Paused Game Time").on_hover_text("How long the game timer was paused since the run started, which is the time that load removal took out of the run.");
+                            ui.label(format!(
+                                "{} ({} Pauses)",
+                                fmt_duration(state.run_history.paused_time(Instant::now())),
+                                state.run_history.pauses.len(),
+                            ));
                             ui.end_row();
 
                             ui.label("Split Index").on_hover_text("The index of the current split.");
@@ -387,6 +1786,49 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                             ui.end_row();
                         }
                     });
+
+                if self
+                    .state
+                    .shared_state
+                    .paused
+                    .load(atomic::Ordering::Relaxed)
+                {
+                    let state = self.state.timer.0.read().unwrap();
+                    let (changes, unchanged) = state.host_calls.diff();
+                    ui.add_space(10.0);
+                    egui::CollapsingHeader::new(format!(
+                        "Host Calls of Tick {} ({} changed, {unchanged} unchanged)",
+                        state.tick_index,
+                        changes.len(),
+                    ))
+                    .id_source("host_call_diff")
+                    .show(ui, |ui| {
+                        ui.label("How the calls of the timer's host functions differ from the tick before. Reading process memory is handled by the runtime itself, so those calls aren't included.");
+                        Grid::new("host_call_diff_grid")
+                            .num_columns(2)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for change in changes {
+                                    match change {
+                                        host_calls::Change::New(call) => {
+                                            ui.label(RichText::new("New").color(INFO_COLOR));
+                                            ui.label(call.to_string());
+                                        }
+                                        host_calls::Change::Missing(call) => {
+                                            ui.label(RichText::new("Missing").color(ERROR_COLOR));
+                                            ui.label(call.to_string());
+                                        }
+                                        host_calls::Change::Changed { before, after } => {
+                                            ui.label(RichText::new("Changed").color(CHANGED_COLOR));
+                                            ui.label(format!("{after} (was {})", before.args));
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                }
             }
             Tab::Statistics => {
                 Grid::new("stats_grid")
@@ -394,6 +1836,10 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                     .spacing([10.0, 4.0])
                     .striped(true)
                     .show(ui, |ui| {
+                        ui.label("Tick").on_hover_text("The index of the current tick. It's shown alongside the logs, variable changes, timer events and other data, so they can be correlated to the exact update call.");
+                        ui.label(self.state.timer.0.read().unwrap().tick_index.to_string());
+                        ui.end_row();
+
                         ui.label("Tick Rate").on_hover_text(
                             "The duration between individual calls to the update function.",
                         );
@@ -416,6 +1862,30 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                         )));
                         ui.end_row();
 
+                        ui.label("Tick Time Percentiles").on_hover_text(
+                            "The durations that the given percentage of the executions of the update function stayed below, as well as the slowest duration since the tick times were last cleared.",
+                        );
+                        ui.horizontal(|ui| {
+                            let histogram = self.state.shared_state.tick_times.lock().unwrap();
+                            if histogram.is_empty() {
+                                return;
+                            }
+                            for (name, percentile) in
+                                [("p50", 50.0), ("p90", 90.0), ("p99", 99.0), ("p99.9", 99.9)]
+                            {
+                                ui.label(RichText::new(name).color(time_color(ui)));
+                                ui.label(fmt_duration(time::Duration::nanoseconds(
+                                    histogram.value_at_percentile(percentile) as _,
+                                )));
+                                ui.separator();
+                            }
+                            ui.label(RichText::new("Max").color(time_color(ui)));
+                            ui.label(fmt_duration(time::Duration::nanoseconds(
+                                histogram.max() as _,
+                            )));
+                        });
+                        ui.end_row();
+
                         ui.label("Slowest Tick").on_hover_text(
                             "The slowest duration of the execution of the update function.",
                         );
@@ -433,18 +1903,65 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                         });
                         ui.end_row();
 
+                        ui.label("Missed Ticks").on_hover_text("How many ticks were skipped or started a whole tick late, because a tick or the whole system took too long. The Runtime tab configures how missed ticks are caught up on.");
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                self.state
+                                    .shared_state
+                                    .missed_ticks
+                                    .load(atomic::Ordering::Relaxed)
+                                    .to_string(),
+                            );
+                            if ui.button("Reset").clicked() {
+                                self.state
+                                    .shared_state
+                                    .missed_ticks
+                                    .store(0, atomic::Ordering::Relaxed);
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Compile Time").on_hover_text("How long compiling the auto splitter took when it was last loaded. A module that was reused from the cache didn't need to be compiled.");
+                        ui.label(match (self.state.compile_time, &self.state.module) {
+                            (Some(compile_time), _) => fmt_std_duration(compile_time),
+                            (None, Some(_)) => "Cached".into(),
+                            (None, None) => String::new(),
+                        });
+                        ui.end_row();
+
+                        ui.label("Instantiate Time").on_hover_text("How long instantiating the auto splitter took when it was last started.");
+                        ui.label(self.state.instantiate_time.map(fmt_std_duration).unwrap_or_default());
+                        ui.end_row();
+
                         let handles = self.state.shared_state.handles.load(atomic::Ordering::Relaxed);
                         ui.label("Handles").on_hover_text("The current amount of handles (processes, settings maps, setting values) used by the auto splitter.");
                         ui.label(handles.to_string());
                         ui.end_row();
 
+                        let process_handles = self.state.shared_state.process_handles.load(atomic::Ordering::Relaxed);
+                        ui.label("    Processes").on_hover_text("The processes the auto splitter is attached to.");
+                        ui.label(process_handles.to_string());
+                        ui.end_row();
+
+                        ui.label("    Other").on_hover_text("The settings maps, setting values and any other handles. The runtime doesn't report them separately, so if this keeps climbing, the auto splitter likely doesn't drop the settings maps or values it gets.");
+                        ui.label(handles.saturating_sub(process_handles).to_string());
+                        ui.end_row();
+
+                        let mut handle_trace = self.state.shared_state.handle_trace.lock().unwrap();
+                        ui.label("    Trace Growth").on_hover_text("Records the ticks in which the other handles grew, to find out where the auto splitter leaks them. The runtime doesn't report where a handle got created, so the ticks are the most specific sites available.");
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut handle_trace.enabled, "");
+                            if !handle_trace.growths.is_empty() && ui.button("Clear").clicked() {
+                                handle_trace.growths.clear();
+                            }
+                        });
+                        ui.end_row();
+
                         let memory_usage = self.state.shared_state.memory_usage.load(atomic::Ordering::Relaxed);
                         ui.label("Memory").on_hover_text("The current amount of memory used by the auto splitter (stack, heap, global variables). This excludes the size of the code itself.");
                         ui.horizontal(|ui| {
                             ui.label(
-                                byte_unit::Byte::from_u64(memory_usage as _)
-                                    .get_appropriate_unit(byte_unit::UnitType::Binary)
-                                    .to_string(),
+                                fmt_bytes(memory_usage as _),
                             );
                             if let Some(auto_splitter) = &*self.state.shared_state.auto_splitter.load() {
                                 if ui.button("Dump").clicked() {
@@ -456,7 +1973,7 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                                                 .0
                                                 .write()
                                                 .unwrap()
-                                                .log(format!("Failed to dump memory: {}", e).into(), LogType::Runtime(LogLevel::Error));
+                                                .log(format!("Failed to dump memory: {}", e).into(), LogType::Debugger(LogLevel::Error));
                                         }
                                     } else {
                                         self.state
@@ -464,43 +1981,353 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                                                 .0
                                                 .write()
                                                 .unwrap()
-                                                .log("Timed out waiting for auto splitter.".into(), LogType::Runtime(LogLevel::Error));
+                                                .log("Timed out waiting for auto splitter.".into(), LogType::Debugger(LogLevel::Error));
                                     }
                                 }
                             }
                         });
                         ui.end_row();
+
+                        let peak_memory_usage = &self.state.shared_state.peak_memory_usage;
+                        ui.label("Peak Memory").on_hover_text("The largest amount of memory used by the auto splitter, including the runs before it was reloaded.");
+                        ui.horizontal(|ui| {
+                            ui.label(fmt_bytes(peak_memory_usage.load(atomic::Ordering::Relaxed) as _));
+                            if ui.button("Reset").clicked() {
+                                peak_memory_usage.store(
+                                    self.state.shared_state.memory_usage.load(atomic::Ordering::Relaxed),
+                                    atomic::Ordering::Relaxed,
+                                );
+                            }
+                        });
+                        ui.end_row();
+
+                        let memory_grow_count = self.state.shared_state.memory_grow_count.load(atomic::Ordering::Relaxed);
+                        ui.label("Memory Growths").on_hover_text("How often the memory of the auto splitter grew since it was loaded. Growing the memory is expensive and may cause spikes in the tick time.");
+                        ui.label(memory_grow_count.to_string());
+                        ui.end_row();
+
+                        ui.label("UI Frame Time").on_hover_text("How long the debugger took to draw its own user interface, on average and for the last frame. This doesn't affect the auto splitter, which runs on its own thread.");
+                        ui.label(format!(
+                            "{} (Last: {})",
+                            fmt_std_duration(self.frame_stats.avg_frame_time()),
+                            fmt_std_duration(self.frame_stats.last_frame),
+                        ));
+                        ui.end_row();
+
+                        ui.label("Slowest UI Frame").on_hover_text("The slowest duration it took the debugger to draw its own user interface.");
+                        ui.horizontal(|ui| {
+                            ui.label(fmt_std_duration(self.frame_stats.slowest_frame));
+                            if ui.button("Reset").clicked() {
+                                self.frame_stats.slowest_frame = Duration::ZERO;
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("UI Repaints").on_hover_text("How often the debugger repainted its user interface within the last second.");
+                        ui.label(format!("{} / s", self.frame_stats.repaints_per_second()));
+                        ui.end_row();
+                    });
+
+                let handle_trace = self.state.shared_state.handle_trace.lock().unwrap();
+                if handle_trace.enabled || !handle_trace.growths.is_empty() {
+                    ui.add_space(10.0);
+                    egui::CollapsingHeader::new(format!(
+                        "Handle Growth ({} in {} ticks)",
+                        handle_trace.total(),
+                        handle_trace.growths.len(),
+                    ))
+                    .id_source("handle_growth")
+                    .show(ui, |ui| {
+                        Grid::new("handle_growth_grid")
+                            .num_columns(3)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new("Tick").strong().underline());
+                                ui.label(RichText::new("Time").strong().underline());
+                                ui.label(RichText::new("New Handles").strong().underline());
+                                ui.end_row();
+                                for growth in
+                                    handle_trace.top().into_iter().take(HANDLE_GROWTH_ROWS)
+                                {
+                                    ui.label(growth.tick.to_string());
+                                    ui.label(
+                                        RichText::new(fmt_time_of_day(growth.time))
+                                            .color(time_color(ui)),
+                                    );
+                                    ui.label(growth.count.to_string());
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                }
+                drop(handle_trace);
+
+                ui.add_space(10.0);
+                let mut trap_stats = self.state.shared_state.trap_stats.lock().unwrap();
+                Grid::new("trap_stats_grid")
+                    .num_columns(3)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Trap").strong().underline());
+                        ui.label(RichText::new("Count").strong().underline());
+                        ui.label(RichText::new("Last Seen").strong().underline());
+                        ui.end_row();
+                        for kind in trap_stats::KINDS {
+                            ui.label(kind.to_str());
+                            ui.label(trap_stats.count(kind).to_string());
+                            if let Some(last) = trap_stats.last(kind) {
+                                ui.label(format!("Tick {}", last.tick))
+                                    .on_hover_text(&*last.message);
+                            } else {
+                                ui.label("");
+                            }
+                            ui.end_row();
+                        }
                     });
+                if ui.button("Clear").clicked() {
+                    trap_stats.clear();
+                }
+                drop(trap_stats);
+
+                let mut preferences = self.state.preferences.borrow_mut();
+                if let Some(history) = self
+                    .state
+                    .path
+                    .as_ref()
+                    .and_then(|path| preferences.stats_history.get_mut(path))
+                {
+                    ui.add_space(10.0);
+                    egui::CollapsingHeader::new(format!("History ({} Sessions)", history.len()))
+                        .id_source("stats_history")
+                        .show(ui, |ui| {
+                            ui.label("The tick times and the peak memory of the previous sessions of this auto splitter, measured after it warmed up.");
+                            let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+                            let line = |name, f: fn(&stats_history::Entry) -> Duration| {
+                                Line::new(
+                                    history
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(i, entry)| [i as f64, ms(f(entry))])
+                                        .collect::<PlotPoints>(),
+                                )
+                                .name(name)
+                            };
+                            let (p50, p99) = (
+                                line("p50", |entry| entry.p50).color(BLUE_COLOR),
+                                line("p99", |entry| entry.p99).color(MAGENTA_COLOR),
+                            );
+                            Plot::new("Stats History Plot")
+                                .height(150.0)
+                                .legend(Legend::default())
+                                .x_axis_formatter(|x, _| format!("Session {}", x.value))
+                                .y_axis_formatter(|y, _| format!("{} ms", y.value))
+                                .include_y(0.0)
+                                .allow_zoom(true)
+                                .allow_drag(true)
+                                .show(ui, |plot_ui| {
+                                    plot_ui.line(p50);
+                                    plot_ui.line(p99);
+                                });
+
+                            Grid::new("stats_history_grid")
+                                .num_columns(5)
+                                .spacing([10.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new("Date").strong().underline());
+                                    ui.label(RichText::new("Build").strong().underline());
+                                    ui.label(RichText::new("p50").strong().underline());
+                                    ui.label(RichText::new("p99").strong().underline());
+                                    ui.label(RichText::new("Peak Memory").strong().underline());
+                                    ui.end_row();
+                                    for entry in history.iter().rev() {
+                                        let date = time::OffsetDateTime::from_unix_timestamp(entry.date)
+                                            .map(|date| {
+                                                let date = date.to_offset(self.state.timer.0.read().unwrap().time_zone);
+                                                format!("{} {}", date.date(), fmt_time_of_day(date))
+                                            })
+                                            .unwrap_or_default();
+                                        ui.label(RichText::new(date).color(time_color(ui)));
+                                        ui.label(format!("{:016X}", entry.module_hash));
+                                        ui.label(fmt_std_duration(entry.p50));
+                                        ui.label(fmt_std_duration(entry.p99));
+                                        ui.label(fmt_bytes(entry.peak_memory as _));
+                                        ui.end_row();
+                                    }
+                                });
+                            if ui.button("Clear").clicked() {
+                                history.clear();
+                            }
+                        });
+                }
             }
             Tab::Logs => {
+                let filter = &mut self.state.log_filter;
+                ui.horizontal(|ui| {
+                    ui.label("Filter");
+                    ui.text_edit_singleline(&mut filter.query);
+                    ui.checkbox(&mut filter.use_regex, "Regex")
+                        .on_hover_text("Interpret the filter as a regular expression.");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Sources");
+                    for source in log_filter::SOURCES {
+                        ui.checkbox(filter.show_source_mut(source), source.to_str());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Levels").on_hover_text("The messages of the auto splitter have no level, so they are only hidden through their source.");
+                    for level in log_filter::LEVELS {
+                        ui.checkbox(
+                            filter.show_level_mut(level),
+                            RichText::new(log_level_to_str(level)).color(log_level_color(level)),
+                        );
+                    }
+                });
+                if let Some(e) = filter.prepare() {
+                    ui.label(RichText::new(e.to_string()).color(ERROR_COLOR));
+                }
+                ui.add_space(4.0);
+
                 let mut scroll_to_end = false;
+                let relative_log_times = self.state.relative_log_times;
+                let selection = &mut self.state.log_selection;
+                let mut copy = false;
+                if ui.memory(|m| m.focused().is_none()) {
+                    copy = ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)));
+                    if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::A)) {
+                        *selection = Some((0, usize::MAX));
+                    }
+                }
                 Grid::new("log_grid")
-                    .num_columns(2)
+                    .num_columns(5)
                     .spacing([10.0, 4.0])
                     .striped(true)
                     .show(ui, |ui| {
                         let mut timer = self.state.timer.0.write().unwrap();
-                        for log in &timer.logs {
-                            ui.add(Label::new(RichText::new(&*log.time).color(TIME_COLOR)));
-                            ui.add(
-                                Label::new(RichText::new(&*log.message).color(match log.ty {
-                                    LogType::AutoSplitterMessage => TEXT_COLOR,
-                                    LogType::Runtime(LogLevel::Error) => ERROR_COLOR,
-                                    LogType::Runtime(LogLevel::Warning) => WARN_COLOR,
-                                    _ => INFO_COLOR,
-                                }))
-                                .wrap(),
+                        for (index, log) in timer
+                            .logs
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, log)| filter.matches(log))
+                        {
+                            let level = log.ty.level();
+                            let response = ui
+                                .selectable_label(
+                                    is_log_selected(*selection, index),
+                                    RichText::new(if relative_log_times {
+                                        fmt_duration(log.time - timer.load_time)
+                                    } else {
+                                        fmt_time_of_day(log.time)
+                                    })
+                                    .color(time_color(ui)),
+                                )
+                                .on_hover_text(if relative_log_times {
+                                    fmt_time_of_day(log.time)
+                                } else {
+                                    fmt_duration(log.time - timer.load_time)
+                                });
+                            if response.clicked() {
+                                *selection = match *selection {
+                                    Some((anchor, _)) if ui.input(|i| i.modifiers.shift) => {
+                                        Some((anchor, index))
+                                    }
+                                    _ => Some((index, index)),
+                                };
+                            }
+                            ui.label(RichText::new(log.tick.to_string()).color(time_color(ui)))
+                                .on_hover_text("The tick during which the message was logged.");
+                            ui.label(
+                                RichText::new(log_level_to_str(level))
+                                    .color(log_level_color(level)),
                             );
+                            ui.label(RichText::new(log.ty.source().to_str()).color(time_color(ui)));
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    Label::new(RichText::new(&*log.message).color(match log.ty {
+                                        LogType::AutoSplitterMessage => text_color(ui),
+                                        _ => log_level_color(level),
+                                    }))
+                                    .wrap(),
+                                );
+                                if log.count > 1 {
+                                    ui.label(
+                                        RichText::new(format!("×{}", log.count))
+                                            .strong()
+                                            .color(CHANGED_COLOR),
+                                    )
+                                    .on_hover_text(
+                                        "The amount of times the message got logged in a row.",
+                                    );
+                                }
+                            });
                             ui.end_row();
                         }
-                        if timer.logs.len() != timer.last_logs_len {
-                            timer.last_logs_len = timer.logs.len();
+                        if timer.logs_revision != timer.shown_logs_revision {
+                            timer.shown_logs_revision = timer.logs_revision;
                             scroll_to_end = true;
                         }
                     });
                 ui.horizontal(|ui| {
                     if ui.button("Clear").clicked() {
                         self.state.timer.0.write().unwrap().logs.clear();
+                        *selection = None;
+                    }
+                    if ui
+                        .button("Export")
+                        .on_hover_text("Export all the messages as JSON Lines.")
+                        .clicked()
+                    {
+                        let mut dialog =
+                            FileDialog::save_file(None).default_filename("auto_splitter_logs.jsonl");
+                        dialog.open();
+                        self.state.open_file_dialog = Some((dialog, FileDialogInfo::LogExport));
+                    }
+                    if ui
+                        .add_enabled(selection.is_some(), egui::Button::new("Copy"))
+                        .on_hover_text("Copy the selected messages to the clipboard. Shift-click to select a range and Ctrl+A to select everything.")
+                        .clicked()
+                    {
+                        copy = true;
+                    }
+                    if copy {
+                        let timer = self.state.timer.0.read().unwrap();
+                        let mut text = String::new();
+                        for (_, log) in timer.logs.iter().enumerate().filter(|&(index, log)| {
+                            is_log_selected(*selection, index) && filter.matches(log)
+                        }) {
+                            let _ = writeln!(text, "{log}");
+                        }
+                        if !text.is_empty() {
+                            ui.ctx().copy_text(text);
+                        }
+                    }
+                    ui.checkbox(&mut self.state.relative_log_times, "Relative Time")
+                        .on_hover_text(
+                            "Show the time since the auto splitter was loaded instead of the time of day.",
+                        );
+                    let mut timer = self.state.timer.0.write().unwrap();
+                    ui.checkbox(&mut timer.deduplicate_logs, "Collapse Repeats")
+                        .on_hover_text("Collapse identical consecutive messages into a single entry.");
+                    let mut log_to_file = timer.log_file.is_some();
+                    if ui
+                        .checkbox(&mut log_to_file, "Log to File")
+                        .on_hover_text("Append every new message to a file as it arrives.")
+                        .changed()
+                    {
+                        if log_to_file {
+                            let mut dialog = FileDialog::save_file(None)
+                                .default_filename("auto_splitter_logs.txt");
+                            dialog.open();
+                            self.state.open_file_dialog = Some((dialog, FileDialogInfo::LogFile));
+                        } else {
+                            timer.log_file = None;
+                        }
+                    }
+                    if let Some(log_file) = &timer.log_file {
+                        ui.label(RichText::new(log_file.path().display().to_string()).color(time_color(ui)));
                     }
                 });
                 if scroll_to_end {
@@ -509,17 +2336,117 @@ impl egui_dock::TabViewer for TabViewer<'_> {
             }
             Tab::Variables => {
                 Grid::new("vars_grid")
-                    .num_columns(2)
+                    .num_columns(6)
                     .spacing([10.0, 4.0])
                     .striped(true)
                     .show(ui, |ui| {
-                        let state = self.state.timer.0.read().unwrap();
-                        for (key, value) in &state.variables {
+                        ui.label(RichText::new("Break").strong().underline());
+                        ui.label(RichText::new("Pin").strong().underline());
+                        ui.label(RichText::new("Record").strong().underline());
+                        ui.label(RichText::new("Key").strong().underline());
+                        ui.label(RichText::new("Value").strong().underline());
+                        ui.label(RichText::new("Changed").strong().underline())
+                            .on_hover_text("The tick in which the value last changed.");
+                        ui.end_row();
+
+                        let state = &mut *self.state.timer.0.write().unwrap();
+                        for (key, variable) in &state.variables {
+                            let mut should_break = state.break_variables.contains(key);
+                            if ui
+                                .checkbox(&mut should_break, "")
+                                .on_hover_text(
+                                    "Pause the auto splitter whenever the variable changes.",
+                                )
+                                .changed()
+                            {
+                                if should_break {
+                                    state.break_variables.insert(key.clone());
+                                } else {
+                                    state.break_variables.shift_remove(key);
+                                }
+                            }
+                            let mut pinned = self.state.pinned_variables.contains(key);
+                            if ui
+                                .checkbox(&mut pinned, "")
+                                .on_hover_text(
+                                    "Pin the variable to the strip at the top of the window.",
+                                )
+                                .changed()
+                            {
+                                if pinned {
+                                    self.state.pinned_variables.insert(key.clone());
+                                } else {
+                                    self.state.pinned_variables.shift_remove(key);
+                                }
+                            }
+                            let mut record = state.variable_history.contains_key(key);
+                            if ui
+                                .checkbox(&mut record, "")
+                                .on_hover_text(format!("Record the value of the variable on every tick. Only the last {} values are kept.", variable_history::SAMPLE_CAPACITY))
+                                .changed()
+                            {
+                                if record {
+                                    state.variable_history.insert(key.clone(), VecDeque::new());
+                                } else {
+                                    state.variable_history.shift_remove(key);
+                                }
+                            }
                             ui.label(&**key);
-                            ui.label(&**value);
+                            if ui
+                                .add(
+                                    Label::new(
+                                        RichText::new(&variable.value)
+                                            .color(variable.color(text_color(ui))),
+                                    )
+                                    .sense(egui::Sense::click()),
+                                )
+                                .on_hover_text("Click to edit the value by hand.")
+                                .clicked()
+                            {
+                                self.state.variable_input =
+                                    (key.to_string(), variable.value.clone());
+                            }
+                            ui.label(
+                                RichText::new(format!("Tick {}", variable.last_changed_tick))
+                                    .color(time_color(ui)),
+                            );
                             ui.end_row();
                         }
                     });
+
+                let mut timer = self.state.timer.0.write().unwrap();
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    let (key, value) = &mut self.state.variable_input;
+                    ui.label("Set Variable").on_hover_text("Add or change a variable by hand, like the auto splitter would. The auto splitter overwrites it the next time it sets the variable itself.");
+                    ui.add(egui::TextEdit::singleline(key).hint_text("Key").desired_width(120.0));
+                    ui.add(egui::TextEdit::singleline(value).hint_text("Value").desired_width(160.0));
+                    if ui
+                        .add_enabled(!key.is_empty(), egui::Button::new("Set"))
+                        .clicked()
+                    {
+                        timer.set_variable_by_hand(key, value);
+                    }
+                });
+
+                if !timer.variable_history.is_empty() {
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("Export History").clicked() {
+                            let mut dialog = FileDialog::save_file(None)
+                                .default_filename("variable_history.csv");
+                            dialog.open();
+                            self.state.open_file_dialog =
+                                Some((dialog, FileDialogInfo::VariableHistory));
+                        }
+                        if ui.button("Clear History").clicked() {
+                            timer
+                                .variable_history
+                                .values_mut()
+                                .for_each(VecDeque::clear);
+                        }
+                    });
+                }
             }
             Tab::SettingsGUI => {
                 if let Some(runtime) = &*self.state.shared_state.auto_splitter.load() {
@@ -533,17 +2460,18 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                                     _ => default_value,
                                 };
                                 if ui.checkbox(&mut value, "").changed() {
-                                    loop {
-                                        let old = runtime.settings_map();
-                                        let mut new = old.clone();
-                                        new.insert(
-                                            setting.key.clone(),
-                                            settings::Value::Bool(value),
-                                        );
-                                        if runtime.set_settings_map_if_unchanged(&old, new) {
-                                            break;
-                                        }
-                                    }
+                                    self.state.shared_state.change_settings_map(
+                                        &self.state.timer,
+                                        settings_history::Origin::SettingsGui,
+                                        |old| {
+                                            let mut new = old.clone();
+                                            new.insert(
+                                                setting.key.clone(),
+                                                settings::Value::Bool(value),
+                                            );
+                                            new
+                                        },
+                                    );
                                 }
                                 let label = ui.label(&*setting.description);
                                 if let Some(tooltip) = &setting.tooltip {
@@ -594,17 +2522,20 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                                     })
                                     .changed()
                                 {
-                                    loop {
-                                        let old = runtime.settings_map();
-                                        let mut new = old.clone();
-                                        new.insert(
-                                            setting.key.clone(),
-                                            settings::Value::String(options[selected].key.clone()),
-                                        );
-                                        if runtime.set_settings_map_if_unchanged(&old, new) {
-                                            break;
-                                        }
-                                    }
+                                    self.state.shared_state.change_settings_map(
+                                        &self.state.timer,
+                                        settings_history::Origin::SettingsGui,
+                                        |old| {
+                                            let mut new = old.clone();
+                                            new.insert(
+                                                setting.key.clone(),
+                                                settings::Value::String(
+                                                    options[selected].key.clone(),
+                                                ),
+                                            );
+                                            new
+                                        },
+                                    );
                                 }
                             }
                             settings::WidgetKind::FileSelect { ref filters } => {
@@ -652,58 +2583,627 @@ impl egui_dock::TabViewer for TabViewer<'_> {
 
                     ui.add_space(10.0);
                     if ui.button("Clear").clicked() {
-                        if let Some(runtime) = &*self.state.shared_state.auto_splitter.load() {
-                            runtime.set_settings_map(settings::Map::new());
-                        }
+                        self.state.shared_state.change_settings_map(
+                            &self.state.timer,
+                            settings_history::Origin::SettingsMap,
+                            |_| settings::Map::new(),
+                        );
                     }
                 }
+
+                ui.add_space(10.0);
+                let mut history = self.state.shared_state.settings_history.lock().unwrap();
+                if history.unseen_self_changes != 0 {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(format!(
+                                "The auto splitter changed its own settings {} times. The changes are listed in the history.",
+                                history.unseen_self_changes,
+                            ))
+                            .color(WARN_COLOR),
+                        );
+                        if ui.button("Dismiss").clicked() {
+                            history.unseen_self_changes = 0;
+                        }
+                    });
+                }
+                egui::CollapsingHeader::new(format!("History ({})", history.changes.len()))
+                    .id_source("settings_history")
+                    .show(ui, |ui| {
+                        if ui.button("Clear").clicked() {
+                            history.changes.clear();
+                            history.unseen_self_changes = 0;
+                        }
+                        Grid::new("settings_history_grid")
+                            .num_columns(6)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new("Time").strong().underline());
+                                ui.label(RichText::new("Tick").strong().underline());
+                                ui.label(RichText::new("Origin").strong().underline())
+                                    .on_hover_text("Who changed the setting. Changes that the debugger didn't make were made by the auto splitter itself.");
+                                ui.label(RichText::new("Key").strong().underline());
+                                ui.label(RichText::new("Before").strong().underline());
+                                ui.label(RichText::new("After").strong().underline());
+                                ui.end_row();
+                                for change in history.changes.iter().rev() {
+                                    ui.label(
+                                        RichText::new(fmt_time_of_day(change.time))
+                                            .color(time_color(ui)),
+                                    );
+                                    ui.label(
+                                        RichText::new(change.tick.to_string())
+                                            .color(time_color(ui)),
+                                    );
+                                    if change.origin == settings_history::Origin::AutoSplitter {
+                                        ui.label(
+                                            RichText::new(change.origin.to_str()).color(WARN_COLOR),
+                                        );
+                                    } else {
+                                        ui.label(change.origin.to_str());
+                                    }
+                                    copyable_label(ui, &change.key);
+                                    ui.label(change.before.as_deref().unwrap_or("<Not set>"));
+                                    ui.label(change.after.as_deref().unwrap_or("<Not set>"));
+                                    ui.end_row();
+                                }
+                            });
+                    });
             }
             Tab::Processes => {
+                // Reading the memory maps and the executables and refreshing
+                // the candidates may take a while, so the runtime thread
+                // shouldn't wait for the lock in the meantime.
+                let processes: Vec<ProcessInfo> = self
+                    .state
+                    .shared_state
+                    .processes
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .cloned()
+                    .collect();
+                let is_attached = |pid| processes.iter().any(|p| p.raw_pid == pid);
+                self.state.process_maps.retain(is_attached);
+                self.state
+                    .process_architectures
+                    .retain(|&pid, _| is_attached(pid));
+                self.state
+                    .selected_ranges
+                    .retain(|&(pid, _)| is_attached(pid));
+
                 Grid::new("processes_grid")
-                    .num_columns(2)
+                    .num_columns(3)
                     .spacing([10.0, 4.0])
                     .striped(true)
                     .show(ui, |ui| {
-                        ui.label(RichText::new("PID").strong().underline());
-                        ui.label(RichText::new("Path").strong().underline());
+                        sort_header(ui, "PID", ProcessColumn::Pid, &mut self.state.process_sort);
+                        ui.label(RichText::new("Architecture").strong().underline())
+                            .on_hover_text("The architecture the executable of the process got compiled for. This determines the size of the pointers in its memory.");
+                        sort_header(ui, "Path", ProcessColumn::Path, &mut self.state.process_sort);
                         ui.end_row();
-                        for process in &*self.state.shared_state.processes.lock().unwrap() {
-                            ui.label(&process.pid);
-                            ui.label(&process.path);
+
+                        let mut sorted: Vec<&ProcessInfo> = processes.iter().collect();
+                        let (column, descending) = self.state.process_sort;
+                        sorted.sort_by(|a, b| {
+                            let ordering = match column {
+                                ProcessColumn::Pid => a.raw_pid.cmp(&b.raw_pid),
+                                ProcessColumn::Path => a.path.cmp(&b.path),
+                            };
+                            if descending {
+                                ordering.reverse()
+                            } else {
+                                ordering
+                            }
+                        });
+
+                        for process in sorted {
+                            copyable_label(ui, &process.pid);
+                            let architecture = *self
+                                .state
+                                .process_architectures
+                                .entry(process.raw_pid)
+                                .or_insert_with(|| Architecture::read(Path::new(&process.path)));
+                            ui.label(match architecture {
+                                Some(architecture) => {
+                                    format!("{} ({}-bit)", architecture.name, architecture.bits())
+                                }
+                                None => "Unknown".into(),
+                            });
+                            copyable_label(ui, &process.path);
                             ui.end_row();
                         }
                     });
+
+                let now = Instant::now();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let mut events = self.state.shared_state.process_events.lock().unwrap();
+                    egui::CollapsingHeader::new(format!("History ({})", events.len()))
+                        .id_source("process_events")
+                        .show(ui, |ui| {
+                            if ui.button("Clear").clicked() {
+                                events.clear();
+                            }
+                            Grid::new("process_events_grid")
+                                .num_columns(5)
+                                .spacing([10.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new("Time").strong().underline());
+                                    ui.label(RichText::new("Tick").strong().underline());
+                                    ui.label(RichText::new("Event").strong().underline());
+                                    ui.label(RichText::new("PID").strong().underline());
+                                    ui.label(RichText::new("Path").strong().underline());
+                                    ui.end_row();
+                                    for event in events.iter().rev() {
+                                        ui.label(
+                                            RichText::new(fmt_time_of_day(event.time))
+                                                .color(time_color(ui)),
+                                        );
+                                        ui.label(
+                                            RichText::new(event.tick.to_string())
+                                                .color(time_color(ui)),
+                                        );
+                                        if event.attached {
+                                            ui.label(RichText::new("Attached").color(INFO_COLOR));
+                                        } else {
+                                            ui.label(RichText::new("Detached").color(WARN_COLOR));
+                                        }
+                                        ui.label(event.pid.to_string());
+                                        ui.label(&*event.path);
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    drop(events);
+
+                    egui::CollapsingHeader::new("Candidates")
+                        .id_source("process_candidates")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            let candidates = &mut self.state.process_candidates;
+                            ui.horizontal(|ui| {
+                                ui.label("Process Names").on_hover_text("The names of the processes the auto splitter attaches to, separated by commas. These are guessed from the executable names found in the auto splitter.");
+                                if ui.text_edit_singleline(&mut candidates.names).changed() {
+                                    candidates.invalidate();
+                                }
+                            });
+                            candidates.update(now, |pid| processes.iter().any(|p| p.raw_pid == pid));
+
+                            Grid::new("process_candidates_grid")
+                                .num_columns(4)
+                                .spacing([10.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new("Name").strong().underline());
+                                    ui.label(RichText::new("PID").strong().underline());
+                                    ui.label(RichText::new("Process").strong().underline());
+                                    ui.label(RichText::new("Status").strong().underline());
+                                    ui.end_row();
+                                    for candidate in &candidates.candidates {
+                                        ui.label(&*candidate.name);
+                                        ui.label(candidate.pid.map(|pid| pid.to_string()).unwrap_or_default());
+                                        ui.label(&*candidate.process_name);
+                                        let color = match candidate.status {
+                                            process_candidates::Status::Attached => INFO_COLOR,
+                                            process_candidates::Status::NotAttached => text_color(ui),
+                                            _ => WARN_COLOR,
+                                        };
+                                        ui.label(RichText::new(candidate.status.to_str()).color(color))
+                                            .on_hover_text(candidate.status.description());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+
+                    for process in &processes {
+                        egui::CollapsingHeader::new(format!("{} ({})", process.path, process.pid))
+                            .id_source(("process_maps", process.raw_pid))
+                            .show(ui, |ui| {
+                                match self.state.process_maps.get(process.raw_pid, now) {
+                                    Ok(maps) => {
+                                        if render_process_maps(
+                                            ui,
+                                            process.raw_pid,
+                                            maps,
+                                            &mut self.state.selected_ranges,
+                                        ) {
+                                            let mut dialog = FileDialog::select_folder(None);
+                                            dialog.open();
+                                            self.state.open_file_dialog = Some((
+                                                dialog,
+                                                FileDialogInfo::ProcessMemory(process.raw_pid),
+                                            ));
+                                        }
+                                    }
+                                    Err(e) => {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "Failed to read the memory map: {e}"
+                                            ))
+                                            .color(ERROR_COLOR),
+                                        );
+                                    }
+                                }
+                            });
+                    }
+
+                    let mut pointer_paths = self.state.shared_state.pointer_paths.lock().unwrap();
+                    egui::CollapsingHeader::new(format!("Pointer Paths ({})", pointer_paths.len()))
+                        .id_source("pointer_paths")
+                        .show(ui, |ui| {
+                            let draft = &mut self.state.pointer_path_draft;
+                            if draft.pid.is_none_or(|pid| !is_attached(pid)) {
+                                draft.pid = processes.first().map(|p| p.raw_pid);
+                            }
+                            ui.horizontal(|ui| {
+                                ComboBox::from_id_source("pointer_path_process")
+                                    .selected_text(draft.pid.map(|pid| pid.to_string()).unwrap_or_default())
+                                    .show_ui(ui, |ui| {
+                                        for process in &processes {
+                                            ui.selectable_value(
+                                                &mut draft.pid,
+                                                Some(process.raw_pid),
+                                                format!("{} ({})", process.path, process.pid),
+                                            );
+                                        }
+                                    });
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut draft.module)
+                                        .hint_text("Module")
+                                        .desired_width(120.0),
+                                );
+                                ui.label("+");
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut draft.base)
+                                        .hint_text("Base")
+                                        .desired_width(80.0),
+                                )
+                                .on_hover_text("The hexadecimal address relative to the module.");
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut draft.offsets)
+                                        .hint_text("Offsets")
+                                        .desired_width(120.0),
+                                )
+                                .on_hover_text("Hexadecimal offsets separated by commas. Before each offset is added, a pointer is read from the current address.");
+                                ComboBox::from_id_source("pointer_path_type")
+                                    .selected_text(draft.ty.to_str())
+                                    .show_ui(ui, |ui| {
+                                        for ty in pointer_path::VALUE_TYPES {
+                                            ui.selectable_value(&mut draft.ty, ty, ty.to_str());
+                                        }
+                                    });
+                                let parsed = draft
+                                    .pid
+                                    .zip(pointer_path::parse_hex(&draft.base))
+                                    .zip(pointer_path::parse_offsets(&draft.offsets))
+                                    .filter(|_| !draft.module.trim().is_empty());
+                                if ui
+                                    .add_enabled(parsed.is_some(), egui::Button::new("Add"))
+                                    .clicked()
+                                {
+                                    if let Some(((pid, base), offsets)) = parsed {
+                                        let pointer_size = self
+                                            .state
+                                            .process_architectures
+                                            .get(&pid)
+                                            .copied()
+                                            .flatten()
+                                            .map_or(8, |architecture| architecture.pointer_size);
+                                        let process_path = processes
+                                            .iter()
+                                            .find(|p| p.raw_pid == pid)
+                                            .map_or("".into(), |p| p.path.as_str().into());
+                                        pointer_paths.push(PointerPath {
+                                            pid,
+                                            process_path,
+                                            module: draft.module.trim().into(),
+                                            module_base: None,
+                                            base,
+                                            offsets,
+                                            pointer_size,
+                                            ty: draft.ty,
+                                            value: Err("Not read yet.".into()),
+                                        });
+                                    }
+                                }
+                            });
+
+                            let mut remove = None;
+                            Grid::new("pointer_paths_grid")
+                                .num_columns(5)
+                                .spacing([10.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new("PID").strong().underline());
+                                    ui.label(RichText::new("Pointer Path").strong().underline());
+                                    ui.label(RichText::new("Type").strong().underline());
+                                    ui.label(RichText::new("Value").strong().underline())
+                                        .on_hover_text("The value as of the last tick.");
+                                    ui.end_row();
+                                    for (index, pointer_path) in pointer_paths.iter().enumerate() {
+                                        ui.label(pointer_path.pid.to_string());
+                                        copyable_label(ui, &pointer_path.to_string());
+                                        ui.label(pointer_path.ty.to_str());
+                                        match &pointer_path.value {
+                                            Ok(value) => copyable_label(ui, value),
+                                            Err(e) => {
+                                                ui.label(RichText::new(e).color(ERROR_COLOR));
+                                            }
+                                        }
+                                        if ui.button("Remove").clicked() {
+                                            remove = Some(index);
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                            if let Some(index) = remove {
+                                pointer_paths.remove(index);
+                            }
+                        });
+                    drop(pointer_paths);
+
+                    egui::CollapsingHeader::new("Signature Scan")
+                        .id_source("sig_scan")
+                        .show(ui, |ui| {
+                            let scan = &mut self.state.sig_scan;
+                            scan.poll();
+                            if scan.pid.is_none_or(|pid| !is_attached(pid)) {
+                                scan.pid = processes.first().map(|p| p.raw_pid);
+                            }
+                            ui.horizontal(|ui| {
+                                ComboBox::from_id_source("sig_scan_process")
+                                    .selected_text(scan.pid.map(|pid| pid.to_string()).unwrap_or_default())
+                                    .show_ui(ui, |ui| {
+                                        for process in &processes {
+                                            ui.selectable_value(
+                                                &mut scan.pid,
+                                                Some(process.raw_pid),
+                                                format!("{} ({})", process.path, process.pid),
+                                            );
+                                        }
+                                    });
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut scan.module)
+                                        .hint_text("Module")
+                                        .desired_width(120.0),
+                                )
+                                .on_hover_text("The module to scan. The whole process is scanned if no module is specified.");
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut scan.signature)
+                                        .hint_text("48 8B 05 ?? ?? ?? ??")
+                                        .font(egui::TextStyle::Monospace),
+                                )
+                                .on_hover_text("Hexadecimal bytes separated by spaces. ?? matches any byte.");
+                                let signature = sig_scan::Signature::parse(&scan.signature);
+                                if scan.running.is_some() {
+                                    ui.spinner();
+                                } else if ui
+                                    .add_enabled(
+                                        signature.is_some() && scan.pid.is_some(),
+                                        egui::Button::new("Scan"),
+                                    )
+                                    .clicked()
+                                {
+                                    if let (Some(signature), Some(pid)) = (signature, scan.pid) {
+                                        match self.state.process_maps.get(pid, now) {
+                                            Ok(maps) => {
+                                                let module = scan.module.trim();
+                                                let module = maps.modules.iter().find(|m| {
+                                                    m.name.eq_ignore_ascii_case(module)
+                                                });
+                                                let ranges = maps
+                                                    .ranges
+                                                    .iter()
+                                                    .filter(|range| range.permissions[0] == b'r')
+                                                    .filter(|range| {
+                                                        module.is_none_or(|module| {
+                                                            (module.base..module.base + module.size)
+                                                                .contains(&range.start)
+                                                        })
+                                                    })
+                                                    .map(|range| (range.start, range.size))
+                                                    .collect();
+                                                if module.is_none() && !scan.module.trim().is_empty() {
+                                                    scan.result = Some(Err(format!(
+                                                        "The module {} is not loaded.",
+                                                        scan.module.trim(),
+                                                    )));
+                                                } else {
+                                                    scan.result = None;
+                                                    scan.running =
+                                                        Some(sig_scan::start(pid, signature, ranges));
+                                                }
+                                            }
+                                            Err(e) => {
+                                                scan.result = Some(Err(format!(
+                                                    "Failed to read the memory map: {e}"
+                                                )));
+                                            }
+                                        }
+                                    }
+                                }
+                            });
+
+                            match &scan.result {
+                                Some(Ok(result)) => {
+                                    ui.label(format!(
+                                        "{} matches in {} of memory, found in {}.",
+                                        result.matches.len(),
+                                        fmt_bytes(result.scanned_bytes),
+                                        fmt_std_duration(result.duration),
+                                    ));
+                                    for &address in &result.matches {
+                                        copyable_label(ui, &format!("{address:#X}"));
+                                    }
+                                }
+                                Some(Err(e)) => {
+                                    ui.label(RichText::new(e).color(ERROR_COLOR));
+                                }
+                                None => {}
+                            }
+                        });
+                });
             }
             Tab::Performance => {
                 let mut histogram = self.state.shared_state.tick_times.lock().unwrap();
+                let mut tick_heatmap = self.state.shared_state.tick_heatmap.lock().unwrap();
+                let mut tick_jitter = self.state.shared_state.tick_jitter.lock().unwrap();
 
-                if ui.button("Clear").clicked() {
-                    histogram.clear();
+                ui.horizontal(|ui| {
+                    if ui.button("Clear").clicked() {
+                        histogram.clear();
+                        tick_heatmap.clear();
+                        tick_jitter.clear();
+                    }
+                    ui.separator();
+                    ui.selectable_value(
+                        &mut self.state.performance_view,
+                        PerformanceView::TickTimes,
+                        "Tick Times",
+                    );
+                    ui.selectable_value(
+                        &mut self.state.performance_view,
+                        PerformanceView::Heatmap,
+                        "Heatmap",
+                    )
+                    .on_hover_text("How many ticks took how long over the last 10 minutes. Slowdowns that happen periodically, like a signature scan that runs every few seconds, show up as stripes.");
+                    ui.selectable_value(
+                        &mut self.state.performance_view,
+                        PerformanceView::Jitter,
+                        "Jitter",
+                    )
+                    .on_hover_text("How late the ticks started compared to when they were scheduled. Auto splitters that measure time themselves may misbehave when ticks arrive late.");
+
+                    if self.state.performance_view == PerformanceView::TickTimes {
+                        ui.separator();
+                        let mut preferences = self.state.preferences.borrow_mut();
+                        let mut enabled = preferences.tick_budget.is_some();
+                        let mut millis = preferences
+                            .tick_budget
+                            .map_or(16.6, |budget| 1000.0 * budget.as_secs_f64());
+                        ui.checkbox(&mut enabled, "Budget").on_hover_text("Highlights the ticks that take longer than the budget, like the 16.6 ms of a frame of LiveSplit at 60 FPS.");
+                        ui.add_enabled(
+                            enabled,
+                            egui::DragValue::new(&mut millis)
+                                .range(0.01..=1000.0)
+                                .speed(0.1)
+                                .suffix(" ms"),
+                        );
+                        preferences.tick_budget =
+                            enabled.then(|| Duration::from_secs_f64(millis / 1000.0));
+                    }
+                });
+
+                // The automatic colors of the plot are too pale on a light
+                // background.
+                let (bar_color, mean_color, median_color, budget_color) = if ui.visuals().dark_mode
+                {
+                    (BLUE_COLOR, YELLOW_COLOR, RED_COLOR, MAGENTA_COLOR)
+                } else {
+                    (
+                        LIGHT_BLUE_COLOR,
+                        LIGHT_YELLOW_COLOR,
+                        LIGHT_RED_COLOR,
+                        LIGHT_MAGENTA_COLOR,
+                    )
+                };
+
+                if self.state.performance_view == PerformanceView::Heatmap {
+                    render_tick_heatmap(ui, &tick_heatmap, bar_color);
+                    return;
+                }
+
+                if self.state.performance_view == PerformanceView::Jitter {
+                    let (mean, max) = if tick_jitter.is_empty() {
+                        (Duration::ZERO, Duration::ZERO)
+                    } else {
+                        (
+                            tick_jitter
+                                .iter()
+                                .map(|&(_, jitter)| jitter)
+                                .sum::<Duration>()
+                                / tick_jitter.len() as u32,
+                            tick_jitter
+                                .iter()
+                                .map(|&(_, jitter)| jitter)
+                                .max()
+                                .unwrap_or_default(),
+                        )
+                    };
+                    ui.label(format!(
+                        "Mean: {}, Max: {} over the last {} ticks",
+                        fmt_std_duration(mean),
+                        fmt_std_duration(max),
+                        tick_jitter.len(),
+                    ));
+
+                    let line =
+                        Line::new(PlotPoints::from_iter(tick_jitter.iter().map(
+                            |&(tick, jitter)| [tick as f64, 1000.0 * jitter.as_secs_f64()],
+                        )))
+                        .color(bar_color)
+                        .name("Jitter");
+
+                    Plot::new("Jitter Plot")
+                        .legend(Legend::default())
+                        .x_axis_formatter(|x, _| format!("Tick {}", x.value))
+                        .y_axis_formatter(|y, _| format!("{} ms", y.value))
+                        .include_y(0.0)
+                        .allow_zoom(true)
+                        .allow_drag(true)
+                        .show(ui, |plot_ui| plot_ui.line(line));
+                    return;
                 }
 
+                let budget = self
+                    .state
+                    .preferences
+                    .borrow()
+                    .tick_budget
+                    .filter(|_| !histogram.is_empty())
+                    .map(|budget| budget.as_nanos() as u64);
+
                 let mut right_x = 0.0;
                 let scale_y = 100.0 / histogram.len() as f64;
 
-                let chart = BarChart::new(
-                    histogram
-                        .iter_recorded()
-                        .map(|bar| {
-                            let left_x = right_x;
-                            right_x = bar.percentile();
-                            let mid_x = 0.5 * (left_x + right_x);
+                let (over_budget, within_budget): (Vec<_>, Vec<_>) = histogram
+                    .iter_recorded()
+                    .map(|bar| {
+                        let left_x = right_x;
+                        right_x = bar.percentile();
+                        let mid_x = 0.5 * (left_x + right_x);
+                        let value = histogram.value_at_percentile(mid_x as _);
+                        let bar =
                             Bar::new(mid_x, scale_y * bar.count_since_last_iteration() as f64)
                                 .name(format!(
                                     "{}\n{:.2}th percentile",
-                                    fmt_duration(time::Duration::nanoseconds(
-                                        histogram.value_at_percentile(mid_x as _) as _,
-                                    )),
+                                    fmt_duration(time::Duration::nanoseconds(value as _)),
                                     mid_x
                                 ))
-                                .width(right_x - left_x)
-                        })
-                        .collect(),
-                )
-                .name("Tick Time");
+                                .width(right_x - left_x);
+                        (bar, budget.is_some_and(|budget| value > budget))
+                    })
+                    .partition(|&(_, over_budget)| over_budget);
+
+                let chart = BarChart::new(within_budget.into_iter().map(|(bar, _)| bar).collect())
+                    .color(bar_color)
+                    .name("Tick Time");
+                let over_budget_chart =
+                    BarChart::new(over_budget.into_iter().map(|(bar, _)| bar).collect())
+                        .color(budget_color)
+                        .name("Over Budget");
+
+                let budget_percentile = budget.map(|budget| histogram.percentile_below(budget));
+                if let Some(percentile) = budget_percentile {
+                    ui.label(format!(
+                        "{:.2}% of the ticks took longer than the budget.",
+                        100.0 - percentile,
+                    ));
+                }
 
                 Plot::new("Performance Plot")
                     .legend(Legend::default())
@@ -724,249 +3224,3628 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                     .show(ui, |plot_ui| {
                         plot_ui.vline(
                             VLine::new(histogram.percentile_below(histogram.mean() as _))
+                                .color(mean_color)
                                 .name("Mean"),
                         );
-                        plot_ui.vline(VLine::new(50.0).name("Median"));
+                        plot_ui.vline(VLine::new(50.0).color(median_color).name("Median"));
+                        if let Some(percentile) = budget_percentile {
+                            plot_ui
+                                .vline(VLine::new(percentile).color(budget_color).name("Budget"));
+                            plot_ui.bar_chart(over_budget_chart);
+                        }
                         plot_ui.bar_chart(chart);
                     });
             }
-        }
-    }
-
-    fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
-        match tab {
-            Tab::Main => "Main",
-            Tab::Statistics => "Statistics",
-            Tab::Logs => "Logs",
-            Tab::Variables => "Variables",
-            Tab::SettingsGUI => "Settings GUI",
-            Tab::SettingsMap => "Settings Map",
-            Tab::Processes => "Processes",
-            Tab::Performance => "Performance",
-        }
-        .into()
-    }
-}
+            Tab::Module => {
+                let Some(info) = &self.state.module_info else {
+                    ui.label("No auto splitter loaded.");
+                    return;
+                };
 
-fn render_settings_map(ui: &mut egui::Ui, settings_map: &settings::Map, path: fmt::Arguments<'_>) {
-    Grid::new(format!("settings_{path}"))
-        .num_columns(2)
-        .spacing([10.0, 4.0])
-        .striped(true)
-        .show(ui, |ui| {
-            ui.label(RichText::new("Key").strong().underline());
-            ui.label(RichText::new("Value").strong().underline());
-            ui.end_row();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("module_overview_grid")
+                        .num_columns(2)
+                        .spacing([10.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.label("File Size");
+                            ui.label(fmt_bytes(info.size as _));
+                            ui.end_row();
 
-            for (key, value) in settings_map.iter() {
-                ui.label(key);
-                render_value(value, ui, format_args!("{path}.{key}"));
-                ui.end_row();
-            }
-        });
-}
+                            ui.label("Functions").on_hover_text(
+                                "The amount of functions defined by the module itself.",
+                            );
+                            ui.label(info.defined_functions.to_string());
+                            ui.end_row();
 
-fn render_settings_list(
-    ui: &mut egui::Ui,
-    settings_list: &settings::List,
-    path: fmt::Arguments<'_>,
-) {
-    Grid::new(format!("settings_{path}"))
-        .num_columns(1)
-        .spacing([10.0, 4.0])
-        .striped(true)
-        .show(ui, |ui| {
-            for (i, value) in settings_list.iter().enumerate() {
-                render_value(value, ui, format_args!("{path}[{i}]"));
-                ui.end_row();
-            }
-        });
-}
+                            for (field, label) in [
+                                ("language", "Language"),
+                                ("processed-by", "Processed By"),
+                                ("sdk", "SDK"),
+                            ] {
+                                let producers = info
+                                    .producers
+                                    .iter()
+                                    .filter(|producer| &*producer.field == field)
+                                    .map(|producer| {
+                                        format!("{} {}", producer.name, producer.version)
+                                    })
+                                    .collect::<Vec<_>>();
+                                if !producers.is_empty() {
+                                    ui.label(label);
+                                    ui.label(producers.join("\n"));
+                                    ui.end_row();
+                                }
+                            }
 
-fn render_value(value: &settings::Value, ui: &mut egui::Ui, path: fmt::Arguments<'_>) {
-    match value {
-        settings::Value::Map(v) => render_settings_map(ui, v, path),
-        settings::Value::List(v) => render_settings_list(ui, v, path),
-        settings::Value::Bool(v) => {
-            ui.label(if *v { "true" } else { "false" });
-        }
-        settings::Value::I64(v) => {
-            ui.label(v.to_string());
-        }
-        settings::Value::F64(v) => {
-            ui.label(v.to_string());
-        }
-        settings::Value::String(v) => {
-            ui.label(&**v);
-        }
-        _ => {
-            ui.label("<Unsupported>");
-        }
-    }
-}
+                            ui.label("asr Version").on_hover_text(
+                                "The version of the asr crate the auto splitter got built with. It is only known for versions from crates.io that appear in the paths of panic messages.",
+                            );
+                            ui.label(info.asr_version.as_deref().unwrap_or("Unknown"));
+                            ui.end_row();
+                        });
 
-impl App for Debugger {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
-        ctx.request_repaint();
+                    egui::CollapsingHeader::new(format!(
+                        "Imports ({})",
+                        info.imports.values().map(Vec::len).sum::<usize>()
+                    ))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for (module, imports) in &info.imports {
+                            egui::CollapsingHeader::new(&**module)
+                                .id_source(("module_imports", module))
+                                .default_open(true)
+                                .show(ui, |ui| render_module_items(ui, module, imports));
+                        }
+                    });
 
-        if let Some(path) = &self.state.path {
-            if fs::metadata(path).ok().and_then(|m| m.modified().ok())
-                > self.state.module_modified_time
-            {
-                self.state.load(Load::Reload);
-            }
-        }
-        if let Some(script_path) = &self.state.script_path {
-            if fs::metadata(script_path)
-                .ok()
-                .and_then(|m| m.modified().ok())
-                > self.state.script_modified_time
-            {
-                self.state.set_script_path(script_path.clone());
-            }
-        }
+                    egui::CollapsingHeader::new(format!("Exports ({})", info.exports.len()))
+                        .default_open(true)
+                        .show(ui, |ui| render_module_items(ui, "exports", &info.exports));
 
-        if let Some((dialog, info)) = &mut self.state.open_file_dialog {
-            if dialog.show(ctx).selected() {
-                if let Some(file) = dialog.path().map(ToOwned::to_owned) {
-                    match info {
-                        FileDialogInfo::Wasm => self.state.load(Load::File(file)),
-                        FileDialogInfo::Script => self.state.set_script_path(file),
-                        FileDialogInfo::SettingsWidget(key) => {
-                            if let Some(s) =
-                                wasi_path::from_native(&file.canonicalize().unwrap_or(file))
-                            {
-                                if let Some(runtime) =
-                                    &*self.state.shared_state.auto_splitter.load()
-                                {
-                                    loop {
-                                        let old = runtime.settings_map();
-                                        let mut new = old.clone();
-                                        new.insert(
-                                            key.clone(),
-                                            settings::Value::String(s.as_ref().into()),
+                    egui::CollapsingHeader::new(format!("Globals ({})", info.globals.len()))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            ui.label("The runtime doesn't expose the instance of the auto splitter, so only the globals that can't change have a known current value. The static variables in its memory are shown with their current values in the Memory tab.");
+                            Grid::new("module_globals_grid")
+                                .num_columns(6)
+                                .spacing([10.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new("Index").strong().underline());
+                                    ui.label(RichText::new("Name").strong().underline());
+                                    ui.label(RichText::new("Type").strong().underline());
+                                    ui.label(RichText::new("Initial Value").strong().underline());
+                                    ui.label(RichText::new("Current Value").strong().underline());
+                                    ui.label(RichText::new("Origin").strong().underline());
+                                    ui.end_row();
+                                    for (index, global) in info.globals.iter().enumerate() {
+                                        ui.label(index.to_string());
+                                        ui.label(global.name.as_deref().unwrap_or_default());
+                                        ui.label(if global.mutable {
+                                            format!("mut {}", global.ty)
+                                        } else {
+                                            global.ty.to_string()
+                                        });
+                                        ui.label(
+                                            global.initial_value.as_deref().unwrap_or_default(),
                                         );
-                                        if runtime.set_settings_map_if_unchanged(&old, new) {
-                                            break;
+                                        match &global.initial_value {
+                                            Some(value) if !global.mutable => {
+                                                ui.label(&**value);
+                                            }
+                                            _ => {
+                                                ui.label("Unknown").on_hover_text(
+                                                    "The runtime doesn't expose the values of mutable or imported globals.",
+                                                );
+                                            }
                                         }
+                                        ui.label(if global.imported {
+                                            "Imported"
+                                        } else {
+                                            "Defined"
+                                        });
+                                        ui.end_row();
                                     }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+                                });
+                        });
 
-        let mut tab_viewer = TabViewer {
-            state: &mut self.state,
+                    egui::CollapsingHeader::new("Memories & Tables")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            Grid::new("module_limits_grid")
+                                .num_columns(4)
+                                .spacing([10.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new("Kind").strong().underline());
+                                    ui.label(RichText::new("Initial").strong().underline());
+                                    ui.label(RichText::new("Maximum").strong().underline());
+                                    ui.label(RichText::new("Origin").strong().underline());
+                                    ui.end_row();
+                                    for memory in &info.memories {
+                                        ui.label(if memory.is_64 { "memory64" } else { "memory" });
+                                        ui.label(fmt_memory_pages(memory.initial))
+                                            .on_hover_text(format!("{} pages", memory.initial));
+                                        match memory.maximum {
+                                            Some(maximum) => ui
+                                                .label(fmt_memory_pages(maximum))
+                                                .on_hover_text(format!("{maximum} pages")),
+                                            None => ui.label("None"),
+                                        };
+                                        ui.label(if memory.imported {
+                                            "Imported"
+                                        } else {
+                                            "Defined"
+                                        });
+                                        ui.end_row();
+                                    }
+                                    for table in &info.tables {
+                                        ui.label(if table.is_64 { "table64" } else { "table" });
+                                        ui.label(format!("{} elements", table.initial));
+                                        ui.label(match table.maximum {
+                                            Some(maximum) => format!("{maximum} elements"),
+                                            None => "None".into(),
+                                        });
+                                        ui.label(if table.imported {
+                                            "Imported"
+                                        } else {
+                                            "Defined"
+                                        });
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+
+                    egui::CollapsingHeader::new(format!(
+                        "Custom Sections ({})",
+                        info.custom_sections.len()
+                    ))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        render_module_sections(
+                            ui,
+                            "module_custom_sections_grid",
+                            info.size,
+                            &info.custom_sections,
+                        );
+                    });
+
+                    egui::CollapsingHeader::new("Size Breakdown")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            render_module_sections(
+                                ui,
+                                "module_sections_grid",
+                                info.size,
+                                &info.sections,
+                            );
+                        });
+
+                    let code_size = info.functions.iter().map(|f| f.size).sum::<usize>();
+                    egui::CollapsingHeader::new(format!("Functions ({})", info.functions.len()))
+                        .show(ui, |ui| {
+                            ui.label(format!(
+                                "The function bodies take up {} of the module.",
+                                fmt_bytes(code_size as _),
+                            ));
+                            let mut functions =
+                                info.functions.iter().enumerate().collect::<Vec<_>>();
+                            let (column, descending) = self.state.function_sort;
+                            match column {
+                                FunctionColumn::Index => {}
+                                FunctionColumn::Name => {
+                                    functions.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name))
+                                }
+                                FunctionColumn::Size => functions.sort_by_key(|(_, f)| f.size),
+                            }
+                            if descending {
+                                functions.reverse();
+                            }
+                            Grid::new("module_functions_grid")
+                                .num_columns(4)
+                                .spacing([10.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    sort_header(
+                                        ui,
+                                        "Index",
+                                        FunctionColumn::Index,
+                                        &mut self.state.function_sort,
+                                    );
+                                    sort_header(
+                                        ui,
+                                        "Name",
+                                        FunctionColumn::Name,
+                                        &mut self.state.function_sort,
+                                    );
+                                    sort_header(
+                                        ui,
+                                        "Size",
+                                        FunctionColumn::Size,
+                                        &mut self.state.function_sort,
+                                    );
+                                    ui.label(RichText::new("Share").strong().underline());
+                                    ui.end_row();
+                                    for (index, function) in
+                                        functions.iter().take(MODULE_FUNCTION_ROWS)
+                                    {
+                                        ui.label(
+                                            (*index as u32 + info.imported_functions).to_string(),
+                                        );
+                                        ui.label(function.name.as_deref().unwrap_or_default());
+                                        ui.label(fmt_bytes(function.size as _));
+                                        ui.label(format!(
+                                            "{:.1}%",
+                                            100.0 * function.size as f64 / info.size.max(1) as f64,
+                                        ));
+                                        ui.end_row();
+                                    }
+                                });
+                            if functions.len() > MODULE_FUNCTION_ROWS {
+                                ui.label(format!(
+                                    "Showing {MODULE_FUNCTION_ROWS} of {} functions.",
+                                    functions.len(),
+                                ));
+                            }
+                        });
+
+                    egui::CollapsingHeader::new(format!(
+                        "Data Segments ({})",
+                        info.data_segments.len()
+                    ))
+                    .show(ui, |ui| {
+                        let mut segments =
+                            info.data_segments.iter().enumerate().collect::<Vec<_>>();
+                        segments.sort_by_key(|&(_, &size)| std::cmp::Reverse(size));
+                        Grid::new("module_data_segments_grid")
+                            .num_columns(3)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new("Index").strong().underline());
+                                ui.label(RichText::new("Size").strong().underline());
+                                ui.label(RichText::new("Share").strong().underline());
+                                ui.end_row();
+                                for (index, &size) in segments {
+                                    ui.label(index.to_string());
+                                    ui.label(fmt_bytes(size as _));
+                                    ui.label(format!(
+                                        "{:.1}%",
+                                        100.0 * size as f64 / info.size.max(1) as f64,
+                                    ));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                });
+            }
+            Tab::MemoryDiff => {
+                let diff = &mut self.state.memory_diff;
+                Grid::new("memory_diff_files")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .show(ui, |ui| {
+                        for (index, name) in ["Before", "After"].into_iter().enumerate() {
+                            ui.label(name);
+                            ui.horizontal(|ui| {
+                                if ui.button("Open").clicked() {
+                                    let mut dialog = FileDialog::open_file(
+                                        diff.dumps[index].as_ref().map(|(path, _)| path.clone()),
+                                    );
+                                    dialog.open();
+                                    self.state.open_file_dialog =
+                                        Some((dialog, FileDialogInfo::MemoryDump(index)));
+                                }
+                                if let Some((path, data)) = &diff.dumps[index] {
+                                    ui.label(format!(
+                                        "{} ({})",
+                                        path.display(),
+                                        fmt_bytes(data.len() as _),
+                                    ));
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+
+                let differing_bytes = diff.differing_bytes();
+                let memory_diff::MemoryDiff {
+                    dumps,
+                    ranges,
+                    selected,
+                } = diff;
+                let [Some((_, before)), Some((_, after))] = &*dumps else {
+                    ui.label("Open two memory dumps to compare them.");
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} differing ranges, {} differ.",
+                        ranges.len(),
+                        fmt_bytes(differing_bytes as _),
+                    ));
+                    if !ranges.is_empty() {
+                        ui.separator();
+                        if ui.button("Previous").clicked() {
+                            *selected = Some(selected.map_or(0, |i| i.saturating_sub(1)));
+                        }
+                        if ui.button("Next").clicked() {
+                            *selected = Some(selected.map_or(0, |i| (i + 1).min(ranges.len() - 1)));
+                        }
+                    }
+                });
+
+                egui::ScrollArea::vertical()
+                    .id_source("memory_diff_ranges")
+                    .max_height(ui.available_height() / 2.0)
+                    .show(ui, |ui| {
+                        Grid::new("memory_diff_ranges_grid")
+                            .num_columns(4)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new("Offset").strong().underline());
+                                ui.label(RichText::new("Size").strong().underline());
+                                ui.label(RichText::new("Before").strong().underline());
+                                ui.label(RichText::new("After").strong().underline());
+                                ui.end_row();
+                                for (index, range) in ranges.iter().enumerate() {
+                                    if ui
+                                        .selectable_label(
+                                            *selected == Some(index),
+                                            RichText::new(format!("{:#X}", range.offset))
+                                                .monospace(),
+                                        )
+                                        .clicked()
+                                    {
+                                        *selected = Some(index);
+                                    }
+                                    ui.label(fmt_bytes(range.len as _));
+                                    let preview_len = range.len.min(MEMORY_DIFF_PREVIEW_LEN);
+                                    ui.label(
+                                        RichText::new(memory_diff::hex(
+                                            before,
+                                            range.offset,
+                                            preview_len,
+                                        ))
+                                        .monospace(),
+                                    );
+                                    ui.label(
+                                        RichText::new(memory_diff::hex(
+                                            after,
+                                            range.offset,
+                                            preview_len,
+                                        ))
+                                        .monospace(),
+                                    );
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                let Some(range) = selected.and_then(|i| ranges.get(i)) else {
+                    return;
+                };
+                ui.separator();
+                // Shows a few rows of context around the difference.
+                let start = (range.offset / 16 * 16).saturating_sub(32);
+                let end = (range.offset + range.len).min(start + 16 * MEMORY_DIFF_MAX_ROWS) + 32;
+                egui::ScrollArea::vertical()
+                    .id_source("memory_diff_hex")
+                    .show(ui, |ui| {
+                        Grid::new("memory_diff_hex_grid")
+                            .num_columns(3)
+                            .spacing([20.0, 2.0])
+                            .show(ui, |ui| {
+                                for offset in (start..end).step_by(16) {
+                                    ui.label(
+                                        RichText::new(format!("{offset:08X}"))
+                                            .monospace()
+                                            .color(time_color(ui)),
+                                    );
+                                    ui.label(hex_diff_row(ui, before, after, offset));
+                                    ui.label(hex_diff_row(ui, after, before, offset));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+            Tab::Memory => {
+                let (Some(data), Some(_)) = (
+                    &self.state.module_data,
+                    &*self.state.shared_state.auto_splitter.load(),
+                ) else {
+                    ui.label("No auto splitter loaded.");
+                    return;
+                };
+
+                let variables = match self.state.global_variables.get_or_insert_with(|| {
+                    debug_info::global_variables(data).map_err(|e| format!("{e:?}"))
+                }) {
+                    Ok(variables) => &**variables,
+                    Err(e) => {
+                        ui.label(
+                            RichText::new(format!("Failed to read the debug information: {e}"))
+                                .color(ERROR_COLOR),
+                        );
+                        &[]
+                    }
+                };
+
+                egui::CollapsingHeader::new(format!("Global Variables ({})", variables.len()))
+                    .id_source("global_variables")
+                    .show(ui, |ui| {
+                        if variables.is_empty() {
+                            ui.label("The auto splitter has no debug information about its global variables. Build it with debug information to see them.");
+                            return;
+                        }
+                        // The runtime thread copies the values out of the
+                        // memory after each tick, along with the visible rows
+                        // of the memory view.
+                        let snapshot = self.state.shared_state.memory_view.snapshot();
+                        egui::ScrollArea::vertical()
+                            .id_source("global_variables_scroll")
+                            .max_height(ui.available_height() / 3.0)
+                            .show(ui, |ui| {
+                                Grid::new("global_variables_grid")
+                                    .num_columns(5)
+                                    .spacing([10.0, 4.0])
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        ui.label(RichText::new("Address").strong().underline());
+                                        ui.label(RichText::new("Name").strong().underline());
+                                        ui.label(RichText::new("Type").strong().underline());
+                                        ui.label(RichText::new("Size").strong().underline());
+                                        ui.label(RichText::new("Value").strong().underline());
+                                        ui.end_row();
+                                        for variable in variables {
+                                            if ui
+                                                .link(
+                                                    RichText::new(format!("{:#X}", variable.address))
+                                                        .monospace(),
+                                                )
+                                                .on_hover_text("Shows the variable in the memory view.")
+                                                .clicked()
+                                            {
+                                                self.state.memory_view_target = Some(variable.address);
+                                            }
+                                            copyable_label(ui, &variable.name);
+                                            ui.label(&*variable.type_name);
+                                            ui.label(variable.size.map(fmt_bytes).unwrap_or_default());
+                                            match variable.value_range().and_then(|range| snapshot.get(range)) {
+                                                Some(bytes) => copyable_label(ui, &variable.fmt_value(bytes)),
+                                                None => {
+                                                    ui.label("");
+                                                }
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                    });
+                ui.separator();
+
+                let memory_len = self
+                    .state
+                    .shared_state
+                    .memory_usage
+                    .load(atomic::Ordering::Relaxed);
+                let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+                let row_height =
+                    ui.fonts(|fonts| fonts.row_height(&font_id)) + ui.spacing().item_spacing.y;
+                let mut scroll_area = egui::ScrollArea::vertical()
+                    .id_source("memory_view")
+                    .auto_shrink(false);
+                if let Some(address) = self.state.memory_view_target.take() {
+                    scroll_area =
+                        scroll_area.vertical_scroll_offset((address / 16) as f32 * row_height);
+                }
+                let (text, highlights) = (text_color(ui), [BLUE_COLOR, YELLOW_COLOR]);
+                let highlights = if ui.visuals().dark_mode {
+                    highlights
+                } else {
+                    [LIGHT_BLUE_COLOR, LIGHT_YELLOW_COLOR]
+                };
+                let memory_view = &self.state.shared_state.memory_view;
+                scroll_area.show_rows(ui, row_height, memory_len.div_ceil(16), |ui, rows| {
+                    // The runtime thread copies the visible rows after each
+                    // tick. The rows around them are requested as well, so
+                    // scrolling doesn't have to wait for the next copy.
+                    let (start, end) = (rows.start * 16, rows.end * 16);
+                    let margin = end - start;
+                    memory_view.request(
+                        iter::once(start.saturating_sub(margin)..end + margin)
+                            .chain(variables.iter().filter_map(|v| v.value_range())),
+                    );
+                    let snapshot = memory_view.snapshot();
+                    let Some(memory) = snapshot.get_prefix(start..end) else {
+                        ui.ctx().request_repaint();
+                        return;
+                    };
+
+                    for (row, bytes) in rows.zip(memory.chunks(16)) {
+                        let address = row as u64 * 16;
+                        let mut job = egui::text::LayoutJob::default();
+                        job.append(
+                            &format!("{address:08X}  "),
+                            0.0,
+                            egui::TextFormat::simple(font_id.clone(), time_color(ui)),
+                        );
+                        for (i, byte) in bytes.iter().enumerate() {
+                            let address = address + i as u64;
+                            // The variables are sorted by their address, so the
+                            // one containing the byte is the last one starting
+                            // before it.
+                            let index = variables.partition_point(|v| v.address <= address);
+                            let color = match index.checked_sub(1) {
+                                Some(index)
+                                    if address
+                                        < variables[index].address
+                                            + variables[index].size.unwrap_or(1) =>
+                                {
+                                    highlights[index % 2]
+                                }
+                                _ => text,
+                            };
+                            job.append(
+                                &format!("{byte:02X}"),
+                                if i == 0 { 0.0 } else { 6.0 },
+                                egui::TextFormat::simple(font_id.clone(), color),
+                            );
+                        }
+                        let names = variables
+                            .iter()
+                            .filter(|v| (address..address + 16).contains(&v.address))
+                            .map(|v| format!("{}: {}", v.name, v.type_name))
+                            .collect::<Vec<_>>();
+                        if !names.is_empty() {
+                            job.append(
+                                &names.join(", "),
+                                12.0,
+                                egui::TextFormat::simple(font_id.clone(), text),
+                            );
+                        }
+                        ui.label(job);
+                    }
+                });
+            }
+            Tab::Disassembly => {
+                let Some(data) = &self.state.module_data else {
+                    ui.label("No auto splitter loaded.");
+                    return;
+                };
+
+                let disassembly = match &mut self.state.disassembly {
+                    Some(Ok(disassembly)) => disassembly,
+                    Some(Err(e)) => {
+                        ui.label(RichText::new(&*e).color(ERROR_COLOR));
+                        return;
+                    }
+                    None => {
+                        if ui
+                            .button("Disassemble")
+                            .on_hover_text(
+                                "Disassembles the auto splitter into the WebAssembly text format.",
+                            )
+                            .clicked()
+                        {
+                            self.state.disassembly =
+                                Some(Disassembly::new(data).map_err(|e| format!("{e:?}")));
+                        }
+                        return;
+                    }
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label("Search");
+                    ui.text_edit_singleline(&mut disassembly.query);
+                });
+                ui.separator();
+
+                let matches = disassembly.search().to_vec();
+                ui.label(format!(
+                    "{} of {} functions",
+                    matches.len(),
+                    disassembly.functions.len(),
+                ));
+
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    for index in matches {
+                        let function = &disassembly.functions[index];
+                        egui::CollapsingHeader::new(RichText::new(&*function.name).monospace())
+                            .id_source(("disassembly_function", index))
+                            .show(ui, |ui| {
+                                ui.add(
+                                    Label::new(RichText::new(&function.text).monospace()).extend(),
+                                );
+                            });
+                    }
+                });
+            }
+            Tab::Runtime => {
+                let config = &mut self.state.pending_runtime_config;
+                Grid::new("runtime_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Optimize").on_hover_text("Whether to optimize the WASM file. Don't activate this when you want to step through the source code.");
+                        ui.checkbox(&mut config.optimize, "");
+                        ui.end_row();
+
+                        ui.label("Debug Info").on_hover_text("Whether to generate debug information for the WASM file, so a native debugger can step through its source code.");
+                        ui.checkbox(&mut config.debug_info, "");
+                        ui.end_row();
+
+                        ui.label("Backtrace Details").on_hover_text("Whether to include file names and line numbers in the backtraces of errors. This requires the WASM file to contain DWARF debug information.");
+                        ui.checkbox(&mut config.backtrace_details, "");
+                        ui.end_row();
+                    });
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    let changed = self.state.pending_runtime_config != self.state.runtime_config;
+                    if ui
+                        .add_enabled(changed, egui::Button::new("Apply & Reload"))
+                        .clicked()
+                    {
+                        self.state.apply_runtime_config();
+                    }
+                    if ui
+                        .add_enabled(changed, egui::Button::new("Revert"))
+                        .clicked()
+                    {
+                        self.state.pending_runtime_config = self.state.runtime_config;
+                    }
+                });
+
+                ui.separator();
+                Grid::new("runtime_limits_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        let mut max_tick_duration =
+                            self.state.shared_state.max_tick_duration.lock().unwrap();
+                        ui.label("Max Tick Duration").on_hover_text("Interrupts the auto splitter if a single call to the update function takes longer than this. This takes effect immediately.");
+                        ui.horizontal(|ui| {
+                            let mut enabled = max_tick_duration.is_some();
+                            let mut millis = max_tick_duration.map_or(1000, |d| d.as_millis() as u64);
+                            ui.checkbox(&mut enabled, "");
+                            ui.add_enabled(
+                                enabled,
+                                egui::DragValue::new(&mut millis)
+                                    .range(1..=60_000)
+                                    .suffix(" ms"),
+                            );
+                            *max_tick_duration = enabled.then(|| Duration::from_millis(millis));
+                        });
+                        ui.end_row();
+
+                        let auto_restart = &mut self.state.auto_restart;
+                        ui.label("Auto Restart").on_hover_text("Automatically restarts the auto splitter after it trapped, doubling the delay with every consecutive retry.");
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut auto_restart.enabled, "");
+                            if auto_restart.enabled {
+                                ui.label(format!(
+                                    "{} of {} retries used",
+                                    auto_restart.retries(),
+                                    auto_restart.max_retries,
+                                ));
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Max Retries").on_hover_text("How often the auto splitter gets restarted in a row before giving up.");
+                        ui.add_enabled(
+                            auto_restart.enabled,
+                            egui::DragValue::new(&mut auto_restart.max_retries).range(1..=100),
+                        );
+                        ui.end_row();
+
+                        ui.label("Restart Delay").on_hover_text("How long to wait before the first restart.");
+                        let mut millis = auto_restart.delay.as_millis() as u64;
+                        ui.add_enabled(
+                            auto_restart.enabled,
+                            egui::DragValue::new(&mut millis)
+                                .range(0..=60_000)
+                                .suffix(" ms"),
+                        );
+                        auto_restart.delay = Duration::from_millis(millis);
+                        ui.end_row();
+
+                        let mut catch_up = self.state.shared_state.catch_up.lock().unwrap();
+                        ui.label("Catch Up").on_hover_text("What to do about ticks that couldn't happen on time, because a tick or the whole system took too long, like after the system was suspended. Different behaviors expose different bugs of auto splitters.");
+                        ui.horizontal(|ui| {
+                            let mut max = match *catch_up {
+                                CatchUp::Capped(max) => max,
+                                _ => 10,
+                            };
+                            ComboBox::from_id_source("catch_up")
+                                .selected_text(match *catch_up {
+                                    CatchUp::Skip => "Skip Missed Ticks",
+                                    CatchUp::All => "Run All Missed Ticks",
+                                    CatchUp::Capped(_) => "Run Recent Missed Ticks",
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut *catch_up, CatchUp::Skip, "Skip Missed Ticks");
+                                    ui.selectable_value(&mut *catch_up, CatchUp::All, "Run All Missed Ticks");
+                                    ui.selectable_value(&mut *catch_up, CatchUp::Capped(max), "Run Recent Missed Ticks");
+                                });
+                            if let CatchUp::Capped(_) = *catch_up {
+                                ui.add(egui::DragValue::new(&mut max).range(1..=1000).prefix("Max: "));
+                                *catch_up = CatchUp::Capped(max);
+                            }
+                        });
+                        drop(catch_up);
+                        ui.end_row();
+
+                        let requested = self.state.thread_options;
+                        let applied = *self.state.shared_state.applied_thread_options.lock().unwrap();
+                        ui.label("Thread Priority").on_hover_text("The priority of the runtime thread. Start the debugger with --high-priority to raise it.");
+                        let label = ui.label(if applied.high_priority { "High" } else { "Normal" });
+                        if requested.high_priority && !applied.high_priority {
+                            label.on_hover_text("Raising the priority failed. Check the logs for the reason.");
+                        }
+                        ui.end_row();
+
+                        ui.label("CPU Core").on_hover_text("The CPU core the runtime thread is pinned to. Start the debugger with --pin-core <INDEX> to pin it.");
+                        let label = ui.label(applied.core.map_or_else(|| "Any".into(), |core| core.to_string()));
+                        if requested.core.is_some() && applied.core.is_none() {
+                            label.on_hover_text("Pinning the thread to the core failed. Check the logs for the reason.");
+                        }
+                        ui.end_row();
+                    });
+            }
+            Tab::Run => {
+                egui::CollapsingHeader::new("Timer Script")
+                    .id_source("timer_script")
+                    .show(ui, |ui| {
+                        ui.label("Performs timer actions as if the user did them, to test how the auto splitter copes with them. Each line is one of start, split, skip_split, undo_split, reset, pause_game_time, resume_game_time, set_game_time 1:23.456 or wait 500ms.");
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.state.timer_script)
+                                .code_editor()
+                                .desired_rows(4)
+                                .desired_width(f32::INFINITY)
+                                .hint_text("start\nwait 500ms\nset_game_time 1:23.456\nsplit"),
+                        );
+                        if self
+                            .state
+                            .timer_script_run
+                            .as_ref()
+                            .is_some_and(timer_script::Running::is_finished)
+                        {
+                            self.state.timer_script_run = None;
+                        }
+                        ui.horizontal(|ui| {
+                            if let Some(run) = &self.state.timer_script_run {
+                                ui.spinner();
+                                if ui.button("Stop").clicked() {
+                                    run.stop();
+                                }
+                            } else if ui.button("Run").clicked() {
+                                match timer_script::parse(&self.state.timer_script) {
+                                    Ok(commands) => {
+                                        self.state.timer_script_error = None;
+                                        let timer = self.state.timer.clone();
+                                        self.state.timer_script_run =
+                                            Some(timer_script::start(commands, move |command| {
+                                                let mut timer = timer.0.write().unwrap();
+                                                timer.perform_user_action(command);
+                                                timer.log(
+                                                    format!("The timer script performed {}.", command.to_str()).into(),
+                                                    LogType::Debugger(LogLevel::Info),
+                                                );
+                                            }));
+                                    }
+                                    Err(e) => self.state.timer_script_error = Some(e),
+                                }
+                            }
+                            if let Some(e) = &self.state.timer_script_error {
+                                ui.label(RichText::new(e).color(ERROR_COLOR));
+                            }
+                        });
+                    });
+
+                let mut run_scenario = false;
+                let mut record = false;
+                ui.horizontal(|ui| {
+                    ui.label("Scenario").on_hover_text("A JSON file with the timer events the auto splitter is expected to cause at specific ticks.");
+                    if ui.button("Open").clicked() {
+                        let mut dialog = FileDialog::open_file(self.state.scenario_path.clone());
+                        dialog.open();
+                        self.state.open_file_dialog = Some((dialog, FileDialogInfo::Scenario));
+                    }
+                    record = ui
+                        .button("Record")
+                        .on_hover_text("Restart the auto splitter and record its timer events, so they can be saved as a golden run to compare later runs against.")
+                        .clicked();
+                    if let Some(path) = &self.state.scenario_path {
+                        run_scenario = ui
+                            .button("Run")
+                            .on_hover_text("Restart the auto splitter and check its timer events against the scenario.")
+                            .clicked();
+                        ui.label(path.file_name().unwrap_or_default().to_string_lossy())
+                            .on_hover_text(path.display().to_string());
+                    }
+                });
+                if run_scenario {
+                    self.state.run_scenario();
+                }
+                if record {
+                    self.state.start_scenario(
+                        ScenarioRun::recording(0),
+                        "Started recording the timer events.",
+                    );
+                }
+
+                // The timer is only locked for writing once the user clicked
+                // something, so the runtime isn't held up while rendering.
+                let mut stop_scenario = false;
+                let mut clear_suspicious_actions = false;
+                let mut clear_run_history = false;
+                let timer = self.state.timer.0.read().unwrap();
+
+                if let Some(run) = &timer.scenario {
+                    let evaluation = run.evaluate(timer.tick_index);
+                    egui::CollapsingHeader::new("Scenario Results")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(if run.is_recording() {
+                                    format!("{} recorded", run.events.len())
+                                } else {
+                                    format!(
+                                        "{} passed, {} failed, {} pending",
+                                        evaluation.passed(),
+                                        evaluation.failed(),
+                                        evaluation.pending(),
+                                    )
+                                });
+                                stop_scenario = ui.button("Stop").clicked();
+                                if ui
+                                    .button("Save as Golden Run")
+                                    .on_hover_text("Save the recorded timer events as a scenario that expects the exact same events. The timer actions and settings changes made by hand while recording are replayed when checking it.")
+                                    .clicked()
+                                {
+                                    let mut dialog = FileDialog::save_file(None)
+                                        .default_filename("golden_run.json");
+                                    dialog.open();
+                                    self.state.open_file_dialog =
+                                        Some((dialog, FileDialogInfo::GoldenRun));
+                                }
+                            });
+                            Grid::new("scenario_grid")
+                                .num_columns(3)
+                                .spacing([10.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new("Tick").strong().underline());
+                                    ui.label(RichText::new("Event").strong().underline());
+                                    ui.label(RichText::new("Result").strong().underline());
+                                    ui.end_row();
+                                    for (expected, outcome) in
+                                        run.scenario.events.iter().zip(&evaluation.outcomes)
+                                    {
+                                        ui.label(
+                                            RichText::new(expected.tick.to_string())
+                                                .color(time_color(ui)),
+                                        );
+                                        ui.label(expected.event.to_str());
+                                        match outcome {
+                                            scenario::Outcome::Pending => {
+                                                ui.label("Pending");
+                                            }
+                                            scenario::Outcome::Passed => {
+                                                ui.label(RichText::new("Passed").color(INFO_COLOR));
+                                            }
+                                            scenario::Outcome::Failed(reason) => {
+                                                ui.label(
+                                                    RichText::new(format!("Failed: {reason}"))
+                                                        .color(ERROR_COLOR),
+                                                );
+                                            }
+                                        }
+                                        ui.end_row();
+                                    }
+                                    if run.is_recording() {
+                                        for event in &run.events {
+                                            ui.label(
+                                                RichText::new(event.tick.to_string())
+                                                    .color(time_color(ui)),
+                                            );
+                                            ui.label(event.event.to_str());
+                                            ui.label("Recorded");
+                                            ui.end_row();
+                                        }
+                                    }
+                                    for &index in &evaluation.unexpected {
+                                        let event = &run.events[index];
+                                        ui.label(
+                                            RichText::new(event.tick.to_string())
+                                                .color(time_color(ui)),
+                                        );
+                                        ui.label(event.event.to_str());
+                                        ui.label(
+                                            RichText::new("Failed: Wasn't expected.")
+                                                .color(ERROR_COLOR),
+                                        );
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+
+                egui::CollapsingHeader::new("Game Time")
+                    .id_source("game_time_chart")
+                    .show(ui, |ui| {
+                        let history = &timer.run_history;
+                        if history.game_times.is_empty() {
+                            ui.label("The auto splitter didn't set the game time during the run.");
+                            return;
+                        }
+                        let secs = |duration: time::Duration| duration.as_seconds_f64();
+                        let end = history
+                            .game_times
+                            .last()
+                            .map_or(0.0, |last| secs(last.real_time));
+                        let top = history
+                            .game_times
+                            .iter()
+                            .map(|sample| secs(sample.game_time))
+                            .fold(end, f64::max);
+                        let game_time = Line::new(
+                            history
+                                .game_times
+                                .iter()
+                                .map(|sample| [secs(sample.real_time), secs(sample.game_time)])
+                                .collect::<PlotPoints>(),
+                        )
+                        .color(BLUE_COLOR)
+                        .name("Game Time");
+                        let real_time = Line::new(PlotPoints::new(vec![[0.0, 0.0], [end, end]]))
+                            .color(time_color(ui))
+                            .style(egui_plot::LineStyle::dashed_loose())
+                            .name("Real Time");
+                        Plot::new("Game Time Plot")
+                            .height(200.0)
+                            .legend(Legend::default())
+                            .x_axis_formatter(|x, _| format!("{} s", x.value))
+                            .y_axis_formatter(|y, _| format!("{} s", y.value))
+                            .include_x(0.0)
+                            .include_y(0.0)
+                            .allow_zoom(true)
+                            .allow_drag(true)
+                            .show(ui, |plot_ui| {
+                                for &(start, pause_end) in &history.pauses {
+                                    let (start, pause_end) =
+                                        (secs(start), pause_end.map_or(end, secs).max(secs(start)));
+                                    plot_ui.polygon(
+                                        egui_plot::Polygon::new(PlotPoints::new(vec![
+                                            [start, 0.0],
+                                            [pause_end, 0.0],
+                                            [pause_end, top],
+                                            [start, top],
+                                        ]))
+                                        .fill_color(YELLOW_COLOR.gamma_multiply(0.2))
+                                        .stroke(egui::Stroke::NONE)
+                                        .name("Paused"),
+                                    );
+                                }
+                                plot_ui.line(real_time);
+                                plot_ui.line(game_time);
+                            });
+                    });
+
+                egui::CollapsingHeader::new("State Transitions")
+                    .id_source("state_transitions")
+                    .show(ui, |ui| {
+                        ui.label("How often the actions of the auto splitter moved the timer and the game timer between their states. Actions that didn't change the state show up as loops.");
+                        ui.horizontal_top(|ui| {
+                            render_state_diagram(ui, &TIMER_STATES, &timer.timer_transitions);
+                            render_state_diagram(ui, &GAME_TIME_STATES, &timer.game_time_transitions);
+                        });
+                        Grid::new("state_transitions_grid")
+                            .num_columns(4)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new("From").strong().underline());
+                                ui.label(RichText::new("Action").strong().underline());
+                                ui.label(RichText::new("To").strong().underline());
+                                ui.label(RichText::new("Count").strong().underline());
+                                ui.end_row();
+                                for edge in timer
+                                    .timer_transitions
+                                    .edges
+                                    .iter()
+                                    .chain(&timer.game_time_transitions.edges)
+                                {
+                                    ui.label(edge.from);
+                                    ui.label(edge.action);
+                                    ui.label(edge.to);
+                                    ui.label(edge.count.to_string());
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                egui::CollapsingHeader::new(format!(
+                    "Suspicious Timer Actions ({})",
+                    timer.suspicious_actions.total(),
+                ))
+                .id_source("suspicious_actions")
+                .show(ui, |ui| {
+                    Grid::new("suspicious_actions_grid")
+                        .num_columns(3)
+                        .spacing([10.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Pattern").strong().underline());
+                            ui.label(RichText::new("Count").strong().underline());
+                            ui.label(RichText::new("Last Seen").strong().underline());
+                            ui.end_row();
+                            for pattern in suspicious_actions::PATTERNS {
+                                ui.label(pattern.to_str())
+                                    .on_hover_text(pattern.description());
+                                let count = timer.suspicious_actions.count(pattern);
+                                if count != 0 {
+                                    ui.label(RichText::new(count.to_string()).color(WARN_COLOR));
+                                } else {
+                                    ui.label("0");
+                                }
+                                if let Some(tick) = timer.suspicious_actions.last_tick(pattern) {
+                                    ui.label(format!("Tick {tick}"));
+                                } else {
+                                    ui.label("");
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    clear_suspicious_actions = ui.button("Clear").clicked();
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "Previous Attempts: {}",
+                        timer.run_history.attempts.len()
+                    ));
+                    clear_run_history = ui.button("Clear").clicked();
+                });
+
+                let (splits, comparison) = timer.run_history.comparison();
+                if splits.is_empty() {
+                    ui.label("The auto splitter didn't split yet.");
+                } else {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        Grid::new("run_grid")
+                            .num_columns(7)
+                            .spacing([10.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new("Split").strong().underline());
+                                ui.label(RichText::new("Tick").strong().underline());
+                                ui.label(RichText::new("Real Time").strong().underline());
+                                ui.label(RichText::new("Game Time").strong().underline());
+                                ui.label(RichText::new("Segment").strong().underline())
+                                    .on_hover_text("The duration of the segment, in game time if both splits have one.");
+                                ui.label(RichText::new("Delta").strong().underline())
+                                    .on_hover_text("The difference to the same split in the previous attempt, in game time if both attempts have one.");
+                                ui.label(RichText::new("Tick Delta").strong().underline())
+                                    .on_hover_text("The difference in ticks to the same split in the previous attempt.");
+                                ui.end_row();
+
+                                let mut previous = None::<&run_history::Split>;
+                                for (index, split) in splits.iter().enumerate() {
+                                    if split.skipped {
+                                        ui.label(format!("{} (Skipped)", index + 1));
+                                    } else {
+                                        ui.label((index + 1).to_string());
+                                    }
+                                    ui.label(
+                                        RichText::new(split.tick.to_string()).color(time_color(ui)),
+                                    );
+                                    ui.label(fmt_duration(split.real_time));
+                                    ui.label(split.game_time.map(fmt_duration).unwrap_or_default());
+                                    ui.label(fmt_duration(
+                                        previous.map_or(split.time(), |p| split.since(p)),
+                                    ));
+                                    previous = Some(split);
+
+                                    match comparison
+                                        .and_then(|c| c.get(index))
+                                        .filter(|c| !c.skipped && !split.skipped)
+                                    {
+                                        Some(compared) => {
+                                            let delta = split.since(compared);
+                                            let color = if delta.is_positive() {
+                                                ERROR_COLOR
+                                            } else if delta.is_negative() {
+                                                INFO_COLOR
+                                            } else {
+                                                text_color(ui)
+                                            };
+                                            let sign = if delta.is_negative() { "" } else { "+" };
+                                            ui.label(
+                                                RichText::new(format!("{sign}{}", fmt_duration(delta)))
+                                                    .color(color),
+                                            );
+                                            ui.label(format!(
+                                                "{:+}",
+                                                split.tick as i64 - compared.tick as i64
+                                            ));
+                                        }
+                                        None => {
+                                            ui.label("");
+                                            ui.label("");
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                }
+                drop(timer);
+
+                if stop_scenario || clear_suspicious_actions || clear_run_history {
+                    let mut timer = self.state.timer.0.write().unwrap();
+                    if stop_scenario {
+                        timer.scenario = None;
+                    }
+                    if clear_suspicious_actions {
+                        timer.suspicious_actions.clear();
+                    }
+                    if clear_run_history {
+                        timer.run_history.clear();
+                    }
+                }
+            }
+            Tab::Diagnostics => {
+                if self.state.module_info.is_none() {
+                    ui.label("No auto splitter loaded.");
+                    return;
+                }
+                if self.state.diagnostics.is_empty() {
+                    ui.label("No problems found in the auto splitter.");
+                    return;
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("diagnostics_grid")
+                        .num_columns(3)
+                        .spacing([10.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Severity").strong().underline());
+                            ui.label(RichText::new("Problem").strong().underline());
+                            ui.label(RichText::new("Description").strong().underline());
+                            ui.end_row();
+                            for diagnostic in &self.state.diagnostics {
+                                ui.label(
+                                    RichText::new(diagnostic.severity.to_str())
+                                        .color(severity_color(diagnostic.severity)),
+                                );
+                                ui.label(diagnostic.title);
+                                ui.add(Label::new(&diagnostic.message).wrap());
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+            Tab::About => {
+                let environment = self.state.environment();
+                Grid::new("about_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (key, value) in &environment {
+                            ui.label(*key);
+                            ui.add(Label::new(value).wrap());
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(10.0);
+                if ui.button("Copy to Clipboard").clicked() {
+                    let mut text = String::new();
+                    for (key, value) in &environment {
+                        let _ = writeln!(text, "{key}: {value}");
+                    }
+                    ui.ctx().copy_text(text);
+                }
+            }
+        }
+    }
+
+    fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+        match tab {
+            Tab::Main => "Main",
+            Tab::Statistics => "Statistics",
+            Tab::Logs => "Logs",
+            Tab::Variables => "Variables",
+            Tab::SettingsGUI => "Settings GUI",
+            Tab::SettingsMap => {
+                let unseen = self
+                    .state
+                    .shared_state
+                    .settings_history
+                    .lock()
+                    .unwrap()
+                    .unseen_self_changes;
+                if unseen != 0 {
+                    return RichText::new(format!("Settings Map ({unseen})"))
+                        .color(WARN_COLOR)
+                        .into();
+                }
+                "Settings Map"
+            }
+            Tab::Processes => "Processes",
+            Tab::Performance => "Performance",
+            Tab::Module => "Module",
+            Tab::Diagnostics => {
+                if let Some(severity) = self.state.diagnostics.first().map(|d| d.severity) {
+                    return RichText::new(format!(
+                        "Diagnostics ({})",
+                        self.state.diagnostics.len()
+                    ))
+                    .color(severity_color(severity))
+                    .into();
+                }
+                "Diagnostics"
+            }
+            Tab::Disassembly => "Disassembly",
+            Tab::Memory => "Memory",
+            Tab::MemoryDiff => "Memory Diff",
+            Tab::Runtime => "Runtime",
+            Tab::Run => "Run",
+            Tab::About => "About",
+        }
+        .into()
+    }
+}
+
+/// A column header that sorts by the column when clicked, or reverses the
+/// order if it's already sorted by it.
+fn sort_header<T: PartialEq>(
+    ui: &mut egui::Ui,
+    name: &str,
+    column: T,
+    (sorted_by, descending): &mut (T, bool),
+) {
+    let text = if *sorted_by != column {
+        name.to_owned()
+    } else if *descending {
+        format!("{name} ⬇")
+    } else {
+        format!("{name} ⬆")
+    };
+    if ui
+        .add(Label::new(RichText::new(text).strong().underline()).sense(egui::Sense::click()))
+        .on_hover_text("Click to sort by this column.")
+        .clicked()
+    {
+        if *sorted_by == column {
+            *descending = !*descending;
+        } else {
+            *sorted_by = column;
+            *descending = false;
+        }
+    }
+}
+
+/// A label that can be copied to the clipboard by right-clicking it.
+fn copyable_label(ui: &mut egui::Ui, text: &str) {
+    ui.add(Label::new(text).sense(egui::Sense::click()))
+        .context_menu(|ui| {
+            if ui.button("Copy").clicked() {
+                ui.ctx().copy_text(text.to_owned());
+                ui.close_menu();
+            }
+        });
+}
+
+const TIMER_STATES: [&str; 4] = ["Not running", "Running", "Paused", "Ended"];
+const GAME_TIME_STATES: [&str; 3] = ["Not initialized", "Running", "Paused"];
+
+/// Draws the states on a circle, with arrows for the transitions between
+/// them that are labeled with how often they happened.
+fn render_state_diagram(ui: &mut egui::Ui, states: &[&str], transitions: &Transitions) {
+    const NODE_SIZE: egui::Vec2 = egui::vec2(100.0, 22.0);
+    // Arrows in opposite directions are moved apart so both stay visible.
+    const ARROW_OFFSET: f32 = 5.0;
+
+    let (response, painter) = ui.allocate_painter(egui::vec2(280.0, 200.0), egui::Sense::hover());
+    let rect = response.rect;
+    let radius = rect.size() / 2.0 - NODE_SIZE / 2.0 - egui::vec2(0.0, 20.0);
+    let positions = (0..states.len())
+        .map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / states.len() as f32
+                - std::f32::consts::FRAC_PI_2;
+            rect.center() + egui::vec2(angle.cos() * radius.x, angle.sin() * radius.y)
+        })
+        .collect::<Vec<_>>();
+    let color = text_color(ui);
+    let stroke = egui::Stroke::new(1.0, color);
+    let font = egui::FontId::proportional(12.0);
+    // How far from the center of a node its border is in the direction.
+    let border = |direction: egui::Vec2| {
+        (NODE_SIZE.x / 2.0 / direction.x.abs()).min(NODE_SIZE.y / 2.0 / direction.y.abs())
+    };
+
+    for (i, from) in states.iter().enumerate() {
+        for (j, to) in states.iter().enumerate() {
+            let count = transitions.between(from, to);
+            if count == 0 {
+                continue;
+            }
+            if i == j {
+                let outward = (positions[i] - rect.center()).normalized();
+                let center = positions[i] + outward * (border(outward) + 8.0);
+                painter.circle_stroke(center, 8.0, stroke);
+                painter.text(
+                    center + outward * 18.0,
+                    egui::Align2::CENTER_CENTER,
+                    count.to_string(),
+                    font.clone(),
+                    color,
+                );
+            } else {
+                let direction = (positions[j] - positions[i]).normalized();
+                let offset = direction.rot90() * ARROW_OFFSET;
+                let start = positions[i] + direction * border(direction) + offset;
+                let end = positions[j] - direction * border(direction) + offset;
+                painter.arrow(start, end - start, stroke);
+                painter.text(
+                    start + (end - start) / 2.0 + offset * 2.0,
+                    egui::Align2::CENTER_CENTER,
+                    count.to_string(),
+                    font.clone(),
+                    color,
+                );
+            }
+        }
+    }
+
+    for (state, position) in states.iter().zip(positions) {
+        let node = egui::Rect::from_center_size(position, NODE_SIZE);
+        painter.rect(node, 4.0, ui.visuals().extreme_bg_color, stroke);
+        painter.text(
+            position,
+            egui::Align2::CENTER_CENTER,
+            state,
+            font.clone(),
+            color,
+        );
+    }
+}
+
+/// Draws the tick times over the wall clock time, with a column per second
+/// and a row per bucket of tick times, the slowest at the top.
+fn render_tick_heatmap(ui: &mut egui::Ui, heatmap: &TickHeatmap, color: Color32) {
+    // Leaves space for the labels of the axes.
+    const LEFT_MARGIN: f32 = 60.0;
+    const BOTTOM_MARGIN: f32 = 20.0;
+    // How far apart the labels of the time axis are at least.
+    const LABEL_SPACING: f32 = 60.0;
+
+    let (Some(first), Some(last)) = (heatmap.columns.front(), heatmap.columns.back()) else {
+        ui.label("No ticks measured yet.");
+        return;
+    };
+    let (response, painter) = ui.allocate_painter(ui.available_size(), egui::Sense::hover());
+    let rect = egui::Rect::from_min_max(
+        response.rect.min + egui::vec2(LEFT_MARGIN, 0.0),
+        response.rect.max - egui::vec2(0.0, BOTTOM_MARGIN),
+    );
+    let columns = (last.index - first.index + 1) as f32;
+    let cell_size = egui::vec2(
+        rect.width() / columns,
+        rect.height() / tick_heatmap::BUCKETS as f32,
+    );
+    let cell_rect = |column: u64, bucket: usize| {
+        egui::Rect::from_min_size(
+            egui::pos2(
+                rect.left() + (column - first.index) as f32 * cell_size.x,
+                rect.bottom() - (bucket + 1) as f32 * cell_size.y,
+            ),
+            cell_size,
+        )
+    };
+    let text_color = text_color(ui);
+    let font = egui::FontId::proportional(12.0);
+
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+    // The counts are on a logarithmic scale, so a few slow ticks are still
+    // visible next to thousands of fast ones.
+    let max = (heatmap.max_count() as f32).ln_1p();
+    for column in &heatmap.columns {
+        for (bucket, &count) in column.counts.iter().enumerate() {
+            if count != 0 {
+                let intensity = 0.15 + 0.85 * (count as f32).ln_1p() / max;
+                painter.rect_filled(
+                    cell_rect(column.index, bucket),
+                    0.0,
+                    color.gamma_multiply(intensity),
+                );
+            }
+        }
+    }
+
+    for bucket in (0..tick_heatmap::BUCKETS).step_by(2) {
+        painter.text(
+            egui::pos2(rect.left() - 5.0, cell_rect(first.index, bucket).bottom()),
+            egui::Align2::RIGHT_BOTTOM,
+            fmt_std_duration(tick_heatmap::bucket_start(bucket)),
+            font.clone(),
+            text_color,
+        );
+    }
+    let step = (LABEL_SPACING / cell_size.x).ceil().max(1.0) as u64;
+    let seconds_per_column = tick_heatmap::COLUMN_DURATION.as_secs();
+    for column in (first.index.div_ceil(step) * step..=last.index).step_by(step as usize) {
+        painter.text(
+            egui::pos2(cell_rect(column, 0).left(), rect.bottom() + 3.0),
+            egui::Align2::LEFT_TOP,
+            format!("{} s", column * seconds_per_column),
+            font.clone(),
+            text_color,
+        );
+    }
+
+    if let Some(pointer) = response.hover_pos().filter(|pos| rect.contains(*pos)) {
+        let column = first.index + ((pointer.x - rect.left()) / cell_size.x) as u64;
+        let bucket =
+            (((rect.bottom() - pointer.y) / cell_size.y) as usize).min(tick_heatmap::BUCKETS - 1);
+        let count = heatmap
+            .columns
+            .iter()
+            .find(|c| c.index == column)
+            .map_or(0, |c| c.counts[bucket]);
+        let range = if bucket + 1 == tick_heatmap::BUCKETS {
+            format!(
+                "over {}",
+                fmt_std_duration(tick_heatmap::bucket_start(bucket))
+            )
+        } else {
+            format!(
+                "between {} and {}",
+                fmt_std_duration(tick_heatmap::bucket_start(bucket)),
+                fmt_std_duration(tick_heatmap::bucket_start(bucket + 1)),
+            )
+        };
+        painter.rect_stroke(
+            cell_rect(column, bucket),
+            0.0,
+            egui::Stroke::new(1.0, text_color),
+        );
+        response.on_hover_text_at_pointer(format!(
+            "{count} ticks took {range} at {} s.",
+            column * seconds_per_column,
+        ));
+    }
+}
+
+fn text_color(ui: &egui::Ui) -> Color32 {
+    if ui.visuals().dark_mode {
+        TEXT_COLOR
+    } else {
+        LIGHT_TEXT_COLOR
+    }
+}
+
+fn time_color(ui: &egui::Ui) -> Color32 {
+    if ui.visuals().dark_mode {
+        TIME_COLOR
+    } else {
+        LIGHT_TIME_COLOR
+    }
+}
+
+fn apply_theme(ctx: &egui::Context, dark: bool) {
+    let mut style = (*ctx.style()).clone();
+    style.visuals = preferences::visuals(dark);
+    style.visuals.override_text_color = Some(if dark { TEXT_COLOR } else { LIGHT_TEXT_COLOR });
+    ctx.set_style(style);
+}
+
+/// A row of 16 bytes in hexadecimal, with the bytes that differ from the
+/// other data highlighted.
+fn hex_diff_row(ui: &egui::Ui, data: &[u8], other: &[u8], offset: usize) -> egui::text::LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let mut job = egui::text::LayoutJob::default();
+    for i in offset..offset + 16 {
+        let color = if data.get(i) != other.get(i) {
+            CHANGED_COLOR
+        } else {
+            text_color(ui)
+        };
+        job.append(
+            &memory_diff::hex(data, i, 1),
+            if i == offset { 0.0 } else { 6.0 },
+            egui::TextFormat::simple(font_id.clone(), color),
+        );
+    }
+    job
+}
+
+fn render_module_items(ui: &mut egui::Ui, id: &str, items: &[module_info::Item]) {
+    Grid::new(("module_items_grid", id))
+        .num_columns(2)
+        .spacing([10.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(RichText::new("Name").strong().underline());
+            ui.label(RichText::new("Kind").strong().underline());
+            ui.end_row();
+            for item in items {
+                ui.label(&*item.name);
+                ui.label(item.kind);
+                ui.end_row();
+            }
+        });
+}
+
+fn render_module_sections(
+    ui: &mut egui::Ui,
+    id: &str,
+    total_size: usize,
+    sections: &[module_info::Section],
+) {
+    Grid::new(id)
+        .num_columns(3)
+        .spacing([10.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(RichText::new("Name").strong().underline());
+            ui.label(RichText::new("Size").strong().underline());
+            ui.label(RichText::new("Share").strong().underline());
+            ui.end_row();
+            for section in sections {
+                ui.label(&*section.name);
+                ui.label(fmt_bytes(section.size as _));
+                ui.label(format!(
+                    "{:.1}%",
+                    100.0 * section.size as f64 / total_size.max(1) as f64,
+                ));
+                ui.end_row();
+            }
+        });
+}
+
+/// Returns whether dumping the selected memory ranges got requested.
+fn render_process_maps(
+    ui: &mut egui::Ui,
+    pid: u64,
+    maps: &process_maps::ProcessMaps,
+    selected_ranges: &mut HashSet<(u64, u64)>,
+) -> bool {
+    let mut dump = false;
+    egui::CollapsingHeader::new(format!("Modules ({})", maps.modules.len()))
+        .id_source(("process_modules", pid))
+        .default_open(true)
+        .show(ui, |ui| {
+            Grid::new(("process_modules_grid", pid))
+                .num_columns(3)
+                .spacing([10.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Name").strong().underline());
+                    ui.label(RichText::new("Base Address").strong().underline());
+                    ui.label(RichText::new("Size").strong().underline());
+                    ui.end_row();
+                    for module in &maps.modules {
+                        ui.label(&*module.name).on_hover_text(&*module.path);
+                        ui.label(format!("{:#X}", module.base));
+                        ui.label(fmt_bytes(module.size));
+                        ui.end_row();
+                    }
+                });
+        });
+
+    egui::CollapsingHeader::new(format!("Memory Ranges ({})", maps.ranges.len()))
+        .id_source(("process_ranges", pid))
+        .show(ui, |ui| {
+            let selected = maps
+                .ranges
+                .iter()
+                .filter(|range| selected_ranges.contains(&(pid, range.start)))
+                .count();
+            dump = ui
+                .add_enabled(
+                    selected != 0,
+                    egui::Button::new(format!("Dump Selected ({selected})")),
+                )
+                .on_hover_text("Writes the memory of the selected ranges to a file each, to attach the state of the game to an issue.")
+                .clicked();
+            Grid::new(("process_ranges_grid", pid))
+                .num_columns(5)
+                .spacing([10.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.label(RichText::new("Address").strong().underline());
+                    ui.label(RichText::new("Size").strong().underline());
+                    ui.label(RichText::new("Permissions").strong().underline());
+                    ui.label(RichText::new("File").strong().underline());
+                    ui.end_row();
+                    for range in &maps.ranges {
+                        let mut is_selected = selected_ranges.contains(&(pid, range.start));
+                        if ui.checkbox(&mut is_selected, "").changed() {
+                            if is_selected {
+                                selected_ranges.insert((pid, range.start));
+                            } else {
+                                selected_ranges.remove(&(pid, range.start));
+                            }
+                        }
+                        ui.label(RichText::new(format!("{:#X}", range.start)).monospace());
+                        ui.label(fmt_bytes(range.size));
+                        ui.label(RichText::new(range.permissions()).monospace());
+                        ui.label(range.path.as_deref().unwrap_or_default());
+                        ui.end_row();
+                    }
+                });
+        });
+
+    dump
+}
+
+fn fmt_memory_pages(pages: u64) -> String {
+    // WebAssembly memories are always sized in pages of 64 KiB.
+    fmt_bytes(pages.saturating_mul(64 << 10))
+}
+
+fn render_settings_map(ui: &mut egui::Ui, settings_map: &settings::Map, path: fmt::Arguments<'_>) {
+    Grid::new(format!("settings_{path}"))
+        .num_columns(2)
+        .spacing([10.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(RichText::new("Key").strong().underline());
+            ui.label(RichText::new("Value").strong().underline());
+            ui.end_row();
+
+            for (key, value) in settings_map.iter() {
+                ui.label(key);
+                render_value(value, ui, format_args!("{path}.{key}"));
+                ui.end_row();
+            }
+        });
+}
+
+fn render_settings_list(
+    ui: &mut egui::Ui,
+    settings_list: &settings::List,
+    path: fmt::Arguments<'_>,
+) {
+    Grid::new(format!("settings_{path}"))
+        .num_columns(1)
+        .spacing([10.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for (i, value) in settings_list.iter().enumerate() {
+                render_value(value, ui, format_args!("{path}[{i}]"));
+                ui.end_row();
+            }
+        });
+}
+
+fn render_value(value: &settings::Value, ui: &mut egui::Ui, path: fmt::Arguments<'_>) {
+    match value {
+        settings::Value::Map(v) => render_settings_map(ui, v, path),
+        settings::Value::List(v) => render_settings_list(ui, v, path),
+        settings::Value::Bool(v) => {
+            ui.label(if *v { "true" } else { "false" });
+        }
+        settings::Value::I64(v) => {
+            ui.label(v.to_string());
+        }
+        settings::Value::F64(v) => {
+            ui.label(v.to_string());
+        }
+        settings::Value::String(v) => {
+            ui.label(&**v);
+        }
+        _ => {
+            ui.label("<Unsupported>");
+        }
+    }
+}
+
+impl App for Debugger {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let preferences = self.instances[self.selected].preferences.borrow();
+        eframe::set_value(storage, eframe::APP_KEY, &*preferences);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        for state in &mut self.instances {
+            state.shutdown(Some(&self.dock_state));
+        }
+    }
+
+    fn update(&mut self, ctx: &egui::Context, frame: &mut Frame) {
+        let now = Instant::now();
+        self.frame_stats.frame(now, frame.info().cpu_usage);
+        for state in &mut self.instances {
+            state.update(now);
+        }
+
+        egui::TopBottomPanel::top("auto_splitters").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Auto Splitter");
+                ComboBox::from_id_source("auto_splitter")
+                    .selected_text(self.instances[self.selected].name())
+                    .show_ui(ui, |ui| {
+                        for (index, state) in self.instances.iter().enumerate() {
+                            ui.selectable_value(&mut self.selected, index, state.name());
+                        }
+                    });
+                if ui
+                    .button("Add")
+                    .on_hover_text("Loads another auto splitter next to the current one. Each auto splitter runs on its own thread and has its own statistics.")
+                    .clicked()
+                {
+                    let instance = self.instances[self.selected].new_instance();
+                    self.instances.push(instance);
+                    self.selected = self.instances.len() - 1;
+                }
+                if ui
+                    .add_enabled(
+                        self.instances[self.selected].path.is_some(),
+                        egui::Button::new("A/B Test"),
+                    )
+                    .on_hover_text("Runs the auto splitter a second time with the optimizations of the runtime toggled, and compares the timer actions and tick times of both to find bugs that only show up in one of them.")
+                    .clicked()
+                {
+                    let (current, other_index) = (self.selected, self.instances.len());
+                    let state = &mut self.instances[current];
+                    let other = state.start_ab_test();
+                    self.ab_test = Some(if state.runtime_config.optimize {
+                        AbTest {
+                            optimized: current,
+                            unoptimized: other_index,
+                        }
+                    } else {
+                        AbTest {
+                            optimized: other_index,
+                            unoptimized: current,
+                        }
+                    });
+                    self.instances.push(other);
+                }
+                ui.separator();
+                let state = &mut self.instances[self.selected];
+                if ui
+                    .button("Open Session")
+                    .on_hover_text("Restores an investigation that was saved before.")
+                    .clicked()
+                {
+                    let mut dialog = FileDialog::open_file(None).show_files_filter(Box::new(
+                        |path| {
+                            path.extension()
+                                .is_some_and(|ext| ext == session::FILE_EXTENSION)
+                        },
+                    ));
+                    dialog.open();
+                    state.open_file_dialog = Some((dialog, FileDialogInfo::OpenSession));
+                }
+                if ui
+                    .button("Save Session")
+                    .on_hover_text("Saves the loaded files, the settings map, the layout of the tabs, the statistics, the logs and the pinned variables, so the investigation can be continued later.")
+                    .clicked()
+                {
+                    let mut dialog = FileDialog::save_file(None)
+                        .default_filename(format!("session.{}", session::FILE_EXTENSION));
+                    dialog.open();
+                    state.open_file_dialog = Some((dialog, FileDialogInfo::SaveSession));
+                }
+                if self.instances.len() > 1 && ui.button("Close").clicked() {
+                    self.instances.remove(self.selected);
+                    self.selected = self.selected.min(self.instances.len() - 1);
+                    self.ab_test = None;
+                }
+            });
+
+            if let Some(ab_test) = self.ab_test {
+                let optimized = &self.instances[ab_test.optimized];
+                let unoptimized = &self.instances[ab_test.unoptimized];
+                let optimized_timer = optimized.timer.0.read().unwrap();
+                let unoptimized_timer = unoptimized.timer.0.read().unwrap();
+                let (Some(optimized_run), Some(unoptimized_run)) =
+                    (&optimized_timer.scenario, &unoptimized_timer.scenario)
+                else {
+                    // One of the recordings got stopped.
+                    drop((optimized_timer, unoptimized_timer));
+                    self.ab_test = None;
+                    return;
+                };
+                let evaluation = unoptimized_run.evaluate_against(
+                    unoptimized_timer.tick_index,
+                    optimized_run,
+                    AB_TEST_TICK_TOLERANCE,
+                    AB_TEST_GAME_TIME_TOLERANCE,
+                );
+
+                let mut stop = false;
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("A/B Test").strong());
+                    let summary = format!(
+                        "{} matching, {} diverging, {} pending timer actions",
+                        evaluation.passed(),
+                        evaluation.failed(),
+                        evaluation.pending(),
+                    );
+                    if evaluation.failed() == 0 {
+                        ui.label(summary);
+                    } else {
+                        let divergence = evaluation
+                            .outcomes
+                            .iter()
+                            .zip(&optimized_run.events)
+                            .find_map(|(outcome, expected)| match outcome {
+                                scenario::Outcome::Failed(reason) => Some(format!(
+                                    "{} at tick {}: {reason}",
+                                    expected.event.to_str(),
+                                    expected.tick,
+                                )),
+                                _ => None,
+                            })
+                            .unwrap_or_else(|| {
+                                let event = &unoptimized_run.events[evaluation.unexpected[0]];
+                                format!(
+                                    "{} at tick {}: Only happened without optimizations.",
+                                    event.event.to_str(),
+                                    event.tick,
+                                )
+                            });
+                        ui.label(RichText::new(summary).color(ERROR_COLOR))
+                            .on_hover_text(divergence);
+                    }
+                    ui.separator();
+                    ui.label(format!(
+                        "Avg. Tick Time: {} optimized, {} unoptimized",
+                        fmt_duration(time::Duration::seconds_f64(
+                            optimized
+                                .shared_state
+                                .avg_tick_secs
+                                .load(atomic::Ordering::Relaxed),
+                        )),
+                        fmt_duration(time::Duration::seconds_f64(
+                            unoptimized
+                                .shared_state
+                                .avg_tick_secs
+                                .load(atomic::Ordering::Relaxed),
+                        )),
+                    ));
+                    stop = ui.button("Stop").clicked();
+                });
+                drop((optimized_timer, unoptimized_timer));
+
+                if stop {
+                    for index in [ab_test.optimized, ab_test.unoptimized] {
+                        self.instances[index].timer.0.write().unwrap().scenario = None;
+                    }
+                    self.ab_test = None;
+                }
+            }
+        });
+
+        let state = &mut self.instances[self.selected];
+
+        // The runtime thread requests repaints whenever the auto splitter
+        // changes something, so only the highlights of changed variables need
+        // to be animated. Everything else, like the statistics, is refreshed
+        // at a lower rate, so the debugger doesn't keep a CPU core busy.
+        let timer = state.timer.0.read().unwrap();
+        if timer
+            .variables
+            .values()
+            .any(|v| v.last_changed.elapsed() < CHANGED_FADE_DURATION)
+        {
+            ctx.request_repaint();
+        } else {
+            ctx.request_repaint_after(IDLE_REPAINT_INTERVAL);
+        }
+        drop(timer);
+
+        let mut preferences = state.preferences.borrow_mut();
+        // When the system theme changes, eframe replaces the visuals with its
+        // default ones, which don't override the text color.
+        let dark = preferences.theme.is_dark(frame.info().system_theme);
+        let style = ctx.style();
+        if style.visuals.dark_mode != dark || style.visuals.override_text_color.is_none() {
+            apply_theme(ctx, dark);
+        }
+        // The zoom can also be changed with the keyboard shortcuts of egui.
+        preferences.zoom = ctx.zoom_factor();
+        drop(preferences);
+
+        if let Some((dialog, info)) = &mut state.open_file_dialog {
+            if dialog.show(ctx).selected() {
+                if let Some(file) = dialog.path().map(ToOwned::to_owned) {
+                    match info {
+                        FileDialogInfo::Wasm => {
+                            state.watched_directory = None;
+                            state.load(Load::File(file));
+                        }
+                        FileDialogInfo::WasmDirectory => {
+                            state.watched_directory = Some(file);
+                            state.last_directory_scan = None;
+                        }
+                        FileDialogInfo::Script => state.set_script_path(file),
+                        FileDialogInfo::VariableHistory => {
+                            // The history is copied, so the runtime isn't
+                            // blocked while it's written.
+                            let history = state.timer.0.read().unwrap().variable_history.clone();
+                            let result = fs::File::create(&file).and_then(|f| {
+                                variable_history::write_csv(io::BufWriter::new(f), &history)
+                            });
+                            if let Err(e) = result {
+                                state.timer.0.write().unwrap().log(
+                                    format!("Failed to export the variable history: {}", e).into(),
+                                    LogType::Debugger(LogLevel::Error),
+                                );
+                            }
+                        }
+                        FileDialogInfo::CargoWorkspace => state.cargo_workspace = Some(file),
+                        FileDialogInfo::Scenario => state.scenario_path = Some(file),
+                        FileDialogInfo::GoldenRun => {
+                            let mut timer = state.timer.0.write().unwrap();
+                            if let Some(run) = &timer.scenario {
+                                let result = fs::File::create(&file).and_then(|f| {
+                                    let mut writer = io::BufWriter::new(f);
+                                    run.to_golden().write(&mut writer)?;
+                                    writer.flush()
+                                });
+                                match result {
+                                    Ok(()) => {
+                                        timer.log(
+                                            "Saved the golden run.".into(),
+                                            LogType::Debugger(LogLevel::Info),
+                                        );
+                                        state.scenario_path = Some(file);
+                                    }
+                                    Err(e) => timer.log(
+                                        format!("Failed to save the golden run: {e}").into(),
+                                        LogType::Debugger(LogLevel::Error),
+                                    ),
+                                }
+                            }
+                        }
+                        FileDialogInfo::GameExecutable => {
+                            if let Some(path) = &state.path {
+                                state
+                                    .preferences
+                                    .borrow_mut()
+                                    .games
+                                    .entry(path.clone())
+                                    .or_default()
+                                    .executable = file;
+                            }
+                        }
+                        FileDialogInfo::CrashReport => state.save_crash_report(&file),
+                        FileDialogInfo::TrapArtifacts => {
+                            state.preferences.borrow_mut().trap_artifacts = Some(file);
+                        }
+                        FileDialogInfo::ProcessMemory(pid) => {
+                            let pid = *pid;
+                            let ranges: Vec<_> = match state.process_maps.get(pid, Instant::now()) {
+                                Ok(maps) => maps
+                                    .ranges
+                                    .iter()
+                                    .filter(|range| {
+                                        state.selected_ranges.contains(&(pid, range.start))
+                                    })
+                                    .map(|range| (range.start, range.size))
+                                    .collect(),
+                                Err(_) => Vec::new(),
+                            };
+                            let mut timer = state.timer.0.write().unwrap();
+                            match process_maps::dump(pid, &ranges, &file) {
+                                Ok(()) => timer.log(
+                                    format!(
+                                        "Dumped {} memory ranges of process {pid} to {}.",
+                                        ranges.len(),
+                                        file.display(),
+                                    )
+                                    .into(),
+                                    LogType::Debugger(LogLevel::Info),
+                                ),
+                                Err(e) => timer.log(
+                                    format!("Failed to dump the memory of process {pid}: {e}")
+                                        .into(),
+                                    LogType::Debugger(LogLevel::Error),
+                                ),
+                            }
+                        }
+                        FileDialogInfo::MemoryDump(index) => match fs::read(&file) {
+                            Ok(data) => state.memory_diff.set_dump(*index, file, data.into()),
+                            Err(e) => state.timer.0.write().unwrap().log(
+                                format!("Failed to open the memory dump: {e}").into(),
+                                LogType::Debugger(LogLevel::Error),
+                            ),
+                        },
+                        FileDialogInfo::SaveSession => state.save_session(&file, &self.dock_state),
+                        FileDialogInfo::OpenSession => {
+                            match Session::read(&file) {
+                                Ok(mut session) => {
+                                    if let Some(dock_state) = session.dock_state.take() {
+                                        self.dock_state = dock_state;
+                                    }
+                                    state.restore_session(session, &file);
+                                }
+                                Err(e) => state.timer.0.write().unwrap().log(
+                                    format!("{e:?}").into(),
+                                    LogType::Debugger(LogLevel::Error),
+                                ),
+                            }
+                        }
+                        FileDialogInfo::LogExport => {
+                            // The logs are copied, so the runtime isn't blocked
+                            // while they're written.
+                            let logs = state.timer.0.read().unwrap().logs.clone();
+                            let result = fs::File::create(&file).and_then(|f| {
+                                let mut writer = io::BufWriter::new(f);
+                                for log in &logs {
+                                    log.write_json_line(&mut writer)?;
+                                }
+                                writer.flush()
+                            });
+                            if let Err(e) = result {
+                                state.timer.0.write().unwrap().log(
+                                    format!("Failed to export the logs: {}", e).into(),
+                                    LogType::Debugger(LogLevel::Error),
+                                );
+                            }
+                        }
+                        FileDialogInfo::LogFile => {
+                            let log_file = LogFile::open(file);
+                            let mut timer = state.timer.0.write().unwrap();
+                            match log_file {
+                                Ok(log_file) => timer.log_file = Some(log_file),
+                                Err(e) => timer.log(
+                                    format!("Failed to open the log file: {}", e).into(),
+                                    LogType::Debugger(LogLevel::Error),
+                                ),
+                            }
+                        }
+                        FileDialogInfo::SettingsWidget(key) => {
+                            if let Some(s) =
+                                wasi_path::from_native(&file.canonicalize().unwrap_or(file))
+                            {
+                                state.shared_state.change_settings_map(
+                                    &state.timer,
+                                    settings_history::Origin::SettingsGui,
+                                    |old| {
+                                        let mut new = old.clone();
+                                        new.insert(
+                                            key.clone(),
+                                            settings::Value::String(s.as_ref().into()),
+                                        );
+                                        new
+                                    },
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if !state.pinned_variables.is_empty() {
+            egui::TopBottomPanel::top("pinned_variables").show(ctx, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    let timer = state.timer.0.read().unwrap();
+                    for key in &state.pinned_variables {
+                        ui.label(RichText::new(&**key).color(time_color(ui)));
+                        match timer.variables.get(key) {
+                            Some(variable) => ui.label(
+                                RichText::new(&variable.value)
+                                    .color(variable.color(text_color(ui))),
+                            ),
+                            None => ui.label("<Not set>"),
+                        };
+                        ui.separator();
+                    }
+                });
+            });
+        }
+
+        let mut tab_viewer = TabViewer {
+            state,
+            frame_stats: &mut self.frame_stats,
+        };
+
+        DockArea::new(&mut self.dock_state)
+            .show_window_close_buttons(false)
+            .style(Style::from_egui(ctx.style().as_ref()))
+            .show(ctx, &mut tab_viewer);
+    }
+}
+
+/// The user interface for a debugger running elsewhere, which shows what the
+/// HTTP API of that debugger provides.
+struct RemoteDebugger {
+    remote: Remote,
+}
+
+impl App for RemoteDebugger {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
+        let state = self.remote.state();
+        let mut actions = Vec::new();
+
+        egui::TopBottomPanel::top("remote").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&*self.remote.address).strong());
+                match (&state.error, state.last_update) {
+                    (Some(e), _) => {
+                        ui.label(RichText::new(e).color(ERROR_COLOR));
+                    }
+                    (None, None) => {
+                        ui.label(RichText::new("Connecting…").color(time_color(ui)));
+                    }
+                    (None, Some(_)) => {
+                        ui.label(RichText::new("Connected").color(INFO_COLOR));
+                    }
+                }
+                ui.separator();
+                if ui
+                    .button("Reload")
+                    .on_hover_text("Reload the auto splitter from its file.")
+                    .clicked()
+                {
+                    actions.push(remote::Action::Reload);
+                }
+                if ui
+                    .button("Restart")
+                    .on_hover_text("Restart the auto splitter without reloading its file.")
+                    .clicked()
+                {
+                    actions.push(remote::Action::Restart);
+                }
+                if ui
+                    .button("Kill")
+                    .on_hover_text("Interrupt the auto splitter.")
+                    .clicked()
+                {
+                    actions.push(remote::Action::Kill);
+                }
+            });
+        });
+
+        egui::SidePanel::left("remote_stats").show(ctx, |ui| {
+            let (timer, stats) = (&state.timer, &state.stats);
+            Grid::new("remote_stats_grid")
+                .num_columns(2)
+                .spacing([10.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    let mut row = |name: &str, value: String| {
+                        ui.label(name);
+                        ui.label(value);
+                        ui.end_row();
+                    };
+                    let text = |value: &serde_json::Value| match value {
+                        serde_json::Value::String(v) => v.clone(),
+                        serde_json::Value::Null => String::new(),
+                        v => v.to_string(),
+                    };
+                    let duration = |value: &serde_json::Value| {
+                        value.as_f64().map_or_else(String::new, |v| {
+                            fmt_duration(time::Duration::seconds_f64(v))
+                        })
+                    };
+                    let bytes = |value: &serde_json::Value| {
+                        value.as_u64().map_or_else(String::new, fmt_bytes)
+                    };
+
+                    row("Timer State", text(&timer["state"]));
+                    row("Game Time", duration(&timer["game_time"]));
+                    row("Game Time State", text(&timer["game_time_state"]));
+                    row("Split Index", text(&timer["split_index"]));
+                    row("Tick", text(&timer["tick"]));
+                    row("Tick Rate", duration(&stats["tick_rate"]));
+                    row("Avg. Tick Time", duration(&stats["avg_tick_time"]));
+                    row("Slowest Tick", duration(&stats["slowest_tick_time"]));
+                    row("Memory Usage", bytes(&stats["memory_usage"]));
+                    row("Peak Memory Usage", bytes(&stats["peak_memory_usage"]));
+                    row("Memory Grows", text(&stats["memory_grow_count"]));
+                    row("Handles", text(&stats["handles"]));
+                    row("Process Handles", text(&stats["process_handles"]));
+                    row("Traps", text(&stats["traps"]));
+                });
+        });
+
+        egui::SidePanel::right("remote_variables").show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.label(RichText::new("Variables").strong());
+                Grid::new("remote_vars_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (key, value) in &state.variables {
+                            ui.label(key);
+                            ui.label(value.as_str().unwrap_or_default());
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(10.0);
+                ui.label(RichText::new("Settings Map").strong());
+                let Some(settings) = &state.settings else {
+                    ui.label("No auto splitter is loaded.");
+                    return;
+                };
+                Grid::new("remote_settings_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (key, value) in settings {
+                            ui.label(key);
+                            match value {
+                                serde_json::Value::Bool(v) => {
+                                    let mut v = *v;
+                                    if ui.checkbox(&mut v, "").changed() {
+                                        actions.push(remote::Action::SetSetting {
+                                            key: key.clone(),
+                                            value: v.into(),
+                                        });
+                                    }
+                                }
+                                serde_json::Value::String(v) => {
+                                    ui.label(v);
+                                }
+                                v => {
+                                    ui.label(v.to_string());
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .stick_to_bottom(true)
+                .auto_shrink(false)
+                .show(ui, |ui| {
+                    Grid::new("remote_log_grid")
+                        .num_columns(5)
+                        .spacing([10.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for log in &state.logs {
+                                let level = log["level"].as_str().unwrap_or_default();
+                                let color = log_filter::LEVELS
+                                    .into_iter()
+                                    .find(|&l| log_level_to_str(l) == level)
+                                    .map_or(text_color(ui), log_level_color);
+                                ui.label(
+                                    RichText::new(log["timestamp"].as_str().unwrap_or_default())
+                                        .color(time_color(ui)),
+                                );
+                                ui.label(
+                                    RichText::new(log["tick"].to_string()).color(time_color(ui)),
+                                );
+                                ui.label(RichText::new(level).color(color));
+                                let source = log["source"].as_str().unwrap_or_default();
+                                ui.label(RichText::new(source).color(time_color(ui)));
+                                ui.horizontal(|ui| {
+                                    ui.add(
+                                        Label::new(
+                                            RichText::new(
+                                                log["message"].as_str().unwrap_or_default(),
+                                            )
+                                            .color(
+                                                if source == LogSource::AutoSplitter.to_str() {
+                                                    text_color(ui)
+                                                } else {
+                                                    color
+                                                },
+                                            ),
+                                        )
+                                        .wrap(),
+                                    );
+                                    if let Some(count) =
+                                        log["count"].as_u64().filter(|&count| count > 1)
+                                    {
+                                        ui.label(
+                                            RichText::new(format!("×{count}"))
+                                                .strong()
+                                                .color(CHANGED_COLOR),
+                                        );
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+
+        drop(state);
+        for action in actions {
+            self.remote.perform(action);
+        }
+    }
+}
+
+impl Drop for AppState {
+    fn drop(&mut self) {
+        self.shutdown(None);
+    }
+}
+
+enum Load {
+    File(PathBuf),
+    Reload,
+    Restart,
+}
+
+impl AppState {
+    fn new(
+        preferences: Rc<RefCell<Preferences>>,
+        runtime_config: RuntimeConfig,
+        http_address: IpAddr,
+        shared_state: Arc<SharedState>,
+        timer: DebuggerTimer,
+    ) -> Self {
+        Self {
+            preferences,
+            path: None,
+            script_path: None,
+            module_watch: FileWatch::default(),
+            script_watch: FileWatch::default(),
+            runtime_config,
+            pending_runtime_config: runtime_config,
+            open_file_dialog: None,
+            module: None,
+            module_cache: IndexMap::new(),
+            module_info: None,
+            diagnostics: Vec::new(),
+            module_data: None,
+            disassembly: None,
+            global_variables: None,
+            stats_recorded: false,
+            load_failure: None,
+            saved_traps: 0,
+            trap_artifacts_saved: None,
+            memory_view_target: None,
+            compile_time: None,
+            instantiate_time: None,
+            auto_reload: true,
+            watched_directory: None,
+            last_directory_scan: None,
+            cargo_workspace: None,
+            cargo_build: None,
+            auto_restart: AutoRestart::default(),
+            process_maps: ProcessMapsCache::default(),
+            process_candidates: ProcessCandidates::default(),
+            process_sort: (ProcessColumn::Pid, false),
+            function_sort: (FunctionColumn::Size, true),
+            scenario_path: None,
+            session_path: None,
+            performance_view: PerformanceView::TickTimes,
+            process_architectures: HashMap::new(),
+            selected_ranges: HashSet::new(),
+            pointer_path_draft: pointer_path::Draft::default(),
+            sig_scan: sig_scan::SigScan::default(),
+            memory_diff: memory_diff::MemoryDiff::default(),
+            crash_report_memory: false,
+            http_api: None,
+            http_address,
+            http_port: http_api::DEFAULT_PORT,
+            thread_options: ThreadOptions::default(),
+            pinned_variables: IndexSet::new(),
+            break_condition_input: String::new(),
+            break_condition_error: None,
+            log_filter: LogFilter::default(),
+            relative_log_times: false,
+            log_selection: None,
+            timer_script: String::new(),
+            timer_script_error: None,
+            timer_script_run: None,
+            game_time_input: String::new(),
+            variable_input: Default::default(),
+            shared_state,
+            timer,
+            runtime: build_runtime(runtime_config).unwrap(),
+        }
+    }
+
+    fn from_args(
+        preferences: Preferences,
+        args: Args,
+        shared_state: Arc<SharedState>,
+        timer: DebuggerTimer,
+    ) -> Self {
+        let runtime_config = RuntimeConfig {
+            optimize: !args.debug,
+            ..Default::default()
+        };
+        let mut state = Self::new(
+            Rc::new(RefCell::new(preferences)),
+            runtime_config,
+            args.http_address,
+            shared_state,
+            timer,
+        );
+        if let Some(port) = args.http_port {
+            state.http_port = port;
+        }
+        state.thread_options = args.thread_options();
+        *state.shared_state.max_tick_duration.lock().unwrap() =
+            args.max_tick_duration.map(Duration::from_millis);
+
+        // Without a user interface, the HTTP API is the only way to control
+        // the debugger.
+        if args.http_port.is_some() || args.headless {
+            state.start_http_api();
+        }
+
+        if let Some(path) = args.wasm_path {
+            let scenario = args.scenario.and_then(|scenario_path| {
+                let scenario = Scenario::read(&scenario_path)
+                    .map_err(|e| {
+                        state
+                            .timer
+                            .0
+                            .write()
+                            .unwrap()
+                            .log(format!("{e:?}").into(), LogType::Debugger(LogLevel::Error))
+                    })
+                    .ok();
+                state.scenario_path = Some(scenario_path);
+                scenario
+            });
+            state.load_with(
+                Load::File(path),
+                scenario.map(|scenario| ScenarioRun::new(scenario, 0)),
+                None,
+            );
+        }
+
+        state
+    }
+
+    /// Creates another auto splitter with its own runtime thread. It shares
+    /// the preferences and the runtime configuration with this one.
+    fn new_instance(&self) -> Self {
+        let time_zone = self.timer.0.read().unwrap().time_zone;
+        let (shared_state, timer) = start_runtime(time_zone, self.thread_options);
+        if let Some(ctx) = self.shared_state.ui_context.get() {
+            let _ = shared_state.ui_context.set(ctx.clone());
+        }
+        Self::new(
+            self.preferences.clone(),
+            self.runtime_config,
+            self.http_address,
+            shared_state,
+            timer,
+        )
+    }
+
+    fn name(&self) -> String {
+        let Some(name) = self.path.as_deref().and_then(Path::file_name) else {
+            return "Nothing loaded".into();
+        };
+        if self.runtime_config.optimize {
+            name.to_string_lossy().into_owned()
+        } else {
+            format!("{} (Unoptimized)", name.to_string_lossy())
+        }
+    }
+
+    /// Starts the auto splitter a second time next to this one, with the
+    /// optimizations of the runtime toggled. Both get restarted with the same
+    /// settings and record their timer actions, so they can be compared.
+    fn start_ab_test(&mut self) -> Self {
+        let mut other = self.new_instance();
+        other.pending_runtime_config.optimize = !self.runtime_config.optimize;
+        other.path = self.path.clone();
+        other.script_path = self.script_path.clone();
+        other.apply_runtime_config();
+
+        if let Some(auto_splitter) = &*self.shared_state.auto_splitter.load() {
+            let settings_map = auto_splitter.settings_map();
+            other.shared_state.change_settings_map(
+                &other.timer,
+                settings_history::Origin::Debugger,
+                |_| settings_map.clone(),
+            );
+        }
+
+        for state in [&mut *self, &mut other] {
+            state.start_scenario(
+                ScenarioRun::recording(0),
+                "Started recording the timer events for the A/B test.",
+            );
+        }
+        other
+    }
+
+    fn load(&mut self, load: Load) {
+        self.load_with(load, None, None);
+    }
+
+    /// Loads the auto splitter. If there is a scenario, its timer events get
+    /// checked against it from before it runs its first tick. If there is a
+    /// settings map, the auto splitter starts with it instead of its current
+    /// settings.
+    fn load_with(
+        &mut self,
+        load: Load,
+        scenario: Option<ScenarioRun>,
+        settings_map: Option<settings::Map>,
+    ) {
+        let current_settings_map = if let Load::File(path) = &load {
+            self.path = Some(path.clone());
+            None
+        } else {
+            self.shared_state
+                .auto_splitter
+                .load()
+                .as_ref()
+                .map(|r| r.settings_map())
+        };
+        let settings_map = settings_map.or(current_settings_map);
+
+        let mut succeeded = true;
+
+        if let (Load::File(_) | Load::Reload, Some(path)) = (&load, &self.path) {
+            self.auto_restart.reset();
+            self.module_info = None;
+            self.diagnostics.clear();
+            self.module_data = None;
+            self.disassembly = None;
+            self.global_variables = None;
+            self.compile_time = None;
+            self.load_failure = None;
+            self.module = match fs::read(path)
+                .context("Failed loading the auto splitter from the file system.")
+                .and_then(|data| {
+                    self.module_info = ModuleInfo::parse(&data).ok();
+                    if let Some(info) = &self.module_info {
+                        self.diagnostics =
+                            diagnostics::lint(info, self.runtime_config.optimize);
+                    }
+                    if let (Load::File(_), Some(info)) = (&load, &self.module_info) {
+                        self.process_candidates.names = info.process_names.join(", ");
+                        self.process_candidates.invalidate();
+                    }
+                    if let Some(info) = &self.module_info {
+                        let mut timer = self.timer.0.write().unwrap();
+                        for (module, import) in info.unknown_imports() {
+                            timer.log(
+                                if module == module_info::RUNTIME_MODULE {
+                                    format!(
+                                        "The auto splitter imports the {} \"{}\", which the runtime is not known to provide. {} If the auto splitter fails to instantiate, try updating the debugger.",
+                                        import.kind, import.name, info.unknown_import_origin(&import.name),
+                                    )
+                                } else {
+                                    format!(
+                                        "The auto splitter imports the {} \"{}\" from the unknown module \"{module}\". Only \"{}\" and \"{}\" are provided by the runtime.",
+                                        import.kind, import.name, module_info::RUNTIME_MODULE, module_info::WASI_MODULE,
+                                    )
+                                }
+                                .into(),
+                                LogType::Debugger(LogLevel::Warning),
+                            );
+                        }
+                    }
+                    let key = (hash_module(&data), self.runtime_config);
+                    // A module from the cache has no compile time.
+                    let result = if let Some(module) = self.module_cache.get(&key) {
+                        self.timer.0.write().unwrap().log(
+                            "The auto splitter is unchanged. Reusing the already compiled module."
+                                .into(),
+                            LogType::Debugger(LogLevel::Debug),
+                        );
+                        Ok((module.clone(), None))
+                    } else {
+                        let start = Instant::now();
+                        tracing::info_span!("compile", size = data.len())
+                            .in_scope(|| self.runtime.compile(&data))
+                            .context("Failed loading the auto splitter.")
+                            .map(|module| {
+                                if self.module_cache.len() >= MODULE_CACHE_CAPACITY {
+                                    self.module_cache.shift_remove_index(0);
+                                }
+                                self.module_cache.insert(key, module.clone());
+                                (module, Some(start.elapsed()))
+                            })
+                    };
+                    self.module_data = Some(data.into());
+                    result
+                }) {
+                Ok((module, compile_time)) => {
+                    self.compile_time = compile_time;
+                    if let Some(compile_time) = compile_time {
+                        self.timer.0.write().unwrap().log(
+                            format!("Compiling the auto splitter took {}.", fmt_std_duration(compile_time))
+                                .into(),
+                            LogType::Debugger(LogLevel::Debug),
+                        );
+                    }
+                    Some(module)
+                }
+                Err(e) => {
+                    succeeded = false;
+                    self.load_failure = Some(Failure::Compile);
+                    self.timer
+                        .0
+                        .write()
+                        .unwrap()
+                        .log(format!("{e:?}").into(), LogType::Debugger(LogLevel::Error));
+                    None
+                }
+            };
+            self.module_watch.loaded(path);
+        }
+
+        self.instantiate_time = None;
+        let new_auto_splitter = if let Some(module) = &self.module {
+            let start = Instant::now();
+            match tracing::info_span!("instantiate")
+                .in_scope(|| {
+                    module.instantiate(
+                        self.timer.clone(),
+                        settings_map,
+                        self.script_path.as_deref(),
+                    )
+                })
+                .context("Failed starting the auto splitter.")
+            {
+                Ok(r) => {
+                    self.load_failure = None;
+                    let instantiate_time = start.elapsed();
+                    self.instantiate_time = Some(instantiate_time);
+                    self.timer.0.write().unwrap().log(
+                        format!(
+                            "Instantiating the auto splitter took {}.",
+                            fmt_std_duration(instantiate_time),
+                        )
+                        .into(),
+                        LogType::Debugger(LogLevel::Debug),
+                    );
+                    Some(Arc::new(r))
+                }
+                Err(e) => {
+                    succeeded = false;
+                    self.load_failure = Some(Failure::Instantiation);
+                    self.timer
+                        .0
+                        .write()
+                        .unwrap()
+                        .log(format!("{e:?}").into(), LogType::Debugger(LogLevel::Error));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        self.shared_state.kill_auto_splitter_if_it_doesnt_react();
+        {
+            // The timer stays locked until the new auto splitter is stored, so
+            // it can't tick before the scenario is in place.
+            let mut timer = self.timer.0.write().unwrap();
+            if let Some(mut run) = scenario {
+                run.start(timer.tick_index);
+                timer.scenario = Some(run);
+            }
+            let mut settings_history = self.shared_state.settings_history.lock().unwrap();
+            if let Load::File(_) = &load {
+                settings_history.changes.clear();
+                settings_history.unseen_self_changes = 0;
+            }
+            settings_history.reset(new_auto_splitter.as_ref().map(|a| a.settings_map()));
+            self.shared_state.auto_splitter.store(new_auto_splitter);
+            self.shared_state.memory_view.invalidate();
+        }
+
+        *self.shared_state.slowest_tick.lock().unwrap() = std::time::Duration::ZERO;
+        self.shared_state
+            .avg_tick_secs
+            .store(0.0, atomic::Ordering::Relaxed);
+        self.shared_state.tick_times.lock().unwrap().clear();
+        // A newly loaded file is a different auto splitter, so there's
+        // nothing to compare it to.
+        self.shared_state
+            .benchmark
+            .lock()
+            .unwrap()
+            .restart(!matches!(load, Load::File(_)));
+        self.stats_recorded = false;
+        self.shared_state.tick_heatmap.lock().unwrap().clear();
+        self.shared_state.tick_jitter.lock().unwrap().clear();
+        self.shared_state
+            .missed_ticks
+            .store(0, atomic::Ordering::Relaxed);
+        self.shared_state
+            .memory_usage
+            .store(0, atomic::Ordering::Relaxed);
+        self.shared_state
+            .memory_grow_count
+            .store(0, atomic::Ordering::Relaxed);
+        self.shared_state
+            .handles
+            .store(0, atomic::Ordering::Relaxed);
+        self.shared_state
+            .process_handles
+            .store(0, atomic::Ordering::Relaxed);
+        self.shared_state
+            .handle_trace
+            .lock()
+            .unwrap()
+            .growths
+            .clear();
+
+        let mut timer = self.timer.0.write().unwrap();
+        if let Load::File(_) = &load {
+            timer.clear();
+        }
+        timer.load_time = time::OffsetDateTime::now_utc().to_offset(timer.time_zone);
+        timer.variables.clear();
+        timer.output_event("loaded", || {
+            serde_json::json!({
+                "kind": match &load {
+                    Load::File(_) => "file",
+                    Load::Reload => "reload",
+                    Load::Restart => "restart",
+                },
+                "path": self.path.as_ref().map(|path| path.display().to_string()),
+                "succeeded": succeeded,
+            })
+        });
+
+        if succeeded {
+            timer.log(
+                match &load {
+                    Load::File(_) => "Auto splitter loaded.",
+                    Load::Reload => "Auto splitter reloaded.",
+                    Load::Restart => "Auto splitter restarted.",
+                }
+                .into(),
+                LogType::Debugger(LogLevel::Info),
+            );
+        }
+        drop(timer);
+
+        if let (Load::File(path), true) = (&load, succeeded) {
+            if self
+                .preferences
+                .borrow()
+                .games
+                .get(path)
+                .is_some_and(|game| game.launch_on_load)
+            {
+                self.launch_game();
+            }
+        }
+    }
+
+    /// Does the work that doesn't involve the user interface, like handling
+    /// the requests of the HTTP API and reloading modified files.
+    fn update(&mut self, now: Instant) {
+        if self.auto_reload {
+            self.reload_modified_files(now);
+        }
+
+        if !self.stats_recorded {
+            self.record_stats();
+        }
+
+        self.save_trap_artifacts(now);
+        self.resolve_pointer_paths(now);
+
+        while let Some(request) = self.http_api.as_ref().and_then(|api| api.poll()) {
+            self.handle_api_request(request);
+        }
+
+        while let Some(event) = self.cargo_build.as_ref().and_then(|b| b.poll()) {
+            match event {
+                BuildEvent::Output(output, level) => self
+                    .timer
+                    .0
+                    .write()
+                    .unwrap()
+                    .log(output.into(), LogType::Build(level)),
+                BuildEvent::Finished(artifact) => {
+                    self.cargo_build = None;
+                    match artifact {
+                        Some(artifact) if Some(&artifact) == self.path.as_ref() => {
+                            self.load(Load::Reload)
+                        }
+                        Some(artifact) => self.load(Load::File(artifact)),
+                        None => self.timer.0.write().unwrap().log(
+                            "The build failed or didn't produce a WASM file.".into(),
+                            LogType::Build(LogLevel::Error),
+                        ),
+                    }
+                }
+            }
+        }
+
+        if self
+            .shared_state
+            .trapped
+            .swap(false, atomic::Ordering::Relaxed)
+        {
+            let message = match self.auto_restart.trapped(now) {
+                auto_restart::Trapped::Ignored => None,
+                auto_restart::Trapped::Scheduled(delay) => Some((
+                    format!(
+                        "Restarting the auto splitter in {} (retry {} of {}).",
+                        fmt_std_duration(delay),
+                        self.auto_restart.retries(),
+                        self.auto_restart.max_retries,
+                    ),
+                    LogLevel::Info,
+                )),
+                auto_restart::Trapped::GaveUp => Some((
+                    format!(
+                        "The auto splitter kept trapping. Giving up restarting it after {} retries.",
+                        self.auto_restart.retries(),
+                    ),
+                    LogLevel::Warning,
+                )),
+            };
+            if let Some((message, level)) = message {
+                self.timer
+                    .0
+                    .write()
+                    .unwrap()
+                    .log(message.into(), LogType::Debugger(level));
+            }
+        }
+        if self.auto_restart.poll(now) {
+            self.load(Load::Restart);
+        }
+    }
+
+    /// Finds the addresses of the modules the pointer paths are relative to.
+    /// Pointer paths of processes that aren't attached anymore move to an
+    /// attached process with the same executable, such as the restarted game.
+    /// The memory maps are read without holding the locks the runtime thread
+    /// needs.
+    fn resolve_pointer_paths(&mut self, now: Instant) {
+        let paths: Vec<(u64, Box<str>, Box<str>)> = self
+            .shared_state
+            .pointer_paths
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|p| (p.pid, p.process_path.clone(), p.module.clone()))
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+        let processes: Vec<(u64, String)> = self
+            .shared_state
+            .processes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|p| (p.raw_pid, p.path.clone()))
+            .collect();
+
+        let resolved: Vec<(u64, Option<u64>)> = paths
+            .iter()
+            .map(|(pid, process_path, module)| {
+                let pid = if processes.iter().any(|(attached, _)| attached == pid) {
+                    *pid
+                } else {
+                    processes
+                        .iter()
+                        .find(|(_, path)| **path == **process_path)
+                        .map_or(*pid, |(attached, _)| *attached)
+                };
+                let base = self
+                    .process_maps
+                    .get(pid, now)
+                    .as_ref()
+                    .ok()
+                    .and_then(|maps| {
+                        maps.modules
+                            .iter()
+                            .find(|m| m.name.eq_ignore_ascii_case(module))
+                    })
+                    .map(|m| m.base);
+                (pid, base)
+            })
+            .collect();
+
+        // Only the UI thread adds and removes pointer paths, so they are still
+        // the same ones.
+        let mut pointer_paths = self.shared_state.pointer_paths.lock().unwrap();
+        for (pointer_path, (pid, base)) in pointer_paths.iter_mut().zip(resolved) {
+            pointer_path.pid = pid;
+            pointer_path.module_base = base;
+        }
+    }
+
+    fn start_http_api(&mut self) {
+        let mut timer = self.timer.0.write().unwrap();
+        let shared_state = self.shared_state.clone();
+        let on_request = move || {
+            if let Some(ctx) = shared_state.ui_context.get() {
+                ctx.request_repaint();
+            }
+        };
+        match HttpApi::start(self.http_address, self.http_port, on_request) {
+            Ok(api) => {
+                let mut message = format!("The HTTP API is listening on http://{}.", api.address);
+                // The token is kept out of the logs, as they end up in files
+                // and crash reports.
+                if api.token.is_some() {
+                    message.push_str(" It is reachable from other machines, so requests need to send its token in the header \"Authorization: Bearer <token>\".");
+                }
+                timer.log(message.into(), LogType::Debugger(LogLevel::Info));
+                self.http_api = Some(api);
+            }
+            Err(e) => timer.log(
+                format!("Failed to start the HTTP API: {e}").into(),
+                LogType::Debugger(LogLevel::Error),
+            ),
+        }
+    }
+
+    /// Checks the timer events against the scenario in the headless mode and
+    /// returns why to stop once there is a result. The scenario fails as soon
+    /// as an expected timer event fails or an unexpected one happens. It
+    /// passes once every expected event happened, unless it should keep
+    /// running until the time is up, in which case the events that are still
+    /// missing then fail it. The failures are logged.
+    fn check_scenario(
+        &self,
+        time_is_up: bool,
+        run_until_time_is_up: bool,
+    ) -> Option<(Box<str>, LogLevel, u8)> {
+        let mut timer = self.timer.0.write().unwrap();
+        let Some(run) = &timer.scenario else {
+            return Some((
+                "There is no scenario to check the timer events against.".into(),
+                LogLevel::Error,
+                Failure::Scenario.exit_code(),
+            ));
+        };
+        let evaluation = run.evaluate(timer.tick_index);
+        let finished = evaluation.pending() == 0 && !run_until_time_is_up;
+        if evaluation.failed() == 0 && !finished && !time_is_up {
+            return None;
+        }
+
+        let mut failures = Vec::new();
+        for (expected, outcome) in run.scenario.events.iter().zip(&evaluation.outcomes) {
+            let reason = match outcome {
+                scenario::Outcome::Passed => continue,
+                scenario::Outcome::Pending => "Didn't happen in time.",
+                scenario::Outcome::Failed(reason) => reason,
+            };
+            failures.push(format!(
+                "{} at tick {}: {reason}",
+                expected.event.to_str(),
+                expected.tick,
+            ));
+        }
+        for &index in &evaluation.unexpected {
+            let event = &run.events[index];
+            failures.push(format!(
+                "{} at tick {}: Wasn't expected.",
+                event.event.to_str(),
+                event.tick,
+            ));
+        }
+        let passed = evaluation.passed();
+        for failure in &failures {
+            timer.log(failure.as_str().into(), LogType::Debugger(LogLevel::Error));
+        }
+
+        Some(if failures.is_empty() {
+            (
+                format!("The scenario passed with {passed} matching timer events.").into(),
+                LogLevel::Info,
+                self.failure().map_or(0, Failure::exit_code),
+            )
+        } else {
+            (
+                format!(
+                    "The scenario failed with {passed} matching and {} failing timer events.",
+                    failures.len(),
+                )
+                .into(),
+                LogLevel::Error,
+                Failure::Scenario.exit_code(),
+            )
+        })
+    }
+
+    /// How the auto splitter failed, if it did. Updates that got interrupted
+    /// count as watchdog timeouts rather than traps.
+    fn failure(&self) -> Option<Failure> {
+        if let Some(failure) = self.load_failure {
+            return Some(failure);
+        }
+        let trap_stats = self.shared_state.trap_stats.lock().unwrap();
+        if trap_stats.count(TrapKind::Interrupt) != 0 {
+            Some(Failure::WatchdogTimeout)
+        } else if trap_stats.total() != 0 {
+            Some(Failure::Trap)
+        } else {
+            None
+        }
+    }
+
+    /// The statistics of the auto splitter, as returned by the HTTP API and
+    /// printed by the JSON output.
+    fn stats_json(&self) -> serde_json::Value {
+        let shared_state = &self.shared_state;
+        serde_json::json!({
+            "tick_rate": shared_state.tick_rate.lock().unwrap().as_secs_f64(),
+            "avg_tick_time": shared_state.avg_tick_secs.load(atomic::Ordering::Relaxed),
+            "slowest_tick_time": shared_state.slowest_tick.lock().unwrap().as_secs_f64(),
+            "memory_usage": shared_state.memory_usage.load(atomic::Ordering::Relaxed),
+            "peak_memory_usage": shared_state.peak_memory_usage.load(atomic::Ordering::Relaxed),
+            "memory_grow_count": shared_state.memory_grow_count.load(atomic::Ordering::Relaxed),
+            "handles": shared_state.handles.load(atomic::Ordering::Relaxed),
+            "process_handles": shared_state.process_handles.load(atomic::Ordering::Relaxed),
+            "traps": shared_state.trap_stats.lock().unwrap().total(),
+        })
+    }
+
+    fn handle_api_request(&mut self, request: ApiRequest) {
+        let auto_splitter = self.shared_state.auto_splitter.load_full();
+        let no_auto_splitter = || serde_json::json!({ "error": "No auto splitter is loaded." });
+
+        let (status, body) = match &request.endpoint {
+            Endpoint::Timer => {
+                let timer = self.timer.0.read().unwrap();
+                (
+                    200,
+                    serde_json::json!({
+                        "state": timer_state_to_str(timer.timer_state),
+                        "game_time": timer.game_time.as_seconds_f64(),
+                        "game_time_state": timer.game_time_state.to_str(),
+                        "split_index": timer.split_index,
+                        "tick": timer.tick_index,
+                    }),
+                )
+            }
+            Endpoint::Variables => {
+                let timer = self.timer.0.read().unwrap();
+                (
+                    200,
+                    timer
+                        .variables
+                        .iter()
+                        .map(|(key, variable)| (key.to_string(), variable.value.clone().into()))
+                        .collect::<serde_json::Map<_, _>>()
+                        .into(),
+                )
+            }
+            Endpoint::Stats => (200, self.stats_json()),
+            Endpoint::Logs { since } => {
+                let timer = self.timer.0.read().unwrap();
+                (
+                    200,
+                    serde_json::json!({
+                        "next": timer.logs.len(),
+                        "logs": timer
+                            .logs
+                            .get(*since..)
+                            .unwrap_or_default()
+                            .iter()
+                            .map(LogMessage::to_json)
+                            .collect::<Vec<_>>(),
+                    }),
+                )
+            }
+            Endpoint::Settings => match &auto_splitter {
+                Some(auto_splitter) => (
+                    200,
+                    settings_json::settings_map_to_json(&auto_splitter.settings_map()),
+                ),
+                None => (409, no_auto_splitter()),
+            },
+            Endpoint::Reload | Endpoint::Restart if self.path.is_none() => {
+                (409, no_auto_splitter())
+            }
+            Endpoint::Reload => {
+                self.load(Load::Reload);
+                (200, serde_json::json!({}))
+            }
+            Endpoint::Restart => {
+                self.load(Load::Restart);
+                (200, serde_json::json!({}))
+            }
+            Endpoint::Kill => match &auto_splitter {
+                Some(auto_splitter) => {
+                    auto_splitter.interrupt_handle().interrupt();
+                    (200, serde_json::json!({}))
+                }
+                None => (409, no_auto_splitter()),
+            },
+            Endpoint::SetSetting { key, value } => match &auto_splitter {
+                Some(_) => {
+                    self.shared_state.change_settings_map(
+                        &self.timer,
+                        settings_history::Origin::HttpApi,
+                        |old| {
+                            let mut new = old.clone();
+                            new.insert((**key).into(), value.clone());
+                            new
+                        },
+                    );
+                    (200, serde_json::json!({}))
+                }
+                None => (409, no_auto_splitter()),
+            },
+        };
+
+        request.respond(status, body);
+    }
+
+    /// Restarts the auto splitter with a fresh timer and starts checking its
+    /// timer events against the scenario file.
+    fn run_scenario(&mut self) {
+        let Some(path) = &self.scenario_path else {
+            return;
+        };
+        match Scenario::read(path) {
+            Ok(scenario) => self.start_scenario(
+                ScenarioRun::new(scenario, 0),
+                "Started checking the scenario.",
+            ),
+            Err(e) => self
+                .timer
+                .0
+                .write()
+                .unwrap()
+                .log(format!("{e:?}").into(), LogType::Debugger(LogLevel::Error)),
+        }
+    }
+
+    fn start_scenario(&mut self, run: ScenarioRun, message: &str) {
+        self.timer.0.write().unwrap().reset();
+        self.timer
+            .0
+            .write()
+            .unwrap()
+            .log(message.into(), LogType::Debugger(LogLevel::Info));
+        self.load_with(Load::Restart, Some(run), None);
+    }
+
+    fn launch_game(&mut self) {
+        let preferences = self.preferences.borrow();
+        let Some(game) = self
+            .path
+            .as_ref()
+            .and_then(|path| preferences.games.get(path))
+        else {
+            return;
+        };
+        let on_exit = {
+            let timer = self.timer.clone();
+            let executable = game.executable.display().to_string();
+            move |status: io::Result<process::ExitStatus>| {
+                let (message, level) = match status {
+                    Ok(status) => (format!("{executable} exited ({status})."), LogLevel::Info),
+                    Err(e) => (
+                        format!("Failed to wait for {executable} to exit: {e}"),
+                        LogLevel::Warning,
+                    ),
+                };
+                timer
+                    .0
+                    .write()
+                    .unwrap()
+                    .log(message.into(), LogType::Debugger(level));
+            }
+        };
+        let mut timer = self.timer.0.write().unwrap();
+        match game.launch(on_exit) {
+            Ok(id) => timer.log(
+                format!("Launched {} (PID {id}).", game.executable.display()).into(),
+                LogType::Debugger(LogLevel::Info),
+            ),
+            Err(e) => timer.log(
+                format!("Failed to launch {}: {e}", game.executable.display()).into(),
+                LogType::Debugger(LogLevel::Error),
+            ),
+        }
+    }
+
+    /// Information about the debugger and the system it runs on, as needed
+    /// for bug reports.
+    fn environment(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Debugger", environment::DEBUGGER_VERSION.into()),
+            (
+                "livesplit-auto-splitting",
+                environment::LIVESPLIT_AUTO_SPLITTING_VERSION.into(),
+            ),
+            ("wasmtime", environment::WASMTIME_VERSION.into()),
+            ("Optimize", self.runtime_config.optimize.to_string()),
+            ("Debug Info", self.runtime_config.debug_info.to_string()),
+            (
+                "Backtrace Details",
+                self.runtime_config.backtrace_details.to_string(),
+            ),
+            ("OS", environment::os()),
+            ("CPU Features", environment::cpu_features().join(", ")),
+        ]
+    }
+
+    fn save_crash_report(&mut self, path: &Path) {
+        let report = self.crash_report(self.crash_report_memory);
+        let result = fs::File::create(path)
+            .map_err(Into::into)
+            .and_then(|file| report.write_zip(io::BufWriter::new(file)));
+
+        if let Err(e) = result {
+            self.timer.0.write().unwrap().log(
+                format!("Failed to save the crash report: {}", e).into(),
+                LogType::Debugger(LogLevel::Error),
+            );
+        }
+    }
+
+    /// Collects a copy of everything that belongs into a crash report. The
+    /// memory is only included if the auto splitter isn't busy with an
+    /// update, otherwise this is logged.
+    fn crash_report(&self, include_memory: bool) -> CrashReport {
+        let mut info = String::new();
+        if let Some(path) = &self.path {
+            let _ = writeln!(info, "Auto Splitter: {}", path.display());
+        }
+        if let Some(data) = &self.module_data {
+            let _ = writeln!(info, "Module Hash: {:016x}", hash_module(data));
+        }
+        for (key, value) in self.environment() {
+            let _ = writeln!(info, "{key}: {value}");
+        }
+
+        let auto_splitter = self.shared_state.auto_splitter.load();
+        let mut memory_busy = false;
+        let memory = match auto_splitter.as_deref() {
+            Some(auto_splitter) if include_memory => {
+                let memory =
+                    SharedState::try_lock(auto_splitter).map(|guard| guard.memory().to_vec());
+                memory_busy = memory.is_none();
+                memory
+            }
+            _ => None,
+        };
+        let settings_map = auto_splitter.as_ref().map(|a| a.settings_map());
+        let traps = self.shared_state.trap_stats.lock().unwrap().clone();
+
+        let mut logs = String::new();
+        {
+            let timer = self.timer.0.read().unwrap();
+            let _ = writeln!(info, "Tick: {}", timer.tick_index);
+            for log in &timer.logs {
+                let _ = writeln!(logs, "{log}");
+            }
+        }
+
+        if memory_busy {
+            self.timer.0.write().unwrap().log(
+                "The memory couldn't be included in the report, because the auto splitter is busy with an update.".into(),
+                LogType::Debugger(LogLevel::Warning),
+            );
+        }
+
+        CrashReport {
+            info,
+            logs,
+            settings_map,
+            traps,
+            memory,
+        }
+    }
+
+    /// Saves the logs, the memory and the settings map into a new folder
+    /// whenever the auto splitter traps, so they are still around when the
+    /// debugger got closed before investigating the trap. The files are
+    /// written on a separate thread, so neither the UI nor the runtime has to
+    /// wait for the disk.
+    fn save_trap_artifacts(&mut self, now: Instant) {
+        let traps = self.shared_state.trap_stats.lock().unwrap().total();
+        let trapped = traps > self.saved_traps;
+        // The trap statistics can also be cleared.
+        self.saved_traps = traps;
+        if !trapped
+            || self
+                .trap_artifacts_saved
+                .is_some_and(|saved| now.duration_since(saved) < TRAP_ARTIFACT_INTERVAL)
+        {
+            return;
+        }
+        let Some(folder) = self.preferences.borrow().trap_artifacts.clone() else {
+            return;
+        };
+        self.trap_artifacts_saved = Some(now);
+
+        let path = {
+            let timer = self.timer.0.read().unwrap();
+            let time = time::OffsetDateTime::now_utc().to_offset(timer.time_zone);
+            let (h, m, s) = time.time().as_hms();
+            folder.join(format!(
+                "trap_{}_{h:02}-{m:02}-{s:02}_tick_{}",
+                time.date(),
+                timer.tick_index,
+            ))
+        };
+        let report = self.crash_report(true);
+        let timer = self.timer.clone();
+
+        thread::spawn(move || {
+            let result = report.write_folder(&path);
+            let mut timer = timer.0.write().unwrap();
+            match result {
+                Ok(()) => timer.log(
+                    format!("Saved the artifacts of the trap to {}.", path.display()).into(),
+                    LogType::Debugger(LogLevel::Info),
+                ),
+                Err(e) => timer.log(
+                    format!("Failed to save the artifacts of the trap: {e}").into(),
+                    LogType::Debugger(LogLevel::Error),
+                ),
+            }
+        });
+    }
+
+    /// Stops the auto splitter before the debugger exits or its instance
+    /// gets closed. A running update gets interrupted and the runtime thread
+    /// is given some time to stop, so nothing is written to the logs anymore
+    /// once they are flushed. When the debugger exits with the layout of the
+    /// tabs, the session is saved to the file it was last saved to or
+    /// restored from, if there is one.
+    fn shutdown(&mut self, dock_state: Option<&DockState<Tab>>) {
+        if self
+            .shared_state
+            .closed
+            .swap(true, atomic::Ordering::Relaxed)
+        {
+            // Already shut down.
+            return;
+        }
+        if let Some(run) = self.timer_script_run.take() {
+            run.stop();
+        }
+        // The runtime thread doesn't start any further updates once it's
+        // closed, so this at most interrupts the last one, which may be stuck.
+        if let Some(auto_splitter) = &*self.shared_state.auto_splitter.load() {
+            auto_splitter.interrupt_handle().interrupt();
+        }
+
+        let start = Instant::now();
+        while !self
+            .shared_state
+            .runtime_stopped
+            .load(atomic::Ordering::Relaxed)
+        {
+            if start.elapsed() >= SHUTDOWN_TIMEOUT {
+                self.timer.0.write().unwrap().log(
+                    "The auto splitter didn't stop in time.".into(),
+                    LogType::Debugger(LogLevel::Warning),
+                );
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        if let (Some(path), Some(dock_state)) = (self.session_path.clone(), dock_state) {
+            self.save_session(&path, dock_state);
+        }
+        // The runtime thread may still hold on to the auto splitter if it
+        // didn't stop in time, but nothing else does anymore.
+        self.shared_state.auto_splitter.store(None);
+
+        let mut timer = self.timer.0.write().unwrap();
+        timer.log(
+            "Shut down the debugger.".into(),
+            LogType::Debugger(LogLevel::Info),
+        );
+        let log_file = timer.log_file.take();
+        drop(timer);
+        if let Some(log_file) = log_file {
+            let path = log_file.path().display().to_string();
+            if let Err(e) = log_file.close() {
+                eprintln!("Failed to write the log file {path}: {e}");
+            }
+        }
+    }
+
+    /// Adds the statistics of the current build to the statistics history,
+    /// once they were measured.
+    fn record_stats(&mut self) {
+        let (Some(path), Some(result)) = (
+            &self.path,
+            self.shared_state.benchmark.lock().unwrap().result(),
+        ) else {
+            return;
         };
-
-        DockArea::new(&mut self.dock_state)
-            .show_window_close_buttons(false)
-            .style(Style::from_egui(ctx.style().as_ref()))
-            .show(ctx, &mut tab_viewer);
+        self.stats_recorded = true;
+        let entry = stats_history::Entry {
+            date: time::OffsetDateTime::now_utc().unix_timestamp(),
+            module_hash: self.module_data.as_deref().map_or(0, hash_module),
+            p50: result.p50,
+            p99: result.p99,
+            peak_memory: self
+                .shared_state
+                .peak_memory_usage
+                .load(atomic::Ordering::Relaxed) as u64,
+        };
+        let mut preferences = self.preferences.borrow_mut();
+        stats_history::record(
+            preferences.stats_history.entry(path.clone()).or_default(),
+            entry,
+        );
     }
-}
 
-enum Load {
-    File(PathBuf),
-    Reload,
-    Restart,
-}
+    fn save_session(&mut self, path: &Path, dock_state: &DockState<Tab>) {
+        let mut statistics = session::Statistics::default();
+        statistics.record_tick_times(&self.shared_state.tick_times.lock().unwrap());
+        statistics.slowest_tick = *self.shared_state.slowest_tick.lock().unwrap();
+        statistics.missed_ticks = self
+            .shared_state
+            .missed_ticks
+            .load(atomic::Ordering::Relaxed);
+        statistics.peak_memory_usage = self
+            .shared_state
+            .peak_memory_usage
+            .load(atomic::Ordering::Relaxed);
 
-impl AppState {
-    fn load(&mut self, load: Load) {
-        let settings_map = if let Load::File(path) = &load {
-            self.path = Some(path.clone());
-            None
-        } else {
-            self.shared_state
+        let mut timer = self.timer.0.write().unwrap();
+        let session = Session {
+            dock_state: Some(dock_state.clone()),
+            path: self.path.clone(),
+            script_path: self.script_path.clone(),
+            settings_map: self
+                .shared_state
                 .auto_splitter
                 .load()
                 .as_ref()
-                .map(|r| r.settings_map())
+                .map(|a| settings_json::settings_map_to_json(&a.settings_map())),
+            pinned_variables: self.pinned_variables.iter().cloned().collect(),
+            statistics,
+            logs: timer.logs.iter().map(LogMessage::to_session_log).collect(),
         };
 
-        let mut succeeded = true;
-
-        if let (Load::File(_) | Load::Reload, Some(path)) = (&load, &self.path) {
-            self.module = match fs::read(path)
-                .context("Failed loading the auto splitter from the file system.")
-                .and_then(|data| {
-                    self.runtime
-                        .compile(&data)
-                        .context("Failed loading the auto splitter.")
-                }) {
-                Ok(module) => Some(module),
-                Err(e) => {
-                    succeeded = false;
-                    self.timer
-                        .0
-                        .write()
-                        .unwrap()
-                        .log(format!("{e:?}").into(), LogType::Runtime(LogLevel::Error));
-                    None
-                }
-            };
-            self.module_modified_time = fs::metadata(path).ok().and_then(|m| m.modified().ok());
+        match fs::File::create(path).and_then(|f| session.write(io::BufWriter::new(f))) {
+            Ok(()) => {
+                self.session_path = Some(path.to_owned());
+                timer.log(
+                    "Saved the session.".into(),
+                    LogType::Debugger(LogLevel::Info),
+                );
+            }
+            Err(e) => timer.log(
+                format!("Failed to save the session: {e}").into(),
+                LogType::Debugger(LogLevel::Error),
+            ),
         }
+    }
 
-        let new_auto_splitter = if let Some(module) = &self.module {
-            match module
-                .instantiate(
-                    self.timer.clone(),
-                    settings_map,
-                    self.script_path.as_deref(),
-                )
-                .context("Failed starting the auto splitter.")
-            {
-                Ok(r) => Some(Arc::new(r)),
-                Err(e) => {
-                    succeeded = false;
-                    self.timer
-                        .0
-                        .write()
-                        .unwrap()
-                        .log(format!("{e:?}").into(), LogType::Runtime(LogLevel::Error));
-                    None
-                }
+    fn restore_session(&mut self, session: Session, path: &Path) {
+        self.session_path = Some(path.to_owned());
+        self.script_path = session.script_path;
+        self.watched_directory = None;
+        let settings_map = match &session.settings_map {
+            Some(serde_json::Value::Object(settings_map)) => {
+                Some(settings_json::settings_map_from_json(settings_map))
             }
-        } else {
-            None
+            _ => None,
         };
+        if let Some(auto_splitter_path) = session.path {
+            // The auto splitter registers its settings based on the settings
+            // map it gets instantiated with, so it needs to start with the
+            // restored one.
+            self.load_with(Load::File(auto_splitter_path), None, settings_map);
+        } else if let Some(settings_map) = settings_map {
+            self.shared_state.change_settings_map(
+                &self.timer,
+                settings_history::Origin::Debugger,
+                |_| settings_map.clone(),
+            );
+        }
 
-        self.shared_state.kill_auto_splitter_if_it_doesnt_react();
-        self.shared_state.auto_splitter.store(new_auto_splitter);
+        self.pinned_variables = session.pinned_variables.into_iter().collect();
 
-        *self.shared_state.slowest_tick.lock().unwrap() = std::time::Duration::ZERO;
+        let statistics = &session.statistics;
+        statistics.restore_tick_times(&mut self.shared_state.tick_times.lock().unwrap());
+        *self.shared_state.slowest_tick.lock().unwrap() = statistics.slowest_tick;
         self.shared_state
-            .avg_tick_secs
-            .store(0.0, atomic::Ordering::Relaxed);
-        self.shared_state.tick_times.lock().unwrap().clear();
+            .missed_ticks
+            .store(statistics.missed_ticks, atomic::Ordering::Relaxed);
+        self.shared_state
+            .peak_memory_usage
+            .fetch_max(statistics.peak_memory_usage, atomic::Ordering::Relaxed);
 
         let mut timer = self.timer.0.write().unwrap();
-        if let Load::File(_) = &load {
-            timer.clear();
+        let time_zone = timer.time_zone;
+        let mut logs: Vec<_> = session
+            .logs
+            .iter()
+            .filter_map(|log| LogMessage::from_session_log(log, time_zone))
+            .collect();
+        // The messages about loading the auto splitter again come after the
+        // ones of the restored session.
+        logs.append(&mut timer.logs);
+        timer.logs = logs;
+        timer.log(
+            format!("Restored the session from {}.", path.display()).into(),
+            LogType::Debugger(LogLevel::Info),
+        );
+    }
+
+    fn apply_runtime_config(&mut self) {
+        match build_runtime(self.pending_runtime_config) {
+            Ok(runtime) => {
+                self.runtime = runtime;
+                self.runtime_config = self.pending_runtime_config;
+                self.load(Load::Reload);
+            }
+            Err(e) => self
+                .timer
+                .0
+                .write()
+                .unwrap()
+                .log(format!("{e:?}").into(), LogType::Debugger(LogLevel::Error)),
         }
-        timer.variables.clear();
+    }
 
-        if succeeded {
-            timer.log(
-                match load {
-                    Load::File(_) => "Auto splitter loaded.",
-                    Load::Reload => "Auto splitter reloaded.",
-                    Load::Restart => "Auto splitter restarted.",
+    /// Reloads the auto splitter or the script if their files changed, and
+    /// loads the newest WASM file of the watched directory.
+    fn reload_modified_files(&mut self, now: Instant) {
+        if let Some(directory) = &self.watched_directory {
+            if self
+                .last_directory_scan
+                .is_none_or(|scan| scan.elapsed() >= DIRECTORY_SCAN_INTERVAL)
+            {
+                self.last_directory_scan = Some(Instant::now());
+                if let Some(newest) = newest_wasm_file(directory) {
+                    if Some(&newest) != self.path.as_ref() {
+                        self.load(Load::File(newest));
+                    }
                 }
-                .into(),
-                LogType::Runtime(LogLevel::Info),
-            );
+            }
+        }
+        if let Some(path) = &self.path {
+            if self.module_watch.poll(path, now) {
+                // Only the modification time may have changed, such as when
+                // cargo rewrites an artifact that ended up being identical.
+                let unchanged = fs::read(path).ok().is_some_and(|data| {
+                    self.module_data
+                        .as_deref()
+                        .is_some_and(|loaded| hash_module(loaded) == hash_module(&data))
+                });
+                if unchanged {
+                    self.timer.0.write().unwrap().log(
+                        "The auto splitter was modified, but its contents are unchanged. Skipping the reload."
+                            .into(),
+                        LogType::Debugger(LogLevel::Debug),
+                    );
+                } else {
+                    self.load(Load::Reload);
+                }
+            }
+        }
+        if let Some(script_path) = &self.script_path {
+            if self.script_watch.poll(script_path, now) {
+                self.set_script_path(script_path.clone());
+            }
         }
     }
 
     fn set_script_path(&mut self, file: PathBuf) {
         let is_reload = Some(file.as_path()) == self.script_path.as_deref();
-        self.script_modified_time = fs::metadata(&file).ok().and_then(|m| m.modified().ok());
+        self.script_watch.loaded(&file);
         self.script_path = Some(file);
         self.timer.0.write().unwrap().log(
             if is_reload {
@@ -975,22 +6854,84 @@ impl AppState {
                 "Script loaded."
             }
             .into(),
-            LogType::Runtime(LogLevel::Info),
+            LogType::Debugger(LogLevel::Info),
         );
         self.load(Load::Restart);
     }
 }
 
-fn build_runtime(optimize: bool) -> Runtime {
+/// How often the watched directory is searched for a newer WASM file.
+const DIRECTORY_SCAN_INTERVAL: Duration = Duration::from_millis(500);
+
+fn newest_wasm_file(directory: &Path) -> Option<PathBuf> {
+    fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let path = entry.path();
+            if path.extension()? != "wasm" {
+                return None;
+            }
+            Some((entry.metadata().ok()?.modified().ok()?, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// How many compiled modules are kept around, so that reloading an unchanged
+/// file or toggling the optimization doesn't need to compile it again.
+const MODULE_CACHE_CAPACITY: usize = 4;
+
+/// Hashes the module with the 64-bit FNV-1a hash. The hash identifies the
+/// build in crash reports and the persisted statistics, so unlike the hasher
+/// of the standard library, it needs to stay the same across Rust releases.
+fn hash_module(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// The options of the runtime's [`Config`] that can be changed in the
+/// debugger.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+struct RuntimeConfig {
+    optimize: bool,
+    debug_info: bool,
+    backtrace_details: bool,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        let config = Config::default();
+        Self {
+            optimize: config.optimize,
+            debug_info: true,
+            backtrace_details: config.backtrace_details,
+        }
+    }
+}
+
+fn build_runtime(runtime_config: RuntimeConfig) -> anyhow::Result<Runtime> {
     let mut config = Config::default();
-    config.debug_info = true;
-    config.optimize = optimize;
-    Runtime::new(config).unwrap()
+    config.debug_info = runtime_config.debug_info;
+    config.optimize = runtime_config.optimize;
+    config.backtrace_details = runtime_config.backtrace_details;
+    Runtime::new(config).context("Failed creating the runtime.")
 }
 
 const SECONDS_PER_MINUTE: u64 = 60;
 const SECONDS_PER_HOUR: u64 = 60 * SECONDS_PER_MINUTE;
 
+fn fmt_bytes(bytes: u64) -> String {
+    byte_unit::Byte::from_u64(bytes)
+        .get_appropriate_unit(byte_unit::UnitType::Binary)
+        .to_string()
+}
+
+fn fmt_std_duration(time: Duration) -> String {
+    fmt_duration(time::Duration::try_from(time).unwrap_or_default())
+}
+
 fn fmt_duration(time: time::Duration) -> String {
     let nanoseconds = time.subsec_nanoseconds();
     let total_seconds = time.whole_seconds();
@@ -1009,6 +6950,28 @@ fn fmt_duration(time: time::Duration) -> String {
     }
 }
 
+fn fmt_time_of_day(time: time::OffsetDateTime) -> String {
+    let (h, m, s, ms) = time.time().as_hms_milli();
+    format!("{h:02}:{m:02}:{s:02}.{ms:03}")
+}
+
+/// Formats the time as an RFC 3339 timestamp with millisecond precision.
+fn fmt_timestamp(time: time::OffsetDateTime) -> String {
+    let (h, m, s, ms) = time.time().as_hms_milli();
+    let (offset_h, offset_m, _) = time.offset().as_hms();
+    format!(
+        "{}T{h:02}:{m:02}:{s:02}.{ms:03}{}{:02}:{:02}",
+        time.date(),
+        if time.offset().is_negative() {
+            '-'
+        } else {
+            '+'
+        },
+        offset_h.unsigned_abs(),
+        offset_m.unsigned_abs(),
+    )
+}
+
 fn timer_state_to_str(state: TimerState) -> &'static str {
     match state {
         TimerState::NotRunning => "Not running",
@@ -1018,9 +6981,88 @@ fn timer_state_to_str(state: TimerState) -> &'static str {
     }
 }
 
+#[derive(Clone, PartialEq)]
 enum LogType {
+    /// Messages about actions of the debugger itself.
+    Debugger(LogLevel),
+    /// Messages and errors emitted by the runtime.
     Runtime(LogLevel),
+    /// Actions that the auto splitter performed on the timer.
+    TimerAction,
+    /// Messages the auto splitter logged itself.
     AutoSplitterMessage,
+    /// The output of building the auto splitter with cargo.
+    Build(LogLevel),
+}
+
+impl LogType {
+    fn level(&self) -> LogLevel {
+        match self {
+            LogType::Debugger(level) | LogType::Runtime(level) => *level,
+            LogType::TimerAction => LogLevel::Debug,
+            LogType::AutoSplitterMessage => LogLevel::Info,
+            LogType::Build(level) => *level,
+        }
+    }
+
+    fn source(&self) -> LogSource {
+        match self {
+            LogType::Debugger(_) => LogSource::Debugger,
+            LogType::Runtime(_) => LogSource::Runtime,
+            LogType::TimerAction => LogSource::Timer,
+            LogType::AutoSplitterMessage => LogSource::AutoSplitter,
+            LogType::Build(_) => LogSource::Build,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum LogSource {
+    Debugger,
+    Runtime,
+    Timer,
+    AutoSplitter,
+    Build,
+}
+
+impl LogSource {
+    fn to_str(self) -> &'static str {
+        match self {
+            LogSource::Debugger => "Debugger",
+            LogSource::Runtime => "Runtime",
+            LogSource::Timer => "Timer",
+            LogSource::AutoSplitter => "Auto Splitter",
+            LogSource::Build => "Build",
+        }
+    }
+}
+
+fn log_level_to_str(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Trace => "Trace",
+        LogLevel::Debug => "Debug",
+        LogLevel::Info => "Info",
+        LogLevel::Warning => "Warning",
+        LogLevel::Error => "Error",
+    }
+}
+
+fn log_level_color(level: LogLevel) -> Color32 {
+    match level {
+        LogLevel::Trace => TRACE_COLOR,
+        LogLevel::Debug => DEBUG_COLOR,
+        LogLevel::Info => INFO_COLOR,
+        LogLevel::Warning => WARN_COLOR,
+        LogLevel::Error => ERROR_COLOR,
+    }
+}
+
+fn severity_color(severity: diagnostics::Severity) -> Color32 {
+    match severity {
+        diagnostics::Severity::Info => INFO_COLOR,
+        diagnostics::Severity::Warning => WARN_COLOR,
+        diagnostics::Severity::Error => ERROR_COLOR,
+    }
 }
 
 struct DebuggerTimerState {
@@ -1028,10 +7070,40 @@ struct DebuggerTimerState {
     game_time: time::Duration,
     game_time_state: GameTimeState,
     split_index: usize,
-    variables: IndexMap<Box<str>, String>,
+    variables: IndexMap<Box<str>, Variable>,
+    break_variables: IndexSet<Box<str>>,
+    /// The timer actions that pause the auto splitter when it performs them.
+    break_events: Vec<TimerEvent>,
+    break_condition: Option<BreakCondition>,
+    /// The calls of the timer's host functions during the last two ticks.
+    host_calls: HostCalls,
+    break_requested: bool,
+    variable_history: IndexMap<Box<str>, VecDeque<variable_history::Sample>>,
+    run_history: RunHistory,
+    /// How often the auto splitter's actions moved the timer and the game
+    /// timer between their states.
+    timer_transitions: Transitions,
+    game_time_transitions: Transitions,
+    suspicious_actions: SuspiciousActions,
+    /// The scenario the timer events are currently checked against.
+    scenario: Option<ScenarioRun>,
+    tick_index: u64,
+    load_time: time::OffsetDateTime,
     time_zone: UtcOffset,
     logs: Vec<LogMessage>,
-    last_logs_len: usize,
+    /// Increases whenever a message gets logged, including when the repeat
+    /// count of the last message increases, so the Logs tab knows when to
+    /// scroll to the end.
+    logs_revision: u64,
+    shown_logs_revision: u64,
+    log_file: Option<LogFile>,
+    deduplicate_logs: bool,
+    /// Whether anything shown in the user interface changed since the
+    /// runtime thread last requested a repaint.
+    changed: bool,
+    /// The events that are yet to be printed by the JSON output of the
+    /// headless mode. They are only collected if that output is used.
+    output_events: Option<Vec<serde_json::Value>>,
 }
 
 impl DebuggerTimerState {
@@ -1042,29 +7114,205 @@ impl DebuggerTimerState {
             game_time_state: Default::default(),
             split_index: Default::default(),
             variables: Default::default(),
+            break_variables: Default::default(),
+            break_events: Vec::new(),
+            break_condition: None,
+            host_calls: HostCalls::default(),
+            break_requested: false,
+            variable_history: Default::default(),
+            run_history: Default::default(),
+            timer_transitions: Default::default(),
+            game_time_transitions: Default::default(),
+            suspicious_actions: Default::default(),
+            scenario: None,
+            tick_index: 0,
+            load_time: time::OffsetDateTime::now_utc().to_offset(time_zone),
             time_zone,
             logs: Default::default(),
-            last_logs_len: Default::default(),
+            logs_revision: 0,
+            shown_logs_revision: 0,
+            log_file: None,
+            deduplicate_logs: true,
+            changed: false,
+            output_events: None,
         }
     }
 
     fn log(&mut self, message: Box<str>, ty: LogType) {
-        let (h, m, s) = time::OffsetDateTime::now_utc()
-            .to_offset(self.time_zone)
-            .time()
-            .as_hms();
-        self.logs.push(LogMessage {
-            time: format!("{h:02}:{m:02}:{s:02}").into(),
+        self.changed = true;
+        self.logs_revision += 1;
+        let log = LogMessage {
+            time: time::OffsetDateTime::now_utc().to_offset(self.time_zone),
+            tick: self.tick_index,
             message,
             ty,
-        });
+            count: 1,
+        };
+        self.output_event("log", || log.to_json());
+        if let Some(log_file) = &self.log_file {
+            log_file.write(log.to_string());
+            if let Some(e) = log_file.take_error() {
+                self.log_file = None;
+                self.logs.push(LogMessage {
+                    time: log.time,
+                    tick: log.tick,
+                    message: format!("Stopped logging to the file: {e}").into(),
+                    ty: LogType::Debugger(LogLevel::Error),
+                    count: 1,
+                });
+            }
+        }
+        if self.deduplicate_logs {
+            if let Some(last) = self.logs.last_mut() {
+                if last.message == log.message && last.ty == log.ty {
+                    last.count += 1;
+                    return;
+                }
+            }
+        }
+        self.logs.push(log);
+    }
+
+    fn record_game_time(&mut self) {
+        let game_time =
+            (self.game_time_state != GameTimeState::NotInitialized).then_some(self.game_time);
+        self.run_history.record_game_time(
+            Instant::now(),
+            game_time,
+            self.game_time_state == GameTimeState::Paused,
+        );
+    }
+
+    fn record_variable_history(&mut self) {
+        if self.variable_history.is_empty() {
+            return;
+        }
+        let time = time::OffsetDateTime::now_utc().to_offset(self.time_zone);
+        for (key, samples) in &mut self.variable_history {
+            if let Some(variable) = self.variables.get(key) {
+                variable_history::record(
+                    samples,
+                    variable_history::Sample {
+                        tick: self.tick_index,
+                        time,
+                        value: variable.value.clone(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn is_log_selected(selection: Option<(usize, usize)>, index: usize) -> bool {
+    selection
+        .is_some_and(|(anchor, cursor)| (anchor.min(cursor)..=anchor.max(cursor)).contains(&index))
+}
+
+struct Variable {
+    value: String,
+    last_changed: Instant,
+    /// The tick in which the value last changed.
+    last_changed_tick: u64,
+}
+
+impl Variable {
+    /// The color of the value, fading from the highlight color back to the
+    /// normal text color after the value changed.
+    fn color(&self, text_color: Color32) -> Color32 {
+        let t = self.last_changed.elapsed().as_secs_f32() / CHANGED_FADE_DURATION.as_secs_f32();
+        if t >= 1.0 {
+            text_color
+        } else {
+            CHANGED_COLOR.lerp_to_gamma(text_color, t)
+        }
     }
 }
 
+#[derive(Clone)]
 struct LogMessage {
-    time: Box<str>,
+    time: time::OffsetDateTime,
+    tick: u64,
     message: Box<str>,
     ty: LogType,
+    /// How often the message got logged in a row.
+    count: u32,
+}
+
+impl LogMessage {
+    fn to_session_log(&self) -> session::Log {
+        session::Log {
+            time: (self.time.unix_timestamp_nanos() / 1_000_000) as i64,
+            tick: self.tick,
+            level: log_level_to_str(self.ty.level()).into(),
+            source: self.ty.source().to_str().into(),
+            message: self.message.clone(),
+            count: self.count,
+        }
+    }
+
+    fn from_session_log(log: &session::Log, time_zone: UtcOffset) -> Option<Self> {
+        let level = [
+            LogLevel::Trace,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warning,
+            LogLevel::Error,
+        ]
+        .into_iter()
+        .find(|&level| log_level_to_str(level) == &*log.level)?;
+        let ty = match &*log.source {
+            "Debugger" => LogType::Debugger(level),
+            "Runtime" => LogType::Runtime(level),
+            "Timer" => LogType::TimerAction,
+            "Auto Splitter" => LogType::AutoSplitterMessage,
+            "Build" => LogType::Build(level),
+            _ => return None,
+        };
+        Some(Self {
+            time: time::OffsetDateTime::from_unix_timestamp_nanos(log.time as i128 * 1_000_000)
+                .ok()?
+                .to_offset(time_zone),
+            tick: log.tick,
+            message: log.message.clone(),
+            ty,
+            count: log.count,
+        })
+    }
+
+    fn write_json_line(&self, mut writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(&mut writer, &self.to_json())?;
+        writeln!(writer)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "timestamp": fmt_timestamp(self.time),
+            "tick": self.tick,
+            "level": log_level_to_str(self.ty.level()),
+            "source": self.ty.source().to_str(),
+            "message": &*self.message,
+            "count": self.count,
+        })
+    }
+}
+
+impl fmt::Display for LogMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} [{}] {} {}: {}",
+            self.time.date(),
+            fmt_time_of_day(self.time),
+            self.tick,
+            log_level_to_str(self.ty.level()),
+            self.ty.source().to_str(),
+            self.message,
+        )?;
+        if self.count > 1 {
+            write!(f, " (×{})", self.count)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Copy, Clone, Default, PartialEq)]
@@ -1085,6 +7333,23 @@ impl GameTimeState {
     }
 }
 
+impl break_condition::Scope for DebuggerTimerState {
+    fn field(&self, field: break_condition::Field) -> break_condition::Value {
+        use break_condition::{Field, Value};
+        match field {
+            Field::SplitIndex => Value::Number(self.split_index as f64),
+            Field::Tick => Value::Number(self.tick_index as f64),
+            Field::GameTime => Value::Number(self.game_time.as_seconds_f64()),
+            Field::TimerState => Value::String(timer_state_to_str(self.timer_state).into()),
+            Field::GameTimeState => Value::String(self.game_time_state.to_str().into()),
+        }
+    }
+
+    fn variable(&self, key: &str) -> Option<&str> {
+        self.variables.get(key).map(|variable| &*variable.value)
+    }
+}
+
 #[derive(Clone)]
 struct DebuggerTimer(Arc<RwLock<DebuggerTimerState>>);
 
@@ -1096,81 +7361,192 @@ impl DebuggerTimer {
 
 impl Timer for DebuggerTimer {
     fn state(&self) -> TimerState {
-        self.0.read().unwrap().timer_state
+        let mut state = self.0.write().unwrap();
+        let timer_state = state.timer_state;
+        state.host_calls.record(
+            "timer_get_state",
+            None,
+            timer_state_to_str(timer_state).into(),
+        );
+        timer_state
     }
 
     fn start(&mut self) {
         let mut state = self.0.write().unwrap();
+        state.host_calls.record("timer_start", None, String::new());
+        let from = state.timer_state;
+        state.check_suspicious(|actions, tick| actions.start(tick, from));
         if state.timer_state == TimerState::NotRunning {
             state.start();
-            state.log("Timer started.".into(), LogType::Runtime(LogLevel::Debug));
+            state.record_event(TimerEvent::Start);
+            state.log("Timer started.".into(), LogType::TimerAction);
+            state.break_on(TimerEvent::Start);
         }
+        state.record_timer_transition(from, "start");
     }
 
     fn split(&mut self) {
         let mut state = self.0.write().unwrap();
+        state.host_calls.record("timer_split", None, String::new());
+        let from = state.timer_state;
+        state.check_suspicious(|actions, tick| actions.split(tick, from));
         if state.timer_state == TimerState::Running {
             state.split_index += 1;
-            state.log("Splitted.".into(), LogType::Runtime(LogLevel::Debug));
+            state.record_split(false);
+            state.record_event(TimerEvent::Split);
+            state.log("Splitted.".into(), LogType::TimerAction);
+            state.break_on(TimerEvent::Split);
         }
+        state.record_timer_transition(from, "split");
     }
 
     fn skip_split(&mut self) {
         let mut state = self.0.write().unwrap();
+        state
+            .host_calls
+            .record("timer_skip_split", None, String::new());
+        let from = state.timer_state;
+        state.check_suspicious(|actions, tick| actions.split(tick, from));
         if state.timer_state == TimerState::Running {
             state.split_index += 1;
-            state.log("Split skipped.".into(), LogType::Runtime(LogLevel::Debug));
+            state.record_split(true);
+            state.record_event(TimerEvent::SkipSplit);
+            state.log("Split skipped.".into(), LogType::TimerAction);
+            state.break_on(TimerEvent::SkipSplit);
         }
+        state.record_timer_transition(from, "skip_split");
     }
 
     fn undo_split(&mut self) {
         let mut state = self.0.write().unwrap();
+        state
+            .host_calls
+            .record("timer_undo_split", None, String::new());
+        let from = state.timer_state;
+        let split_index = state.split_index;
+        state.check_suspicious(|actions, tick| actions.undo_split(tick, from, split_index));
         if state.timer_state == TimerState::Ended {
             state.timer_state = TimerState::Running;
         }
         if state.timer_state == TimerState::Running {
             state.split_index = state.split_index.saturating_sub(1);
-            state.log("Split undone.".into(), LogType::Runtime(LogLevel::Debug));
+            state.run_history.undo_split();
+            state.record_event(TimerEvent::UndoSplit);
+            state.log("Split undone.".into(), LogType::TimerAction);
+            state.break_on(TimerEvent::UndoSplit);
         }
+        state.record_timer_transition(from, "undo_split");
     }
 
     fn reset(&mut self) {
         let mut state = self.0.write().unwrap();
+        state.host_calls.record("timer_reset", None, String::new());
+        let (from, from_game_time) = (state.timer_state, state.game_time_state);
+        let tick = state.tick_index;
+        state.suspicious_actions.reset(tick, from);
         state.reset();
-        state.log("Run reset.".into(), LogType::Runtime(LogLevel::Debug));
+        state.record_event(TimerEvent::Reset);
+        state.log("Run reset.".into(), LogType::TimerAction);
+        state.break_on(TimerEvent::Reset);
+        state.record_timer_transition(from, "reset");
+        state.record_game_time_transition(from_game_time, "reset");
     }
 
     fn set_game_time(&mut self, time: time::Duration) {
         let mut state = self.0.write().unwrap();
-        state.game_time = time;
+        state
+            .host_calls
+            .record("timer_set_game_time", None, fmt_duration(time));
+        let before =
+            (state.game_time_state != GameTimeState::NotInitialized).then_some(state.game_time);
+        state.check_suspicious(|actions, tick| actions.set_game_time(tick, before, time));
+        if state.game_time != time {
+            state.game_time = time;
+            state.changed = true;
+        }
         if state.game_time_state == GameTimeState::NotInitialized {
             state.game_time_state = GameTimeState::Running;
+            // Setting the game time every tick is expected, so only the
+            // initialization counts as a transition.
+            state.record_game_time_transition(GameTimeState::NotInitialized, "set_game_time");
         }
     }
 
     fn pause_game_time(&mut self) {
-        self.0.write().unwrap().game_time_state = GameTimeState::Paused;
+        let mut state = self.0.write().unwrap();
+        state
+            .host_calls
+            .record("timer_pause_game_time", None, String::new());
+        let from = state.game_time_state;
+        state.game_time_state = GameTimeState::Paused;
+        state.changed = true;
+        state.record_game_time_transition(from, "pause_game_time");
     }
 
     fn resume_game_time(&mut self) {
-        self.0.write().unwrap().game_time_state = GameTimeState::Running;
+        let mut state = self.0.write().unwrap();
+        state
+            .host_calls
+            .record("timer_resume_game_time", None, String::new());
+        let from = state.game_time_state;
+        state.game_time_state = GameTimeState::Running;
+        state.changed = true;
+        state.record_game_time_transition(from, "resume_game_time");
     }
 
     fn set_variable(&mut self, key: &str, value: &str) {
         let mut guard = self.0.write().unwrap();
-        let s = guard.variables.entry(key.into()).or_default();
-        s.clear();
-        s.push_str(value);
+        let state = &mut *guard;
+        state.host_calls.record(
+            "timer_set_variable",
+            Some(key),
+            format!("{key:?}, {value:?}"),
+        );
+        let old_value = if let Some(variable) = state.variables.get_mut(key) {
+            if variable.value == value {
+                return;
+            }
+            state.changed = true;
+            variable.last_changed = Instant::now();
+            variable.last_changed_tick = state.tick_index;
+            Some(mem::replace(&mut variable.value, value.into()))
+        } else {
+            state.variables.insert(
+                key.into(),
+                Variable {
+                    value: value.into(),
+                    last_changed: Instant::now(),
+                    last_changed_tick: state.tick_index,
+                },
+            );
+            state.changed = true;
+            None
+        };
+
+        if state.break_variables.contains(key) {
+            state.break_requested = true;
+            let message = format!(
+                "Break at tick {}: Variable \"{key}\" changed from {} to \"{value}\".",
+                state.tick_index,
+                match &old_value {
+                    Some(old_value) => format!("\"{old_value}\""),
+                    None => "<Not set>".into(),
+                },
+            );
+            state.log(message.into(), LogType::Debugger(LogLevel::Info));
+        }
     }
 
     fn log_auto_splitter(&mut self, message: std::fmt::Arguments<'_>) {
-        self.0.write().unwrap().log(
-            match message.as_str() {
-                Some(m) => m.into(),
-                None => message.to_string().into(),
-            },
-            LogType::AutoSplitterMessage,
-        );
+        let message: Box<str> = match message.as_str() {
+            Some(m) => m.into(),
+            None => message.to_string().into(),
+        };
+        let mut state = self.0.write().unwrap();
+        state
+            .host_calls
+            .record("runtime_print_message", None, format!("{message:?}"));
+        state.log(message, LogType::AutoSplitterMessage);
     }
 
     fn log_runtime(&mut self, message: std::fmt::Arguments<'_>, log_level: LogLevel) {
@@ -1188,10 +7564,122 @@ impl DebuggerTimerState {
     fn start(&mut self) {
         if self.timer_state == TimerState::NotRunning {
             self.timer_state = TimerState::Running;
+            self.run_history.start(Instant::now());
+        }
+    }
+
+    fn record_event(&mut self, event: TimerEvent) {
+        let game_time =
+            (self.game_time_state != GameTimeState::NotInitialized).then_some(self.game_time);
+        if let Some(scenario) = &mut self.scenario {
+            scenario.record(self.tick_index, event, game_time);
+        }
+        self.output_event("timer", || {
+            serde_json::json!({
+                "action": event,
+                "game_time": game_time.map(time::Duration::as_seconds_f64),
+            })
+        });
+    }
+
+    /// Queues an event for the JSON output, if it's used. The fields are
+    /// added to the type of the event, the time and the tick.
+    fn output_event(&mut self, event: &str, fields: impl FnOnce() -> serde_json::Value) {
+        let Some(events) = &mut self.output_events else {
+            return;
+        };
+        let time = time::OffsetDateTime::now_utc().to_offset(self.time_zone);
+        let mut json = serde_json::Map::new();
+        json.insert("event".into(), event.into());
+        json.insert("timestamp".into(), fmt_timestamp(time).into());
+        json.insert("tick".into(), self.tick_index.into());
+        if let serde_json::Value::Object(fields) = fields() {
+            json.extend(fields);
+        }
+        events.push(json.into());
+    }
+
+    fn break_on(&mut self, event: TimerEvent) {
+        if self.break_events.contains(&event) {
+            self.break_requested = true;
+            let message = format!(
+                "Break at tick {}: The auto splitter performed the timer action \"{}\".",
+                self.tick_index,
+                event.to_str(),
+            );
+            self.log(message.into(), LogType::Debugger(LogLevel::Info));
+        }
+    }
+
+    /// Performs a timer action on behalf of the user, like the ones of a
+    /// timer script. Unlike the ones of the auto splitter, they are neither
+    /// checked against the scenario nor traced.
+    fn perform_user_action(&mut self, command: &timer_script::Command) {
+        use timer_script::Command;
+        match command {
+            Command::Start => self.start(),
+            Command::Split | Command::SkipSplit => {
+                if self.timer_state == TimerState::Running {
+                    self.split_index += 1;
+                    self.record_split(*command == Command::SkipSplit);
+                }
+            }
+            Command::UndoSplit => {
+                if self.timer_state == TimerState::Ended {
+                    self.timer_state = TimerState::Running;
+                }
+                if self.timer_state == TimerState::Running {
+                    self.split_index = self.split_index.saturating_sub(1);
+                    self.run_history.undo_split();
+                }
+            }
+            Command::Reset => self.reset(),
+            Command::PauseGameTime => self.game_time_state = GameTimeState::Paused,
+            Command::ResumeGameTime => self.game_time_state = GameTimeState::Running,
+            Command::SetGameTime(time) => {
+                self.game_time = *time;
+                if self.game_time_state == GameTimeState::NotInitialized {
+                    self.game_time_state = GameTimeState::Running;
+                }
+            }
+            Command::Wait(_) => return,
+        }
+        self.changed = true;
+        if let Some(run) = &mut self.scenario {
+            run.record_input(
+                self.tick_index,
+                scenario::InputKind::Timer(command.to_string()),
+            );
         }
     }
 
+    /// Sets a variable on behalf of the user. Unlike the auto splitter
+    /// setting it, this doesn't trigger the breakpoint of the variable.
+    fn set_variable_by_hand(&mut self, key: &str, value: &str) {
+        self.variables.insert(
+            key.into(),
+            Variable {
+                value: value.into(),
+                last_changed: Instant::now(),
+                last_changed_tick: self.tick_index,
+            },
+        );
+        self.changed = true;
+        self.log(
+            format!("Variable \"{key}\" set to \"{value}\" by hand.").into(),
+            LogType::Debugger(LogLevel::Info),
+        );
+    }
+
+    fn record_split(&mut self, skipped: bool) {
+        let game_time =
+            (self.game_time_state != GameTimeState::NotInitialized).then_some(self.game_time);
+        self.run_history
+            .split(Instant::now(), self.tick_index, game_time, skipped);
+    }
+
     fn reset(&mut self) {
+        self.run_history.reset(Instant::now());
         self.timer_state = TimerState::NotRunning;
         self.split_index = 0;
         self.game_time = time::Duration::ZERO;
@@ -1201,5 +7689,34 @@ impl DebuggerTimerState {
 
     fn clear(&mut self) {
         self.reset();
+        self.variable_history.values_mut().for_each(VecDeque::clear);
+        self.run_history.clear();
+        self.timer_transitions.edges.clear();
+        self.game_time_transitions.edges.clear();
+        self.suspicious_actions.clear();
+    }
+
+    /// Logs a warning if the check detects a suspicious timer action.
+    fn check_suspicious(
+        &mut self,
+        check: impl FnOnce(&mut SuspiciousActions, u64) -> Option<suspicious_actions::Pattern>,
+    ) {
+        if let Some(pattern) = check(&mut self.suspicious_actions, self.tick_index) {
+            self.log(
+                format!("Suspicious timer action: {}", pattern.description()).into(),
+                LogType::Debugger(LogLevel::Warning),
+            );
+        }
+    }
+
+    fn record_timer_transition(&mut self, from: TimerState, action: &'static str) {
+        let to = timer_state_to_str(self.timer_state);
+        self.timer_transitions
+            .record(timer_state_to_str(from), action, to);
+    }
+
+    fn record_game_time_transition(&mut self, from: GameTimeState, action: &'static str) {
+        let to = self.game_time_state.to_str();
+        self.game_time_transitions.record(from.to_str(), action, to);
     }
 }
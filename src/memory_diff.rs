@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+/// Differences that are at most this many bytes apart are merged into a
+/// single range, so a changed struct doesn't show up as dozens of ranges.
+const MERGE_DISTANCE: usize = 8;
+/// Diffing stops after this many ranges, as the dumps are unlikely to be of
+/// the same memory at that point.
+const MAX_RANGES: usize = 10_000;
+
+/// A range of bytes that differs between the two dumps.
+#[derive(Debug, PartialEq)]
+pub struct Range {
+    pub offset: usize,
+    pub len: usize,
+}
+
+/// Two memory dumps and the ranges in which they differ.
+#[derive(Default)]
+pub struct MemoryDiff {
+    pub dumps: [Option<(PathBuf, Box<[u8]>)>; 2],
+    pub ranges: Vec<Range>,
+    /// The difference that is shown in the hex view.
+    pub selected: Option<usize>,
+}
+
+impl MemoryDiff {
+    pub fn set_dump(&mut self, index: usize, path: PathBuf, data: Box<[u8]>) {
+        self.dumps[index] = Some((path, data));
+        self.selected = None;
+        self.ranges = match &self.dumps {
+            [Some((_, before)), Some((_, after))] => diff(before, after),
+            _ => Vec::new(),
+        };
+    }
+
+    /// The amount of bytes that differ, including the ones that only one of
+    /// the dumps has.
+    pub fn differing_bytes(&self) -> usize {
+        self.ranges.iter().map(|range| range.len).sum()
+    }
+}
+
+pub fn diff(before: &[u8], after: &[u8]) -> Vec<Range> {
+    let mut ranges = Vec::<Range>::new();
+    let len = before.len().max(after.len());
+    for offset in 0..len {
+        if before.get(offset) == after.get(offset) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if offset - (last.offset + last.len) < MERGE_DISTANCE => {
+                last.len = offset + 1 - last.offset;
+            }
+            _ => {
+                if ranges.len() == MAX_RANGES {
+                    break;
+                }
+                ranges.push(Range { offset, len: 1 });
+            }
+        }
+    }
+    ranges
+}
+
+/// Formats the bytes as hexadecimal, with gaps for the bytes that are out of
+/// bounds.
+pub fn hex(data: &[u8], offset: usize, len: usize) -> String {
+    (offset..offset + len)
+        .map(|i| {
+            data.get(i)
+                .map_or_else(|| "  ".into(), |b| format!("{b:02X}"))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let before = [0u8; 64];
+        let mut after = [0u8; 70];
+        after[2] = 1;
+        after[5] = 1;
+        after[40] = 1;
+
+        assert_eq!(
+            diff(&before, &after),
+            [
+                Range { offset: 2, len: 4 },
+                Range { offset: 40, len: 1 },
+                Range { offset: 64, len: 6 },
+            ],
+        );
+        assert!(diff(&before, &before).is_empty());
+        assert_eq!(hex(&[0xAB, 0x01], 1, 2), "01   ");
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use arc_swap::ArcSwap;
+
+/// The parts of the auto splitter's memory the Memory tab shows. The UI
+/// requests the regions it needs and the runtime thread copies them after
+/// each tick while it holds the auto splitter anyway, so the UI never has to
+/// wait for an update to finish.
+#[derive(Default)]
+pub struct MemoryView {
+    /// The requested regions and whether they changed since they were last
+    /// captured.
+    requested: Mutex<(Vec<Range<usize>>, bool)>,
+    snapshot: ArcSwap<Snapshot>,
+}
+
+/// The copies of the requested regions at the time they were captured.
+#[derive(Default)]
+pub struct Snapshot {
+    regions: Vec<(Range<usize>, Box<[u8]>)>,
+}
+
+impl MemoryView {
+    /// Requests the regions to be captured from now on.
+    pub fn request(&self, regions: impl IntoIterator<Item = Range<usize>>) {
+        let regions = regions.into_iter().collect::<Vec<_>>();
+        let mut requested = self.requested.lock().unwrap();
+        if requested.0 != regions {
+            *requested = (regions, true);
+        }
+    }
+
+    /// Whether the regions changed since they were last captured. While the
+    /// auto splitter is paused, this is the only reason to capture them.
+    pub fn is_stale(&self) -> bool {
+        self.requested.lock().unwrap().1
+    }
+
+    /// Drops the captured memory, as it belongs to an auto splitter that is
+    /// no longer loaded.
+    pub fn invalidate(&self) {
+        self.requested.lock().unwrap().1 = true;
+        self.snapshot.store(Default::default());
+    }
+
+    /// Copies the requested regions out of the memory. Regions that are out of
+    /// bounds are cut off.
+    pub fn capture(&self, memory: &[u8]) {
+        let regions = {
+            let mut requested = self.requested.lock().unwrap();
+            requested.1 = false;
+            requested.0.clone()
+        };
+        let regions = regions
+            .into_iter()
+            .map(|range| {
+                let range = range.start.min(memory.len())..range.end.min(memory.len());
+                let bytes = memory[range.clone()].into();
+                (range, bytes)
+            })
+            .collect();
+        self.snapshot.store(Arc::new(Snapshot { regions }));
+    }
+
+    pub fn snapshot(&self) -> Arc<Snapshot> {
+        self.snapshot.load_full()
+    }
+}
+
+impl Snapshot {
+    /// The bytes of the range, if a captured region contains all of it.
+    pub fn get(&self, range: Range<usize>) -> Option<&[u8]> {
+        self.regions.iter().find_map(|(region, bytes)| {
+            (region.start <= range.start && range.end <= region.end)
+                .then(|| &bytes[range.start - region.start..range.end - region.start])
+        })
+    }
+
+    /// The bytes of the range that were captured, which may be cut off at the
+    /// end of the memory.
+    pub fn get_prefix(&self, range: Range<usize>) -> Option<&[u8]> {
+        self.regions.iter().find_map(|(region, bytes)| {
+            (region.start <= range.start && range.start < region.end).then(|| {
+                &bytes[range.start - region.start..range.end.min(region.end) - region.start]
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        let view = MemoryView::default();
+        let memory = (0..32).collect::<Vec<u8>>();
+        view.request([4..8, 16..48]);
+        assert!(view.is_stale());
+        view.capture(&memory);
+        assert!(!view.is_stale());
+
+        let snapshot = view.snapshot();
+        assert_eq!(snapshot.get(5..7), Some(&[5, 6][..]));
+        assert_eq!(snapshot.get(6..10), None);
+        assert_eq!(snapshot.get(30..34), None);
+        assert_eq!(snapshot.get_prefix(5..7), Some(&[5, 6][..]));
+        assert_eq!(snapshot.get_prefix(30..34), Some(&[30, 31][..]));
+        assert_eq!(snapshot.get_prefix(8..12), None);
+
+        view.request([4..8, 16..48]);
+        assert!(!view.is_stale());
+        view.invalidate();
+        assert!(view.is_stale());
+        assert_eq!(view.snapshot().get_prefix(5..7), None);
+    }
+}
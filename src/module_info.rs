@@ -0,0 +1,711 @@
+use indexmap::IndexMap;
+use wasmparser::{ExternalKind, GlobalType, KnownCustom, Name, Operator, Parser, Payload, TypeRef};
+
+/// Static information about the WebAssembly module of an auto splitter, parsed
+/// directly from its binary.
+pub struct ModuleInfo {
+    pub size: usize,
+    pub defined_functions: u32,
+    /// The imports, grouped by the host module they are imported from.
+    pub imports: IndexMap<Box<str>, Vec<Item>>,
+    pub exports: Vec<Item>,
+    pub memories: Vec<Limits>,
+    pub tables: Vec<Limits>,
+    /// The globals, including the imported ones, in the order of their index.
+    pub globals: Vec<Global>,
+    pub custom_sections: Vec<Section>,
+    pub sections: Vec<Section>,
+    /// The sizes of the data segments, in the order they are defined in.
+    pub data_segments: Vec<usize>,
+    /// The functions defined by the module itself, in the order of their
+    /// index. The imported functions come before them in the index space.
+    pub functions: Vec<Function>,
+    pub imported_functions: u32,
+    /// Names of executables that appear in the data of the module. These are
+    /// likely the processes the auto splitter attaches to.
+    pub process_names: Vec<Box<str>>,
+    /// The tools that produced the module, as listed in its producers
+    /// section.
+    pub producers: Vec<Producer>,
+    /// The version of the asr crate the auto splitter got built with, if it
+    /// appears in the data of the module.
+    pub asr_version: Option<Box<str>>,
+}
+
+pub struct Item {
+    pub name: Box<str>,
+    pub kind: &'static str,
+}
+
+pub struct Function {
+    /// The name of the function if the module has a name section that names
+    /// it. Mangled Rust names are demangled.
+    pub name: Option<Box<str>>,
+    /// The size of the function's body in the code section.
+    pub size: usize,
+}
+
+pub struct Limits {
+    pub initial: u64,
+    pub maximum: Option<u64>,
+    pub is_64: bool,
+    pub imported: bool,
+}
+
+pub struct Global {
+    /// The name of the global if the module has a name section that names it.
+    pub name: Option<Box<str>>,
+    pub ty: Box<str>,
+    pub mutable: bool,
+    pub imported: bool,
+    /// The value the global is initialized with if it's a simple constant.
+    pub initial_value: Option<Box<str>>,
+}
+
+impl Global {
+    fn new(ty: GlobalType, imported: bool, initial_value: Option<Box<str>>) -> Self {
+        Self {
+            name: None,
+            ty: ty.content_type.to_string().into(),
+            mutable: ty.mutable,
+            imported,
+            initial_value,
+        }
+    }
+}
+
+/// A section of the module. The size covers the contents of the section,
+/// without the id and the length in front of it. In `sections` the contents of
+/// custom sections include their name, while `custom_sections` only counts
+/// their data.
+pub struct Section {
+    pub name: Box<str>,
+    pub size: usize,
+}
+
+/// A tool that was involved in producing the module.
+pub struct Producer {
+    /// The kind of tool, which is either "language", "processed-by" or "sdk".
+    pub field: Box<str>,
+    pub name: Box<str>,
+    pub version: Box<str>,
+}
+
+/// The host module that provides the functions of the auto splitting runtime.
+pub const RUNTIME_MODULE: &str = "env";
+
+/// The host module that provides the WASI functions. The runtime links the
+/// entirety of it, so any function imported from it is available.
+pub const WASI_MODULE: &str = "wasi_snapshot_preview1";
+
+/// All the functions the runtime is known to provide to auto splitters. The
+/// runtime doesn't expose the functions it links, so this list is kept by hand
+/// and may lag behind the runtime the debugger got built with.
+pub const RUNTIME_FUNCTIONS: &[&str] = &[
+    "timer_get_state",
+    "timer_start",
+    "timer_split",
+    "timer_skip_split",
+    "timer_undo_split",
+    "timer_reset",
+    "timer_set_variable",
+    "timer_set_game_time",
+    "timer_pause_game_time",
+    "timer_resume_game_time",
+    "runtime_set_tick_rate",
+    "runtime_print_message",
+    "runtime_get_os",
+    "runtime_get_arch",
+    "process_attach",
+    "process_attach_by_pid",
+    "process_detach",
+    "process_list_by_name",
+    "process_is_open",
+    "process_read",
+    "process_get_module_address",
+    "process_get_module_size",
+    "process_get_module_path",
+    "process_get_path",
+    "process_get_memory_range_count",
+    "process_get_memory_range_address",
+    "process_get_memory_range_size",
+    "process_get_memory_range_flags",
+    "user_settings_add_bool",
+    "user_settings_add_title",
+    "user_settings_add_choice",
+    "user_settings_add_choice_option",
+    "user_settings_add_file_select",
+    "user_settings_add_file_select_name_filter",
+    "user_settings_add_file_select_mime_filter",
+    "user_settings_set_tooltip",
+    "settings_map_new",
+    "settings_map_free",
+    "settings_map_load",
+    "settings_map_store",
+    "settings_map_store_if_unchanged",
+    "settings_map_copy",
+    "settings_map_insert",
+    "settings_map_get",
+    "settings_map_len",
+    "settings_map_get_key_by_index",
+    "settings_map_get_value_by_index",
+    "settings_list_new",
+    "settings_list_free",
+    "settings_list_copy",
+    "settings_list_len",
+    "settings_list_get",
+    "settings_list_push",
+    "settings_list_insert",
+    "setting_value_new_map",
+    "setting_value_new_list",
+    "setting_value_new_bool",
+    "setting_value_new_i64",
+    "setting_value_new_f64",
+    "setting_value_new_string",
+    "setting_value_free",
+    "setting_value_copy",
+    "setting_value_get_type",
+    "setting_value_get_map",
+    "setting_value_get_list",
+    "setting_value_get_bool",
+    "setting_value_get_i64",
+    "setting_value_get_f64",
+    "setting_value_get_string",
+];
+
+/// The parts of asr that wrap the functions of the runtime, by the prefixes of
+/// the names of the functions. The more specific prefixes come first.
+pub const ASR_APIS: &[(&str, &str)] = &[
+    ("process_get_memory_range_", "asr::Process::memory_ranges"),
+    ("process_", "asr::Process"),
+    ("timer_", "asr::timer"),
+    ("runtime_", "the functions at the root of asr"),
+    ("user_settings_", "asr::settings::gui"),
+    ("settings_map_", "asr::settings::Map"),
+    ("settings_list_", "asr::settings::List"),
+    ("setting_value_", "asr::settings::Value"),
+];
+
+/// A functional area of the runtime an auto splitter can make use of.
+pub struct Capability {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The host module and the prefixes of the names of the imports that
+    /// indicate the usage of the capability.
+    imports: &'static [(&'static str, &'static str)],
+}
+
+pub const CAPABILITIES: &[Capability] = &[
+    Capability {
+        name: "Timer",
+        description: "Controls the timer, such as starting, splitting or resetting it.",
+        imports: &[
+            (RUNTIME_MODULE, "timer_start"),
+            (RUNTIME_MODULE, "timer_split"),
+            (RUNTIME_MODULE, "timer_skip_split"),
+            (RUNTIME_MODULE, "timer_undo_split"),
+            (RUNTIME_MODULE, "timer_reset"),
+        ],
+    },
+    Capability {
+        name: "Game Time",
+        description: "Sets or pauses the game time.",
+        imports: &[
+            (RUNTIME_MODULE, "timer_set_game_time"),
+            (RUNTIME_MODULE, "timer_pause_game_time"),
+            (RUNTIME_MODULE, "timer_resume_game_time"),
+        ],
+    },
+    Capability {
+        name: "Process Memory",
+        description: "Attaches to processes and reads their memory.",
+        imports: &[(RUNTIME_MODULE, "process_")],
+    },
+    Capability {
+        name: "Sigscan",
+        description: "Queries the memory ranges of processes, which is what signature scanning across entire processes is based on.",
+        imports: &[(RUNTIME_MODULE, "process_get_memory_range_")],
+    },
+    Capability {
+        name: "Settings GUI",
+        description: "Registers settings that the user can change.",
+        imports: &[(RUNTIME_MODULE, "user_settings_")],
+    },
+    Capability {
+        name: "Settings Maps",
+        description: "Directly accesses and modifies the settings maps.",
+        imports: &[
+            (RUNTIME_MODULE, "settings_map_"),
+            (RUNTIME_MODULE, "settings_list_"),
+            (RUNTIME_MODULE, "setting_value_"),
+        ],
+    },
+    Capability {
+        name: "File Access",
+        description: "Accesses files through WASI.",
+        imports: &[(WASI_MODULE, "path_"), (WASI_MODULE, "fd_")],
+    },
+];
+
+impl ModuleInfo {
+    pub fn parse(data: &[u8]) -> wasmparser::Result<Self> {
+        let mut info = ModuleInfo {
+            size: data.len(),
+            defined_functions: 0,
+            imports: IndexMap::new(),
+            exports: Vec::new(),
+            memories: Vec::new(),
+            tables: Vec::new(),
+            globals: Vec::new(),
+            custom_sections: Vec::new(),
+            sections: Vec::new(),
+            data_segments: Vec::new(),
+            functions: Vec::new(),
+            imported_functions: 0,
+            process_names: Vec::new(),
+            producers: Vec::new(),
+            asr_version: None,
+        };
+
+        for payload in Parser::new(0).parse_all(data) {
+            let payload = payload?;
+
+            if let Some((id, range)) = payload.as_section() {
+                let name: Box<str> = match &payload {
+                    Payload::CustomSection(reader) => {
+                        format!("custom \"{}\"", reader.name()).into()
+                    }
+                    _ => section_name(id).into(),
+                };
+                info.sections.push(Section {
+                    name,
+                    size: range.len(),
+                });
+            }
+
+            match payload {
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import?;
+                        match import.ty {
+                            TypeRef::Memory(ty) => info.memories.push(Limits {
+                                initial: ty.initial,
+                                maximum: ty.maximum,
+                                is_64: ty.memory64,
+                                imported: true,
+                            }),
+                            TypeRef::Table(ty) => info.tables.push(Limits {
+                                initial: ty.initial,
+                                maximum: ty.maximum,
+                                is_64: ty.table64,
+                                imported: true,
+                            }),
+                            TypeRef::Global(ty) => info.globals.push(Global::new(ty, true, None)),
+                            TypeRef::Func(_) => info.imported_functions += 1,
+                            _ => {}
+                        }
+                        info.imports
+                            .entry(import.module.into())
+                            .or_default()
+                            .push(Item {
+                                name: import.name.into(),
+                                kind: type_ref_kind(&import.ty),
+                            });
+                    }
+                }
+                Payload::FunctionSection(reader) => {
+                    info.defined_functions = reader.count();
+                }
+                Payload::CodeSectionEntry(body) => {
+                    info.functions.push(Function {
+                        name: None,
+                        size: body.range().len(),
+                    });
+                }
+                Payload::MemorySection(reader) => {
+                    for ty in reader {
+                        let ty = ty?;
+                        info.memories.push(Limits {
+                            initial: ty.initial,
+                            maximum: ty.maximum,
+                            is_64: ty.memory64,
+                            imported: false,
+                        });
+                    }
+                }
+                Payload::TableSection(reader) => {
+                    for table in reader {
+                        let ty = table?.ty;
+                        info.tables.push(Limits {
+                            initial: ty.initial,
+                            maximum: ty.maximum,
+                            is_64: ty.table64,
+                            imported: false,
+                        });
+                    }
+                }
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export?;
+                        info.exports.push(Item {
+                            name: export.name.into(),
+                            kind: external_kind(export.kind),
+                        });
+                    }
+                }
+                Payload::GlobalSection(reader) => {
+                    for global in reader {
+                        let global = global?;
+                        let initial_value = match global.init_expr.get_operators_reader().read()? {
+                            Operator::I32Const { value } => Some(value.to_string()),
+                            Operator::I64Const { value } => Some(value.to_string()),
+                            Operator::F32Const { value } => {
+                                Some(f32::from_bits(value.bits()).to_string())
+                            }
+                            Operator::F64Const { value } => {
+                                Some(f64::from_bits(value.bits()).to_string())
+                            }
+                            _ => None,
+                        };
+                        info.globals.push(Global::new(
+                            global.ty,
+                            false,
+                            initial_value.map(Into::into),
+                        ));
+                    }
+                }
+                Payload::DataSection(reader) => {
+                    for data in reader {
+                        let data = data?.data;
+                        info.data_segments.push(data.len());
+                        for name in executable_names(data) {
+                            if !info.process_names.contains(&name) {
+                                info.process_names.push(name);
+                            }
+                        }
+                        if info.asr_version.is_none() {
+                            info.asr_version = asr_version(data);
+                        }
+                    }
+                }
+                Payload::CustomSection(reader) => {
+                    info.custom_sections.push(Section {
+                        name: reader.name().into(),
+                        size: reader.data().len(),
+                    });
+                    // The custom sections are purely informational, so them
+                    // being malformed is not a reason to reject the module.
+                    match reader.as_known() {
+                        KnownCustom::Name(names) => {
+                            for name in names.into_iter().flatten() {
+                                match name {
+                                    Name::Global(map) => {
+                                        for naming in map.into_iter().flatten() {
+                                            if let Some(global) =
+                                                info.globals.get_mut(naming.index as usize)
+                                            {
+                                                global.name = Some(naming.name.into());
+                                            }
+                                        }
+                                    }
+                                    Name::Function(map) => {
+                                        for naming in map.into_iter().flatten() {
+                                            let function = naming
+                                                .index
+                                                .checked_sub(info.imported_functions)
+                                                .and_then(|index| {
+                                                    info.functions.get_mut(index as usize)
+                                                });
+                                            if let Some(function) = function {
+                                                function.name = Some(demangle(naming.name));
+                                            }
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        KnownCustom::Producers(fields) => {
+                            for field in fields.into_iter().flatten() {
+                                for value in field.values.into_iter().flatten() {
+                                    info.producers.push(Producer {
+                                        field: field.name.into(),
+                                        name: value.name.into(),
+                                        version: value.version.into(),
+                                    });
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// Whether any of the imports of the module indicate that it makes use of
+    /// the capability.
+    pub fn uses(&self, capability: &Capability) -> bool {
+        capability.imports.iter().any(|(module, prefix)| {
+            self.imports
+                .get(*module)
+                .is_some_and(|imports| imports.iter().any(|i| i.name.starts_with(prefix)))
+        })
+    }
+
+    /// The imports the runtime is not known to provide, along with the host
+    /// module they are imported from. Instantiating the module likely fails if
+    /// there are any, unless the runtime is newer than the known functions.
+    /// Explains where a function imported from the runtime that it is not
+    /// known to provide likely comes from. Which release of asr introduced a
+    /// function isn't recorded anywhere, but the version the auto splitter got
+    /// built with is the newest one that could have.
+    pub fn unknown_import_origin(&self, name: &str) -> String {
+        let version = match &self.asr_version {
+            Some(version) => format!(
+                "asr {version}, which the auto splitter got built with, or an earlier version"
+            ),
+            None => "a newer version of asr than this debugger supports".into(),
+        };
+        match ASR_APIS.iter().find(|(prefix, _)| name.starts_with(prefix)) {
+            Some((_, api)) => format!("It's used by {api} and was likely introduced by {version}."),
+            None => format!("It was likely introduced by {version}."),
+        }
+    }
+
+    pub fn unknown_imports(&self) -> impl Iterator<Item = (&str, &Item)> {
+        self.imports.iter().flat_map(|(module, imports)| {
+            imports
+                .iter()
+                .filter(move |import| !is_provided(module, import))
+                .map(move |import| (&**module, import))
+        })
+    }
+}
+
+fn is_provided(module: &str, import: &Item) -> bool {
+    match module {
+        RUNTIME_MODULE => import.kind == "function" && RUNTIME_FUNCTIONS.contains(&&*import.name),
+        WASI_MODULE => true,
+        _ => false,
+    }
+}
+
+/// Demangles the name of a Rust function, leaving out the hash. Names that
+/// aren't mangled are kept as they are.
+fn demangle(name: &str) -> Box<str> {
+    format!("{:#}", rustc_demangle::demangle(name)).into()
+}
+
+fn section_name(id: u8) -> &'static str {
+    match id {
+        0 => "custom",
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "data count",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+fn type_ref_kind(ty: &TypeRef) -> &'static str {
+    match ty {
+        TypeRef::Func(_) => "function",
+        TypeRef::Table(_) => "table",
+        TypeRef::Memory(_) => "memory",
+        TypeRef::Global(_) => "global",
+        TypeRef::Tag(_) => "tag",
+    }
+}
+
+fn external_kind(kind: ExternalKind) -> &'static str {
+    match kind {
+        ExternalKind::Func => "function",
+        ExternalKind::Table => "table",
+        ExternalKind::Memory => "memory",
+        ExternalKind::Global => "global",
+        ExternalKind::Tag => "tag",
+    }
+}
+
+/// Finds the names of Windows executables in the data. String literals are
+/// stored without any separators, so this is only a best guess.
+fn executable_names(data: &[u8]) -> impl Iterator<Item = Box<str>> + '_ {
+    data.windows(4)
+        .enumerate()
+        .filter(|(_, w)| w.eq_ignore_ascii_case(b".exe"))
+        .filter_map(|(index, _)| {
+            let end = index + 4;
+            let start = data[..index]
+                .iter()
+                .rposition(|&b| !(b.is_ascii_alphanumeric() || b" _-.".contains(&b)))
+                .map_or(0, |p| p + 1);
+            let name = std::str::from_utf8(&data[start..end]).ok()?.trim_start();
+            (name.len() > 4).then(|| name.into())
+        })
+}
+
+/// Finds the version of the asr crate in the data. Panic messages contain the
+/// path of the source file, which for crates from crates.io includes the
+/// version in the name of the crate's folder. Crates from git repositories
+/// don't have a version there, so they are not found.
+fn asr_version(data: &[u8]) -> Option<Box<str>> {
+    data.windows(5)
+        .enumerate()
+        .filter(|(_, w)| matches!(w[0], b'/' | b'\\') && &w[1..] == b"asr-")
+        .find_map(|(index, _)| {
+            let start = index + 5;
+            let len = data[start..]
+                .iter()
+                .position(|&b| matches!(b, b'/' | b'\\'))?;
+            let version = std::str::from_utf8(&data[start..start + len]).ok()?;
+            let is_version = version.starts_with(|c: char| c.is_ascii_digit())
+                && version.contains('.')
+                && version
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b".-+".contains(&b));
+            is_version.then(|| version.into())
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_demangle() {
+        assert_eq!(
+            &*demangle("_ZN11my_splitter6update17h0123456789abcdefE"),
+            "my_splitter::update",
+        );
+        assert_eq!(&*demangle("update"), "update");
+    }
+
+    #[test]
+    fn test_executable_names() {
+        let names: Vec<_> =
+            executable_names(b"\0Game.exe\0\x01 Hollow Knight.EXEfailed\x02.exe").collect();
+        assert_eq!(
+            names.iter().map(|n| &**n).collect::<Vec<_>>(),
+            ["Game.exe", "Hollow Knight.EXE"],
+        );
+    }
+
+    #[test]
+    fn test_unknown_import_origin() {
+        let mut info = ModuleInfo::parse(b"\0asm\x01\0\0\0").unwrap();
+        info.asr_version = Some("0.2.0".into());
+        assert_eq!(
+            info.unknown_import_origin("process_get_memory_range_name"),
+            "It's used by asr::Process::memory_ranges and was likely introduced by asr 0.2.0, which the auto splitter got built with, or an earlier version.",
+        );
+        assert!(info
+            .unknown_import_origin("process_get_module_name")
+            .starts_with("It's used by asr::Process and"));
+    }
+
+    #[test]
+    fn test_asr_version() {
+        assert_eq!(
+            asr_version(b"src/lib.rs\0/asr-de5ad8fbc4e5ae9f/src/\0C:\\cargo\\asr-0.1.0\\src\\")
+                .as_deref(),
+            Some("0.1.0"),
+        );
+        assert_eq!(
+            asr_version(b"/asr-1.0.0-beta.1/src/"),
+            Some("1.0.0-beta.1".into())
+        );
+        assert_eq!(asr_version(b"/asr-0.1.0"), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        #[rustfmt::skip]
+        let data = [
+            // Header
+            0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00,
+            // Type section: () -> ()
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+            // Import section: env.tick: func 0, env.memory: memory 1..2
+            0x02, 0x1B, 0x02,
+            0x03, b'e', b'n', b'v', 0x04, b't', b'i', b'c', b'k', 0x00, 0x00,
+            0x03, b'e', b'n', b'v', 0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x01, 0x01, 0x02,
+            // Function section: a single function of type 0
+            0x03, 0x02, 0x01, 0x00,
+            // Export section: update: func 1
+            0x07, 0x0A, 0x01, 0x06, b'u', b'p', b'd', b'a', b't', b'e', 0x00, 0x01,
+            // Code section: an empty function body
+            0x0A, 0x04, 0x01, 0x02, 0x00, 0x0B,
+            // Custom section "name" with two bytes of content
+            0x00, 0x07, 0x04, b'n', b'a', b'm', b'e', 0x00, 0x00,
+        ];
+
+        let info = ModuleInfo::parse(&data).unwrap();
+        assert_eq!(info.size, data.len());
+        assert_eq!(info.defined_functions, 1);
+
+        let env = &info.imports["env"];
+        assert_eq!(env.len(), 2);
+        assert_eq!(&*env[0].name, "tick");
+        assert_eq!(env[0].kind, "function");
+        assert_eq!(env[1].kind, "memory");
+
+        assert_eq!(info.exports.len(), 1);
+        assert_eq!(&*info.exports[0].name, "update");
+
+        assert_eq!(info.imported_functions, 1);
+        assert_eq!(info.functions.len(), 1);
+        assert_eq!(info.functions[0].size, 2);
+
+        assert_eq!(info.memories.len(), 1);
+        assert_eq!(info.memories[0].initial, 1);
+        assert_eq!(info.memories[0].maximum, Some(2));
+        assert!(info.memories[0].imported);
+
+        assert_eq!(info.custom_sections.len(), 1);
+        assert_eq!(&*info.custom_sections[0].name, "name");
+        assert_eq!(info.custom_sections[0].size, 2);
+
+        let unknown: Vec<_> = info
+            .unknown_imports()
+            .map(|(module, import)| format!("{module}.{}", import.name))
+            .collect();
+        assert_eq!(unknown, ["env.tick", "env.memory"]);
+        assert_eq!(
+            info.unknown_import_origin("tick"),
+            "It was likely introduced by a newer version of asr than this debugger supports.",
+        );
+
+        let capabilities: Vec<_> = CAPABILITIES
+            .iter()
+            .filter(|c| info.uses(c))
+            .map(|c| c.name)
+            .collect();
+        assert!(capabilities.is_empty());
+
+        let names: Vec<_> = info.sections.iter().map(|s| &*s.name).collect();
+        assert_eq!(
+            names,
+            [
+                "type",
+                "import",
+                "function",
+                "export",
+                "code",
+                "custom \"name\""
+            ],
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use std::{fmt, io};
+
+use read_process_memory::ProcessHandle;
+
+/// How many bytes get read for strings and raw bytes.
+const MAX_VALUE_LEN: usize = 32;
+
+#[derive(Copy, Clone, Default, PartialEq)]
+pub enum ValueType {
+    #[default]
+    I32,
+    F32,
+    String,
+    Bytes,
+}
+
+pub const VALUE_TYPES: [ValueType; 4] = [
+    ValueType::I32,
+    ValueType::F32,
+    ValueType::String,
+    ValueType::Bytes,
+];
+
+impl ValueType {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            ValueType::I32 => "i32",
+            ValueType::F32 => "f32",
+            ValueType::String => "String",
+            ValueType::Bytes => "Bytes",
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            ValueType::I32 | ValueType::F32 => 4,
+            ValueType::String | ValueType::Bytes => MAX_VALUE_LEN,
+        }
+    }
+
+    fn format(self, bytes: &[u8]) -> String {
+        match self {
+            ValueType::I32 => i32::from_le_bytes(bytes[..4].try_into().unwrap()).to_string(),
+            ValueType::F32 => f32::from_le_bytes(bytes[..4].try_into().unwrap()).to_string(),
+            ValueType::String => {
+                let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                format!("{:?}", String::from_utf8_lossy(&bytes[..len]))
+            }
+            ValueType::Bytes => bytes
+                .iter()
+                .map(|b| format!("{b:02X}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+/// A value in the memory of an attached process, found by following pointers
+/// the same way Cheat Engine does: The base address is relative to a module.
+/// A pointer is read from it, the first offset gets added to it, the next
+/// pointer is read from there and so on.
+pub struct PointerPath {
+    pub pid: u64,
+    /// The path of the executable of the process, so the pointer path can
+    /// follow the game when it gets restarted.
+    pub process_path: Box<str>,
+    pub module: Box<str>,
+    /// The address the module is loaded at, if it's loaded.
+    pub module_base: Option<u64>,
+    pub base: u64,
+    pub offsets: Vec<u64>,
+    pub pointer_size: u8,
+    pub ty: ValueType,
+    /// The value as of the last tick, or why it couldn't be read.
+    pub value: Result<String, String>,
+}
+
+impl PointerPath {
+    /// Resolves the path again and reads the value it points to.
+    pub fn update(&mut self) {
+        self.value = self.read().map_err(|e| e.to_string());
+    }
+
+    fn read(&self) -> io::Result<String> {
+        let module_base = self.module_base.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("The module {} is not loaded.", self.module),
+            )
+        })?;
+        let handle = ProcessHandle::try_from(self.pid as read_process_memory::Pid)?;
+        let address = resolve(
+            module_base.wrapping_add(self.base),
+            &self.offsets,
+            |address| {
+                let bytes = read_process_memory::copy_address(
+                    address as usize,
+                    self.pointer_size as usize,
+                    &handle,
+                )?;
+                Ok(read_pointer(&bytes))
+            },
+        )?;
+        let bytes = read_process_memory::copy_address(address as usize, self.ty.size(), &handle)?;
+        Ok(self.ty.format(&bytes))
+    }
+}
+
+/// Shows the path in the notation of Cheat Engine.
+impl fmt::Display for PointerPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"+{:X}", self.module, self.base)?;
+        for offset in &self.offsets {
+            write!(f, " -> {offset:X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The pointer path that is being entered in the Processes tab.
+#[derive(Default)]
+pub struct Draft {
+    pub pid: Option<u64>,
+    pub module: String,
+    pub base: String,
+    pub offsets: String,
+    pub ty: ValueType,
+}
+
+fn resolve(
+    base: u64,
+    offsets: &[u64],
+    mut read_pointer: impl FnMut(u64) -> io::Result<u64>,
+) -> io::Result<u64> {
+    let mut address = base;
+    for &offset in offsets {
+        address = read_pointer(address)?.wrapping_add(offset);
+    }
+    Ok(address)
+}
+
+fn read_pointer(bytes: &[u8]) -> u64 {
+    let mut buf = [0; 8];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(buf)
+}
+
+/// Parses a hexadecimal number, with or without a `0x` prefix. Negative
+/// numbers wrap around, so they can be used as offsets.
+pub fn parse_hex(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let text = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    let value = u64::from_str_radix(text, 16).ok()?;
+    Some(if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
+
+/// Parses hexadecimal offsets separated by commas or whitespace.
+pub fn parse_offsets(text: &str) -> Option<Vec<u64>> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|offset| !offset.is_empty())
+        .map(parse_hex)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let memory = HashMap::from([(0x1010, 0x2000), (0x2018, 0x3000)]);
+        let read = |address| {
+            memory
+                .get(&address)
+                .copied()
+                .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))
+        };
+
+        assert_eq!(resolve(0x1010, &[], read).unwrap(), 0x1010);
+        assert_eq!(resolve(0x1010, &[0x18, 0x4], read).unwrap(), 0x3004);
+        assert!(resolve(0x1010, &[0x20, 0x4], read).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse_offsets("0x10, 28 -8"),
+            Some(vec![0x10, 0x28, 8u64.wrapping_neg()])
+        );
+        assert_eq!(parse_offsets(""), Some(vec![]));
+        assert_eq!(parse_offsets("0x10, zz"), None);
+        assert_eq!(parse_hex("0X1A"), Some(0x1A));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(ValueType::I32.format(&(-5i32).to_le_bytes()), "-5");
+        assert_eq!(ValueType::F32.format(&1.5f32.to_le_bytes()), "1.5");
+        assert_eq!(ValueType::String.format(b"Level 1\0garbage"), "\"Level 1\"");
+        assert_eq!(ValueType::Bytes.format(&[0xAB, 0x01]), "AB 01");
+    }
+}
@@ -0,0 +1,79 @@
+use std::{collections::HashMap, ops::RangeInclusive, path::PathBuf, time::Duration};
+
+use eframe::egui::Visuals;
+use serde::{Deserialize, Serialize};
+
+use crate::{game_launch::GameLaunch, stats_history};
+
+/// The settings of the debugger itself that persist across sessions.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub theme: Theme,
+    /// The scale of the whole user interface.
+    pub zoom: f32,
+    /// How to launch the game of each auto splitter, keyed by the path of the
+    /// auto splitter.
+    pub games: HashMap<PathBuf, GameLaunch>,
+    /// The tick time above which ticks are highlighted in the Performance
+    /// tab.
+    pub tick_budget: Option<Duration>,
+    /// The statistics of the previous sessions of each auto splitter, keyed
+    /// by the path of the auto splitter.
+    pub stats_history: HashMap<PathBuf, Vec<stats_history::Entry>>,
+    /// The folder in which the logs, the memory and the settings map are
+    /// saved automatically whenever the auto splitter traps.
+    pub trap_artifacts: Option<PathBuf>,
+}
+
+pub const ZOOM_RANGE: RangeInclusive<f32> = 0.5..=3.0;
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            zoom: 1.15,
+            games: HashMap::new(),
+            tick_budget: None,
+            stats_history: HashMap::new(),
+            trap_artifacts: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    System,
+}
+
+pub const THEMES: [Theme; 3] = [Theme::Dark, Theme::Light, Theme::System];
+
+impl Theme {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+            Theme::System => "Follow System",
+        }
+    }
+
+    /// Whether the theme is dark, given the theme of the system if it's known.
+    pub fn is_dark(self, system_theme: Option<eframe::Theme>) -> bool {
+        match self {
+            Theme::Dark => true,
+            Theme::Light => false,
+            Theme::System => system_theme != Some(eframe::Theme::Light),
+        }
+    }
+}
+
+pub fn visuals(dark: bool) -> Visuals {
+    if dark {
+        Visuals::dark()
+    } else {
+        Visuals::light()
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// How often the processes running on the system get listed again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The length process names get truncated to on Linux.
+const LINUX_NAME_LENGTH: usize = 15;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Status {
+    Attached,
+    NotAttached,
+    InsufficientPermissions,
+    NameMismatch,
+    NotRunning,
+}
+
+impl Status {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Status::Attached => "Attached",
+            Status::NotAttached => "Not Attached",
+            Status::InsufficientPermissions => "Insufficient Permissions",
+            Status::NameMismatch => "Name Mismatch",
+            Status::NotRunning => "Not Running",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Status::Attached => "The auto splitter is attached to the process.",
+            Status::NotAttached => {
+                "The process is running, but the auto splitter isn't attached to it."
+            }
+            Status::InsufficientPermissions => {
+                "The process is running, but its memory can't be accessed. Try running the debugger with elevated permissions."
+            }
+            Status::NameMismatch => {
+                "A process with a similar name is running. Attaching requires the exact name."
+            }
+            Status::NotRunning => "No process with this name is running.",
+        }
+    }
+}
+
+/// A running process that the auto splitter might want to attach to.
+pub struct Candidate {
+    /// The name the auto splitter is looking for.
+    pub name: Box<str>,
+    pub pid: Option<u64>,
+    /// The actual name of the process.
+    pub process_name: Box<str>,
+    pub status: Status,
+}
+
+/// Lists the processes running on the system that match the names the auto
+/// splitter is looking for, to figure out why attaching might fail.
+pub struct ProcessCandidates {
+    /// The names of the processes, separated by commas.
+    pub names: String,
+    pub candidates: Vec<Candidate>,
+    system: System,
+    last_refresh: Option<Instant>,
+}
+
+impl Default for ProcessCandidates {
+    fn default() -> Self {
+        Self {
+            names: String::new(),
+            candidates: Vec::new(),
+            system: System::new(),
+            last_refresh: None,
+        }
+    }
+}
+
+impl ProcessCandidates {
+    pub fn update(&mut self, now: Instant, is_attached: impl Fn(u64) -> bool) {
+        if self
+            .last_refresh
+            .is_some_and(|last| now.duration_since(last) < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.last_refresh = Some(now);
+        self.system
+            .refresh_processes_specifics(ProcessesToUpdate::All, ProcessRefreshKind::new());
+
+        self.candidates.clear();
+        for name in self
+            .names
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+        {
+            let len = self.candidates.len();
+            let mut similar = Vec::new();
+            for (pid, process) in self.system.processes() {
+                let process_name = process.name().to_string_lossy();
+                let pid = pid.as_u32() as u64;
+                let status = if process_name == name {
+                    if is_attached(pid) {
+                        Status::Attached
+                    } else if proc_maps::get_process_maps(pid as proc_maps::Pid)
+                        .is_err_and(|e| e.kind() == io::ErrorKind::PermissionDenied)
+                    {
+                        Status::InsufficientPermissions
+                    } else {
+                        Status::NotAttached
+                    }
+                } else if is_similar(name, &process_name) {
+                    Status::NameMismatch
+                } else {
+                    continue;
+                };
+                let candidate = Candidate {
+                    name: name.into(),
+                    pid: Some(pid),
+                    process_name: process_name.into(),
+                    status,
+                };
+                if status == Status::NameMismatch {
+                    similar.push(candidate);
+                } else {
+                    self.candidates.push(candidate);
+                }
+            }
+            // Similar names are only interesting if there's no exact match.
+            if self.candidates.len() == len {
+                if similar.is_empty() {
+                    self.candidates.push(Candidate {
+                        name: name.into(),
+                        pid: None,
+                        process_name: "".into(),
+                        status: Status::NotRunning,
+                    });
+                } else {
+                    self.candidates.append(&mut similar);
+                }
+            }
+        }
+    }
+
+    /// Forces the processes to be listed again on the next update.
+    pub fn invalidate(&mut self) {
+        self.last_refresh = None;
+    }
+}
+
+/// Whether the name of the process is close enough to the name that is
+/// searched for that it is likely meant to be the same process.
+fn is_similar(name: &str, process_name: &str) -> bool {
+    if process_name.eq_ignore_ascii_case(name) {
+        return true;
+    }
+    // Linux truncates the names of processes.
+    if process_name.len() == LINUX_NAME_LENGTH && name.starts_with(process_name) {
+        return true;
+    }
+    let (name, process_name) = (name.to_lowercase(), process_name.to_lowercase());
+    process_name.len() >= 3 && (name.contains(&process_name) || process_name.contains(&name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_similar() {
+        assert!(is_similar("Game.exe", "game.exe"));
+        assert!(is_similar("HollowKnightGame.exe", "HollowKnightGa"));
+        assert!(is_similar("HollowKnightGame", "HollowKnightGam"));
+        assert!(is_similar("Game.exe", "Game"));
+        assert!(!is_similar("Game.exe", "Other.exe"));
+        assert!(!is_similar("Game.exe", "ga"));
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// How often the memory map of an attached process gets read again.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A module loaded into a process, spanning all the memory ranges that are
+/// mapped from its file.
+pub struct Module {
+    pub name: Box<str>,
+    pub path: Box<str>,
+    pub base: u64,
+    pub size: u64,
+}
+
+pub struct MemoryRange {
+    pub start: u64,
+    pub size: u64,
+    /// The permissions of the range in the form of `rwx`.
+    pub permissions: [u8; 3],
+    pub path: Option<Box<str>>,
+}
+
+impl MemoryRange {
+    pub fn permissions(&self) -> &str {
+        std::str::from_utf8(&self.permissions).unwrap_or("???")
+    }
+}
+
+pub struct ProcessMaps {
+    pub modules: Vec<Module>,
+    pub ranges: Vec<MemoryRange>,
+}
+
+impl ProcessMaps {
+    pub fn read(pid: u64) -> io::Result<Self> {
+        let ranges = proc_maps::get_process_maps(pid as proc_maps::Pid)?
+            .into_iter()
+            .map(|range| MemoryRange {
+                start: range.start() as u64,
+                size: range.size() as u64,
+                permissions: [
+                    if range.is_read() { b'r' } else { b'-' },
+                    if range.is_write() { b'w' } else { b'-' },
+                    if range.is_exec() { b'x' } else { b'-' },
+                ],
+                path: range
+                    .filename()
+                    .filter(|path| !path.as_os_str().is_empty())
+                    .map(|path| path.to_string_lossy().into()),
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            modules: modules(&ranges),
+            ranges,
+        })
+    }
+}
+
+/// Writes the memory ranges, given by their start and size, to a file each.
+/// The files are named after the process and the address of the range, so
+/// they can be told apart when attached to an issue.
+pub fn dump(pid: u64, ranges: &[(u64, u64)], directory: &Path) -> io::Result<()> {
+    let handle = read_process_memory::ProcessHandle::try_from(pid as read_process_memory::Pid)?;
+    for &(start, size) in ranges {
+        let data = read_process_memory::copy_address(start as usize, size as usize, &handle)?;
+        fs::write(directory.join(dump_file_name(pid, start)), data)?;
+    }
+    Ok(())
+}
+
+fn dump_file_name(pid: u64, start: u64) -> String {
+    format!("process_{pid}_{start:016X}.bin")
+}
+
+/// Groups the memory ranges by the file they are mapped from. This is the
+/// same way the runtime determines the address and size of a module.
+fn modules(ranges: &[MemoryRange]) -> Vec<Module> {
+    let mut modules = Vec::<Module>::new();
+    for range in ranges {
+        let Some(path) = &range.path else { continue };
+        // Pseudo paths such as `[heap]` aren't files.
+        if path.starts_with('[') {
+            continue;
+        }
+        let end = range.start + range.size;
+        if let Some(module) = modules.iter_mut().find(|m| m.path == *path) {
+            let module_end = (module.base + module.size).max(end);
+            module.base = module.base.min(range.start);
+            module.size = module_end - module.base;
+        } else {
+            modules.push(Module {
+                name: Path::new(&**path)
+                    .file_name()
+                    .map_or_else(|| path.clone(), |name| name.to_string_lossy().into()),
+                path: path.clone(),
+                base: range.start,
+                size: range.size,
+            });
+        }
+    }
+    modules.sort_unstable_by_key(|m| m.base);
+    modules
+}
+
+/// The memory maps of the attached processes, read again periodically.
+#[derive(Default)]
+pub struct ProcessMapsCache {
+    processes: HashMap<u64, (Instant, io::Result<ProcessMaps>)>,
+}
+
+impl ProcessMapsCache {
+    pub fn get(&mut self, pid: u64, now: Instant) -> &io::Result<ProcessMaps> {
+        let (read_at, maps) = self
+            .processes
+            .entry(pid)
+            .or_insert_with(|| (now, ProcessMaps::read(pid)));
+        if now.duration_since(*read_at) >= REFRESH_INTERVAL {
+            *read_at = now;
+            *maps = ProcessMaps::read(pid);
+        }
+        maps
+    }
+
+    /// Forgets about all the processes that aren't attached anymore.
+    pub fn retain(&mut self, mut is_attached: impl FnMut(u64) -> bool) {
+        self.processes.retain(|&pid, _| is_attached(pid));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn range(start: u64, size: u64, path: Option<&str>) -> MemoryRange {
+        MemoryRange {
+            start,
+            size,
+            permissions: *b"r--",
+            path: path.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn test_modules() {
+        let modules = modules(&[
+            range(0x2000, 0x1000, Some("/usr/lib/libc.so.6")),
+            range(0x1000, 0x1000, Some("/game/game.exe")),
+            range(0x3000, 0x1000, None),
+            range(0x4000, 0x2000, Some("/usr/lib/libc.so.6")),
+            range(0x6000, 0x1000, Some("[heap]")),
+        ]);
+
+        assert_eq!(modules.len(), 2);
+        assert_eq!(&*modules[0].name, "game.exe");
+        assert_eq!((modules[0].base, modules[0].size), (0x1000, 0x1000));
+        assert_eq!(&*modules[1].name, "libc.so.6");
+        assert_eq!((modules[1].base, modules[1].size), (0x2000, 0x4000));
+    }
+
+    #[test]
+    fn test_dump_file_name() {
+        assert_eq!(
+            dump_file_name(42, 0x7FF6_1000),
+            "process_42_000000007FF61000.bin"
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often the state of the remote debugger is queried.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// How long a single request may take before the connection is considered
+/// lost.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Responses are only ever JSON values, but the logs can add up.
+const MAX_RESPONSE_SIZE: u64 = 64 << 20;
+
+/// A connection to a debugger running elsewhere, usually in headless mode on
+/// the machine the game runs on. It talks to the HTTP API of that debugger,
+/// which it polls on a separate thread, so the user interface never has to
+/// wait for the network.
+pub struct Remote {
+    pub address: Box<str>,
+    state: Arc<Mutex<RemoteState>>,
+    actions: mpsc::Sender<Action>,
+}
+
+/// The state of the remote debugger as of the last time it was queried.
+#[derive(Default)]
+pub struct RemoteState {
+    pub timer: serde_json::Value,
+    pub variables: serde_json::Map<String, serde_json::Value>,
+    pub stats: serde_json::Value,
+    pub logs: Vec<serde_json::Value>,
+    /// The settings map, if an auto splitter is loaded.
+    pub settings: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Why the last query or action failed. It's cleared once a query
+    /// succeeds again.
+    pub error: Option<String>,
+    pub last_update: Option<Instant>,
+}
+
+/// Something to do on the remote debugger.
+pub enum Action {
+    Reload,
+    Restart,
+    Kill,
+    SetSetting {
+        key: String,
+        value: serde_json::Value,
+    },
+}
+
+impl Remote {
+    /// Starts querying the debugger at the address. The token is the one the
+    /// remote debugger printed at startup, which is only needed if it's
+    /// reachable from other machines. The callback is called whenever the
+    /// state got updated.
+    pub fn connect(
+        address: Box<str>,
+        token: Option<Box<str>>,
+        on_update: impl Fn() + Send + 'static,
+    ) -> io::Result<Self> {
+        let state = Arc::new(Mutex::new(RemoteState::default()));
+        let (actions, receiver) = mpsc::channel();
+        let client = Client {
+            address: address.clone(),
+            token,
+        };
+        thread::Builder::new().name("Remote".into()).spawn({
+            let state = state.clone();
+            move || loop {
+                let deadline = Instant::now() + POLL_INTERVAL;
+                let result = receiver
+                    .try_iter()
+                    .try_for_each(|action| client.perform(action))
+                    .and_then(|()| client.poll(&state));
+                state.lock().unwrap().error = result.err().map(|e| e.to_string());
+                on_update();
+                match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(action) => {
+                        if let Err(e) = client.perform(action) {
+                            state.lock().unwrap().error = Some(e.to_string());
+                        }
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        })?;
+        Ok(Self {
+            address,
+            state,
+            actions,
+        })
+    }
+
+    pub fn state(&self) -> std::sync::MutexGuard<'_, RemoteState> {
+        self.state.lock().unwrap()
+    }
+
+    /// Queues the action to be performed before the next query.
+    pub fn perform(&self, action: Action) {
+        let _ = self.actions.send(action);
+    }
+}
+
+struct Client {
+    address: Box<str>,
+    token: Option<Box<str>>,
+}
+
+impl Client {
+    fn poll(&self, state: &Mutex<RemoteState>) -> io::Result<()> {
+        let timer = self.get("/timer")?;
+        let variables = self.get("/variables")?;
+        let stats = self.get("/stats")?;
+        let settings = match self.request("GET", "/settings", None)? {
+            (200, serde_json::Value::Object(settings)) => Some(settings),
+            // There is no auto splitter loaded.
+            (409, _) => None,
+            (status, body) => return Err(error_response(status, &body)),
+        };
+
+        // The last message is queried again, as it counts up when it gets
+        // repeated.
+        let since = state.lock().unwrap().logs.len().saturating_sub(1);
+        let logs = self.get(&format!("/logs?since={since}"))?;
+
+        let mut state = state.lock().unwrap();
+        state.timer = timer;
+        state.variables = match variables {
+            serde_json::Value::Object(variables) => variables,
+            _ => Default::default(),
+        };
+        state.stats = stats;
+        state.settings = settings;
+        let next = logs["next"].as_u64().unwrap_or_default() as usize;
+        if next < since {
+            // The logs got cleared, so they are queried from the start again
+            // the next time.
+            state.logs.clear();
+        } else if let serde_json::Value::Array(new_logs) = &logs["logs"] {
+            state.logs.truncate(since);
+            state.logs.extend(new_logs.iter().cloned());
+        }
+        state.last_update = Some(Instant::now());
+        Ok(())
+    }
+
+    fn perform(&self, action: Action) -> io::Result<()> {
+        let (path, body) = match action {
+            Action::Reload => ("/reload".to_owned(), String::new()),
+            Action::Restart => ("/restart".to_owned(), String::new()),
+            Action::Kill => ("/kill".to_owned(), String::new()),
+            Action::SetSetting { key, value } => (
+                format!("/settings/{}", percent_encode(&key)),
+                value.to_string(),
+            ),
+        };
+        match self.request("POST", &path, Some(&body))? {
+            (200, _) => Ok(()),
+            (status, body) => Err(error_response(status, &body)),
+        }
+    }
+
+    fn get(&self, path: &str) -> io::Result<serde_json::Value> {
+        match self.request("GET", path, None)? {
+            (200, body) => Ok(body),
+            (status, body) => Err(error_response(status, &body)),
+        }
+    }
+
+    /// Sends a request and returns the status code and the JSON body of the
+    /// response. Every request uses its own connection, which keeps this
+    /// independent of how the server handles keep-alive.
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&str>,
+    ) -> io::Result<(u16, serde_json::Value)> {
+        let address = self
+            .address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::other("The address could not be resolved."))?;
+        let mut stream = TcpStream::connect_timeout(&address, REQUEST_TIMEOUT)?;
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+        stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+
+        let mut request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+            self.address,
+        );
+        if let Some(token) = &self.token {
+            request.push_str(&format!("Authorization: Bearer {token}\r\n"));
+        }
+        if let Some(body) = body {
+            request.push_str(&format!(
+                "Content-Type: application/json\r\nContent-Length: {}\r\n",
+                body.len(),
+            ));
+        }
+        request.push_str("\r\n");
+        request.push_str(body.unwrap_or_default());
+        stream.write_all(request.as_bytes())?;
+
+        let mut response = Vec::new();
+        stream.take(MAX_RESPONSE_SIZE).read_to_end(&mut response)?;
+        parse_response(&response)
+    }
+}
+
+/// Parses the status code and the JSON body out of an HTTP response.
+fn parse_response(response: &[u8]) -> io::Result<(u16, serde_json::Value)> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "The response is invalid.");
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(invalid)?;
+    let head = std::str::from_utf8(&response[..header_end]).map_err(|_| invalid())?;
+    let status = head
+        .split(' ')
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(invalid)?;
+    let is_chunked = head.lines().skip(1).any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("Transfer-Encoding") && value.trim() == "chunked"
+        })
+    });
+    let body = &response[header_end + 4..];
+    let body = if is_chunked {
+        decode_chunked(body).ok_or_else(invalid)?
+    } else {
+        body.to_vec()
+    };
+    let body = serde_json::from_slice(&body).map_err(|_| invalid())?;
+    Ok((status, body))
+}
+
+fn decode_chunked(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body.windows(2).position(|window| window == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..line_end]).ok()?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Some(decoded);
+        }
+        decoded.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+fn error_response(status: u16, body: &serde_json::Value) -> io::Error {
+    match body["error"].as_str() {
+        Some(message) => io::Error::other(format!("{message} ({status})")),
+        None => io::Error::other(format!("The request failed with the status {status}.")),
+    }
+}
+
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let (status, body) = parse_response(
+            b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 13\r\n\r\n{\"tick\": 12}\n",
+        )
+        .unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(body["tick"], 12);
+
+        let (status, body) = parse_response(
+            b"HTTP/1.1 409 Conflict\r\nTransfer-Encoding: chunked\r\n\r\n5\r\n{\"err\r\n9\r\nor\": \"x\"}\r\n0\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(status, 409);
+        assert_eq!(body["error"], "x");
+
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+        assert_eq!(percent_encode("use igt/ä"), "use%20igt%2F%C3%A4");
+    }
+}
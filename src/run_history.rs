@@ -0,0 +1,249 @@
+use std::{collections::VecDeque, time::Instant};
+
+/// How many of the previous attempts are kept.
+const ATTEMPT_CAPACITY: usize = 50;
+/// How often the game time is sampled at most, so long runs don't keep a
+/// sample for every single tick.
+const GAME_TIME_SAMPLE_INTERVAL: time::Duration = time::Duration::milliseconds(100);
+
+pub struct Split {
+    pub tick: u64,
+    /// The real time since the start of the attempt.
+    pub real_time: time::Duration,
+    /// The game time, if the auto splitter set it.
+    pub game_time: Option<time::Duration>,
+    pub skipped: bool,
+}
+
+/// The game time at a point in real time of the current attempt.
+pub struct GameTimeSample {
+    pub real_time: time::Duration,
+    pub game_time: time::Duration,
+}
+
+/// The splits the auto splitter issued in the current attempt and the
+/// previous ones, to check how consistently they are placed.
+#[derive(Default)]
+pub struct RunHistory {
+    started: Option<Instant>,
+    pub current: Vec<Split>,
+    /// The splits of the previous attempts, oldest first.
+    pub attempts: VecDeque<Vec<Split>>,
+    /// The game time over the course of the current or the last attempt.
+    pub game_times: Vec<GameTimeSample>,
+    /// The real times at which the game timer got paused and resumed in the
+    /// current or the last attempt. The last pause may still be ongoing.
+    pub pauses: Vec<(time::Duration, Option<time::Duration>)>,
+}
+
+impl RunHistory {
+    pub fn start(&mut self, now: Instant) {
+        self.started = Some(now);
+        self.current.clear();
+        self.game_times.clear();
+        self.pauses.clear();
+    }
+
+    /// Records the state of the game timer after a tick of the attempt.
+    pub fn record_game_time(
+        &mut self,
+        now: Instant,
+        game_time: Option<time::Duration>,
+        paused: bool,
+    ) {
+        let Some(started) = self.started else { return };
+        let real_time = (now - started).try_into().unwrap_or_default();
+
+        match self.pauses.last_mut() {
+            Some((_, end @ None)) if !paused => *end = Some(real_time),
+            Some((_, None)) => {}
+            _ if paused => self.pauses.push((real_time, None)),
+            _ => {}
+        }
+
+        let Some(game_time) = game_time else { return };
+        if self
+            .game_times
+            .last()
+            .is_some_and(|last| real_time - last.real_time < GAME_TIME_SAMPLE_INTERVAL)
+        {
+            return;
+        }
+        self.game_times.push(GameTimeSample {
+            real_time,
+            game_time,
+        });
+    }
+
+    pub fn split(
+        &mut self,
+        now: Instant,
+        tick: u64,
+        game_time: Option<time::Duration>,
+        skipped: bool,
+    ) {
+        let Some(started) = self.started else { return };
+        self.current.push(Split {
+            tick,
+            real_time: (now - started).try_into().unwrap_or_default(),
+            game_time,
+            skipped,
+        });
+    }
+
+    /// How long the game timer was paused during the current or the last
+    /// attempt. A pause that is still ongoing counts up to now.
+    pub fn paused_time(&self, now: Instant) -> time::Duration {
+        let real_time = self
+            .started
+            .map(|started| (now - started).try_into().unwrap_or_default());
+        self.pauses
+            .iter()
+            .map(|&(start, end)| {
+                end.or(real_time)
+                    .map_or(time::Duration::ZERO, |end| end - start)
+            })
+            .sum()
+    }
+
+    pub fn undo_split(&mut self) {
+        self.current.pop();
+    }
+
+    /// Ends the current attempt. A pause that is still ongoing ends with the
+    /// attempt.
+    pub fn reset(&mut self, now: Instant) {
+        if let Some(started) = self.started.take() {
+            if let Some((_, end @ None)) = self.pauses.last_mut() {
+                *end = Some((now - started).try_into().unwrap_or_default());
+            }
+        }
+        if self.current.is_empty() {
+            return;
+        }
+        if self.attempts.len() == ATTEMPT_CAPACITY {
+            self.attempts.pop_front();
+        }
+        self.attempts.push_back(std::mem::take(&mut self.current));
+    }
+
+    pub fn clear(&mut self) {
+        self.started = None;
+        self.current.clear();
+        self.attempts.clear();
+        self.game_times.clear();
+        self.pauses.clear();
+    }
+
+    /// The splits of the attempt to show, which is the current one unless
+    /// there's no attempt in progress, along with the splits of the attempt
+    /// before it to compare against.
+    pub fn comparison(&self) -> (&[Split], Option<&[Split]>) {
+        let len = self.attempts.len();
+        if self.started.is_some() || !self.current.is_empty() || len == 0 {
+            (&self.current, self.attempts.back().map(|a| &**a))
+        } else {
+            (
+                &self.attempts[len - 1],
+                len.checked_sub(2).map(|i| &*self.attempts[i]),
+            )
+        }
+    }
+}
+
+impl Split {
+    /// The time used for comparisons, preferring the game time.
+    pub fn time(&self) -> time::Duration {
+        self.game_time.unwrap_or(self.real_time)
+    }
+
+    /// The time from the other split to this one. The game time is only used
+    /// if both splits have one, so game time never gets compared with real
+    /// time.
+    pub fn since(&self, other: &Split) -> time::Duration {
+        match (self.game_time, other.game_time) {
+            (Some(game_time), Some(other)) => game_time - other,
+            _ => self.real_time - other.real_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_attempts() {
+        let mut history = RunHistory::default();
+        let now = Instant::now();
+
+        history.split(now, 0, None, false);
+        assert!(history.current.is_empty());
+
+        history.start(now);
+        history.split(now + Duration::from_secs(1), 10, None, false);
+        history.split(now + Duration::from_secs(2), 20, None, false);
+        history.undo_split();
+        history.reset(now + Duration::from_secs(3));
+
+        let (splits, comparison) = history.comparison();
+        assert_eq!(splits.len(), 1);
+        assert_eq!(splits[0].real_time, time::Duration::SECOND);
+        assert!(comparison.is_none());
+
+        history.start(now);
+        history.split(now, 5, Some(time::Duration::SECOND), true);
+        let (splits, comparison) = history.comparison();
+        assert_eq!(splits[0].time(), time::Duration::SECOND);
+        assert!(splits[0].skipped);
+        assert_eq!(comparison.unwrap()[0].tick, 10);
+        assert_eq!(
+            splits[0].since(&comparison.unwrap()[0]),
+            -time::Duration::SECOND,
+        );
+    }
+
+    #[test]
+    fn test_game_time() {
+        let mut history = RunHistory::default();
+        let now = Instant::now();
+        let ms = |ms| now + Duration::from_millis(ms);
+
+        history.start(now);
+        history.record_game_time(ms(0), None, false);
+        history.record_game_time(ms(50), Some(time::Duration::ZERO), true);
+        history.record_game_time(ms(100), Some(time::Duration::ZERO), true);
+        history.record_game_time(ms(200), Some(time::Duration::ZERO), false);
+        history.record_game_time(ms(250), Some(time::Duration::ZERO), false);
+
+        assert_eq!(history.game_times.len(), 2);
+        assert_eq!(
+            history.pauses,
+            [(
+                time::Duration::milliseconds(50),
+                Some(time::Duration::milliseconds(200)),
+            )],
+        );
+
+        history.record_game_time(ms(300), Some(time::Duration::ZERO), true);
+        assert_eq!(
+            history.paused_time(ms(400)),
+            time::Duration::milliseconds(250),
+        );
+
+        history.reset(ms(500));
+        assert_eq!(
+            history.pauses.last(),
+            Some(&(
+                time::Duration::milliseconds(300),
+                Some(time::Duration::milliseconds(500)),
+            )),
+        );
+        assert_eq!(
+            history.paused_time(ms(1000)),
+            time::Duration::milliseconds(350),
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::timer_script;
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimerEvent {
+    Start,
+    Split,
+    SkipSplit,
+    UndoSplit,
+    Reset,
+}
+
+pub const TIMER_EVENTS: [TimerEvent; 5] = [
+    TimerEvent::Start,
+    TimerEvent::Split,
+    TimerEvent::SkipSplit,
+    TimerEvent::UndoSplit,
+    TimerEvent::Reset,
+];
+
+impl TimerEvent {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            TimerEvent::Start => "Start",
+            TimerEvent::Split => "Split",
+            TimerEvent::SkipSplit => "Skip Split",
+            TimerEvent::UndoSplit => "Undo Split",
+            TimerEvent::Reset => "Reset",
+        }
+    }
+}
+
+/// The timer events an auto splitter is expected to cause, read from a JSON
+/// file. The ticks are counted from the start of the scenario, so the first
+/// tick is tick 1.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Scenario {
+    /// How many ticks an event may be off from the expected tick.
+    #[serde(default)]
+    pub tick_tolerance: u64,
+    /// How many seconds the game time may be off from the expected game time.
+    #[serde(default)]
+    pub game_time_tolerance: f64,
+    pub events: Vec<ExpectedEvent>,
+    /// What happens to the auto splitter from the outside during the
+    /// scenario, replayed at the same ticks.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<Input>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExpectedEvent {
+    pub tick: u64,
+    pub event: TimerEvent,
+    /// The game time in seconds at the time of the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_time: Option<f64>,
+}
+
+/// An input that gets replayed right before the auto splitter updates in the
+/// tick.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Input {
+    pub tick: u64,
+    #[serde(flatten)]
+    pub kind: InputKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputKind {
+    /// A timer action performed by the user, written like a line of a timer
+    /// script.
+    Timer(String),
+    /// Settings that get changed, as a JSON object like the settings map.
+    Settings(serde_json::Map<String, serde_json::Value>),
+}
+
+impl Scenario {
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let data = fs::read(path).context("Failed reading the scenario file.")?;
+        let mut scenario: Self =
+            serde_json::from_slice(&data).context("Failed parsing the scenario file.")?;
+        for input in &scenario.inputs {
+            if let InputKind::Timer(line) = &input.kind {
+                timer_script::parse(line).map_err(|e| {
+                    anyhow::anyhow!("The timer input at tick {} is invalid: {e}", input.tick)
+                })?;
+            }
+        }
+        scenario.inputs.sort_by_key(|input| input.tick);
+        Ok(scenario)
+    }
+
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)
+    }
+}
+
+pub struct RecordedEvent {
+    pub tick: u64,
+    pub event: TimerEvent,
+    pub game_time: Option<time::Duration>,
+}
+
+/// The result of checking the recorded events against the scenario.
+pub struct Evaluation {
+    /// The outcome of each of the expected events.
+    pub outcomes: Vec<Outcome>,
+    /// The indices of the recorded events that weren't expected.
+    pub unexpected: Vec<usize>,
+}
+
+impl Evaluation {
+    pub fn passed(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|o| matches!(o, Outcome::Passed))
+            .count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|o| matches!(o, Outcome::Failed(_)))
+            .count()
+            + self.unexpected.len()
+    }
+
+    pub fn pending(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|o| matches!(o, Outcome::Pending))
+            .count()
+    }
+}
+
+pub enum Outcome {
+    Pending,
+    Passed,
+    Failed(String),
+}
+
+/// A scenario being checked against the timer events of the auto splitter.
+/// When recording, the events are only collected, so they can be saved as a
+/// golden run, together with the inputs that get replayed when checking the
+/// golden run.
+pub struct ScenarioRun {
+    pub scenario: Scenario,
+    start_tick: u64,
+    recording: bool,
+    pub events: Vec<RecordedEvent>,
+    /// The index of the next input to replay.
+    next_input: usize,
+}
+
+impl ScenarioRun {
+    pub fn new(scenario: Scenario, start_tick: u64) -> Self {
+        Self {
+            scenario,
+            start_tick,
+            recording: false,
+            events: Vec::new(),
+            next_input: 0,
+        }
+    }
+
+    /// Records the timer events without checking them against anything.
+    pub fn recording(start_tick: u64) -> Self {
+        Self {
+            recording: true,
+            ..Self::new(Scenario::default(), start_tick)
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Counts the ticks from the tick on, dropping the events recorded so far.
+    pub fn start(&mut self, tick: u64) {
+        self.start_tick = tick;
+        self.events.clear();
+        self.next_input = 0;
+    }
+
+    /// Takes the inputs to replay before the auto splitter updates in the
+    /// tick. Nothing is replayed when recording.
+    pub fn take_inputs(&mut self, tick: u64) -> Vec<InputKind> {
+        if self.recording {
+            return Vec::new();
+        }
+        let tick = tick.saturating_sub(self.start_tick);
+        let start = self.next_input;
+        while self
+            .scenario
+            .inputs
+            .get(self.next_input)
+            .is_some_and(|input| input.tick <= tick)
+        {
+            self.next_input += 1;
+        }
+        self.scenario.inputs[start..self.next_input]
+            .iter()
+            .map(|input| input.kind.clone())
+            .collect()
+    }
+
+    /// Records an input that happened after the tick, so it gets replayed
+    /// before the next one. Inputs are only recorded when recording.
+    pub fn record_input(&mut self, tick: u64, kind: InputKind) {
+        if self.recording {
+            self.scenario.inputs.push(Input {
+                tick: (tick + 1).saturating_sub(self.start_tick),
+                kind,
+            });
+        }
+    }
+
+    pub fn record(&mut self, tick: u64, event: TimerEvent, game_time: Option<time::Duration>) {
+        self.events.push(RecordedEvent {
+            tick: tick.saturating_sub(self.start_tick),
+            event,
+            game_time,
+        });
+    }
+
+    /// Checks the recorded events against the expected ones. The expected
+    /// events are matched in order with the recorded events of the same kind.
+    /// Nothing is checked when recording.
+    pub fn evaluate(&self, tick: u64) -> Evaluation {
+        if self.recording {
+            return Evaluation {
+                outcomes: Vec::new(),
+                unexpected: Vec::new(),
+            };
+        }
+        evaluate(
+            &self.scenario,
+            &self.events,
+            tick.saturating_sub(self.start_tick),
+        )
+    }
+
+    /// Checks the recorded events against the ones recorded by another run,
+    /// as if those were the expected events.
+    pub fn evaluate_against(
+        &self,
+        tick: u64,
+        other: &ScenarioRun,
+        tick_tolerance: u64,
+        game_time_tolerance: f64,
+    ) -> Evaluation {
+        let scenario = Scenario {
+            tick_tolerance,
+            game_time_tolerance,
+            ..other.to_golden()
+        };
+        evaluate(
+            &scenario,
+            &self.events,
+            tick.saturating_sub(self.start_tick),
+        )
+    }
+
+    /// Turns the recorded events into a scenario that expects the exact same
+    /// events, to compare later runs against.
+    pub fn to_golden(&self) -> Scenario {
+        Scenario {
+            tick_tolerance: self.scenario.tick_tolerance,
+            game_time_tolerance: self.scenario.game_time_tolerance,
+            events: self
+                .events
+                .iter()
+                .map(|e| ExpectedEvent {
+                    tick: e.tick,
+                    event: e.event,
+                    game_time: e.game_time.map(|t| t.as_seconds_f64()),
+                })
+                .collect(),
+            inputs: self.scenario.inputs.clone(),
+        }
+    }
+}
+
+/// Matches the expected events in order with the recorded events of the same
+/// kind. The ticks are relative to the start of the scenario.
+fn evaluate(scenario: &Scenario, events: &[RecordedEvent], tick: u64) -> Evaluation {
+    let tolerance = scenario.tick_tolerance;
+    let mut matched = vec![false; events.len()];
+    let mut next = 0;
+
+    let outcomes = scenario
+        .events
+        .iter()
+        .map(|expected| {
+            match events[next..]
+                .iter()
+                .position(|e| e.event == expected.event)
+            {
+                Some(index) => {
+                    let index = next + index;
+                    next = index + 1;
+                    matched[index] = true;
+                    let actual = &events[index];
+                    if actual.tick.abs_diff(expected.tick) > tolerance {
+                        return Outcome::Failed(format!("Happened at tick {}.", actual.tick));
+                    }
+                    let Some(expected_game_time) = expected.game_time else {
+                        return Outcome::Passed;
+                    };
+                    match actual.game_time {
+                        Some(game_time)
+                            if (game_time.as_seconds_f64() - expected_game_time).abs()
+                                <= scenario.game_time_tolerance =>
+                        {
+                            Outcome::Passed
+                        }
+                        Some(game_time) => Outcome::Failed(format!(
+                            "The game time was {:.3} s instead of {expected_game_time:.3} s.",
+                            game_time.as_seconds_f64(),
+                        )),
+                        None => Outcome::Failed("The game time wasn't set.".into()),
+                    }
+                }
+                None if tick > expected.tick + tolerance => {
+                    Outcome::Failed("Didn't happen.".into())
+                }
+                None => Outcome::Pending,
+            }
+        })
+        .collect();
+
+    Evaluation {
+        outcomes,
+        unexpected: (0..events.len()).filter(|&i| !matched[i]).collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_outcomes() {
+        let scenario: Scenario = serde_json::from_str(
+            r#"{
+                "tick_tolerance": 1,
+                "events": [
+                    { "tick": 1, "event": "start" },
+                    { "tick": 10, "event": "split" },
+                    { "tick": 20, "event": "split" },
+                    { "tick": 30, "event": "reset" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let mut run = ScenarioRun::new(scenario, 100);
+        run.record(101, TimerEvent::Start, None);
+        run.record(111, TimerEvent::Split, None);
+        run.record(125, TimerEvent::Split, None);
+
+        let evaluation = run.evaluate(125);
+        let outcomes = &evaluation.outcomes;
+        assert!(matches!(outcomes[0], Outcome::Passed));
+        assert!(matches!(outcomes[1], Outcome::Passed));
+        assert!(matches!(&outcomes[2], Outcome::Failed(m) if m == "Happened at tick 25."));
+        assert!(matches!(outcomes[3], Outcome::Pending));
+        assert!(evaluation.unexpected.is_empty());
+
+        assert!(matches!(run.evaluate(132).outcomes[3], Outcome::Failed(_)));
+    }
+
+    #[test]
+    fn test_golden() {
+        let mut recording = ScenarioRun::recording(0);
+        recording.record(1, TimerEvent::Start, None);
+        recording.record(50, TimerEvent::Split, Some(time::Duration::seconds(10)));
+        assert_eq!(recording.evaluate(50).failed(), 0);
+        let golden = recording.to_golden();
+        assert_eq!(golden.events.len(), 2);
+        assert_eq!(golden.events[1].game_time, Some(10.0));
+
+        let mut run = ScenarioRun::new(golden, 0);
+        run.record(1, TimerEvent::Start, None);
+        run.record(2, TimerEvent::Start, None);
+        run.record(50, TimerEvent::Split, Some(time::Duration::seconds(11)));
+
+        let evaluation = run.evaluate(50);
+        assert!(matches!(evaluation.outcomes[0], Outcome::Passed));
+        assert!(
+            matches!(&evaluation.outcomes[1], Outcome::Failed(m) if m.starts_with("The game time"))
+        );
+        assert_eq!(evaluation.unexpected, [1]);
+        assert_eq!(evaluation.failed(), 2);
+    }
+
+    #[test]
+    fn test_inputs() {
+        let mut recording = ScenarioRun::recording(100);
+        recording.record_input(104, InputKind::Timer("start".into()));
+        recording.record_input(109, InputKind::Timer("split".into()));
+        assert!(recording.take_inputs(105).is_empty());
+        let golden = recording.to_golden();
+        assert_eq!(golden.inputs.len(), 2);
+        assert_eq!(golden.inputs[0].tick, 5);
+
+        let json = serde_json::to_string(&golden).unwrap();
+        assert!(json.contains(r#"{"tick":5,"timer":"start"}"#));
+        let golden: Scenario = serde_json::from_str(&json).unwrap();
+
+        let mut run = ScenarioRun::new(golden, 200);
+        assert!(run.take_inputs(204).is_empty());
+        assert_eq!(run.take_inputs(205), [InputKind::Timer("start".into())]);
+        assert!(run.take_inputs(206).is_empty());
+        assert_eq!(run.take_inputs(220), [InputKind::Timer("split".into())]);
+        run.start(300);
+        assert_eq!(run.take_inputs(320).len(), 2);
+    }
+
+    #[test]
+    fn test_evaluate_against() {
+        let mut optimized = ScenarioRun::new(Scenario::default(), 100);
+        optimized.record(101, TimerEvent::Start, None);
+        optimized.record(110, TimerEvent::Split, None);
+        optimized.record(120, TimerEvent::Split, None);
+
+        let mut unoptimized = ScenarioRun::new(Scenario::default(), 200);
+        unoptimized.record(202, TimerEvent::Start, None);
+        unoptimized.record(230, TimerEvent::Split, None);
+
+        let evaluation = unoptimized.evaluate_against(230, &optimized, 2, 0.0);
+        assert!(matches!(evaluation.outcomes[0], Outcome::Passed));
+        assert!(matches!(evaluation.outcomes[1], Outcome::Failed(_)));
+        assert!(matches!(evaluation.outcomes[2], Outcome::Failed(_)));
+        assert!(evaluation.unexpected.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+use egui_dock::DockState;
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Serialize};
+
+use crate::Tab;
+
+pub const FILE_EXTENSION: &str = "asrdbg";
+
+/// Everything needed to continue investigating an auto splitter later, stored
+/// as JSON in a `.asrdbg` file.
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    /// The layout of the tabs. Sessions saved without a user interface don't
+    /// have one.
+    #[serde(default)]
+    pub dock_state: Option<DockState<Tab>>,
+    pub path: Option<PathBuf>,
+    pub script_path: Option<PathBuf>,
+    /// The settings map of the auto splitter, in the same JSON representation
+    /// as in crash reports.
+    #[serde(default)]
+    pub settings_map: Option<serde_json::Value>,
+    #[serde(default)]
+    pub pinned_variables: Vec<Box<str>>,
+    #[serde(default)]
+    pub statistics: Statistics,
+    #[serde(default)]
+    pub logs: Vec<Log>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Statistics {
+    /// The recorded tick times in nanoseconds and how many ticks took that
+    /// long.
+    pub tick_times: Vec<(u64, u64)>,
+    pub slowest_tick: Duration,
+    pub missed_ticks: u64,
+    pub peak_memory_usage: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Log {
+    /// The Unix timestamp in milliseconds.
+    pub time: i64,
+    pub tick: u64,
+    pub level: Box<str>,
+    pub source: Box<str>,
+    pub message: Box<str>,
+    pub count: u32,
+}
+
+impl Session {
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let data = fs::read(path).context("Failed reading the session file.")?;
+        serde_json::from_slice(&data).context("Failed parsing the session file.")
+    }
+
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()
+    }
+}
+
+impl Statistics {
+    pub fn record_tick_times(&mut self, histogram: &Histogram<u64>) {
+        self.tick_times = histogram
+            .iter_recorded()
+            .map(|bucket| (bucket.value_iterated_to(), bucket.count_at_value()))
+            .collect();
+    }
+
+    pub fn restore_tick_times(&self, histogram: &mut Histogram<u64>) {
+        histogram.clear();
+        for &(value, count) in &self.tick_times {
+            // Invalid values in a modified session file are skipped.
+            let _ = histogram.record_n(value, count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tick_times() {
+        let mut histogram = Histogram::<u64>::new(3).unwrap();
+        histogram.record_n(1_000, 5).unwrap();
+        histogram.record(250_000).unwrap();
+
+        let mut statistics = Statistics::default();
+        statistics.record_tick_times(&histogram);
+        let json = serde_json::to_string(&statistics).unwrap();
+        let statistics: Statistics = serde_json::from_str(&json).unwrap();
+
+        let mut restored = Histogram::<u64>::new(3).unwrap();
+        statistics.restore_tick_times(&mut restored);
+        assert_eq!(restored.len(), 6);
+        assert_eq!(
+            restored.value_at_quantile(0.5),
+            histogram.value_at_quantile(0.5)
+        );
+        assert_eq!(restored.max(), histogram.max());
+    }
+}
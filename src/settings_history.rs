@@ -0,0 +1,158 @@
+use std::collections::VecDeque;
+
+use livesplit_auto_splitting::{settings, time::OffsetDateTime};
+
+use crate::settings_json::settings_map_to_json;
+
+/// How many changes are kept before the oldest ones get discarded.
+const CAPACITY: usize = 1000;
+
+/// Who changed the settings map.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Origin {
+    SettingsGui,
+    SettingsMap,
+    HttpApi,
+    /// The debugger itself, like when restoring a session.
+    Debugger,
+    AutoSplitter,
+}
+
+impl Origin {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Origin::SettingsGui => "Settings GUI",
+            Origin::SettingsMap => "Settings Map",
+            Origin::HttpApi => "HTTP API",
+            Origin::Debugger => "Debugger",
+            Origin::AutoSplitter => "Auto Splitter",
+        }
+    }
+}
+
+/// A single setting that got changed. The values are shown as JSON.
+pub struct Change {
+    pub tick: u64,
+    pub time: OffsetDateTime,
+    pub origin: Origin,
+    pub key: Box<str>,
+    /// The value before the change, if the setting was set.
+    pub before: Option<Box<str>>,
+    /// The value after the change, if the setting is still set.
+    pub after: Option<Box<str>>,
+}
+
+/// Every change of the settings map, whether the debugger or the auto
+/// splitter itself made it.
+#[derive(Default)]
+pub struct SettingsHistory {
+    /// The settings map as of the last recorded change.
+    known: Option<settings::Map>,
+    pub changes: VecDeque<Change>,
+    /// How many changes the auto splitter made to its own settings since the
+    /// user last dismissed the notification about them.
+    pub unseen_self_changes: usize,
+}
+
+impl SettingsHistory {
+    /// Starts comparing against the settings map of a newly started auto
+    /// splitter, without recording its initial settings as a change.
+    pub fn reset(&mut self, settings_map: Option<settings::Map>) {
+        self.known = settings_map;
+    }
+
+    /// Records how the settings map differs from the last recorded one.
+    /// Returns how many settings changed. Those are the last entries of the
+    /// changes, as far as they fit into the history.
+    pub fn record(
+        &mut self,
+        settings_map: settings::Map,
+        origin: Origin,
+        tick: u64,
+        time: OffsetDateTime,
+    ) -> usize {
+        let Some(known) = &self.known else {
+            self.known = Some(settings_map);
+            return 0;
+        };
+        if settings_map.is_unchanged(known) {
+            return 0;
+        }
+
+        let (before, after) = (
+            settings_map_to_json(known),
+            settings_map_to_json(&settings_map),
+        );
+        let mut changed = 0;
+        for (key, before, after) in diff(&before, &after) {
+            if self.changes.len() >= CAPACITY {
+                self.changes.pop_front();
+            }
+            self.changes.push_back(Change {
+                tick,
+                time,
+                origin,
+                key: key.into(),
+                before: before.map(|v| v.to_string().into()),
+                after: after.map(|v| v.to_string().into()),
+            });
+            changed += 1;
+        }
+        if origin == Origin::AutoSplitter {
+            self.unseen_self_changes += changed;
+        }
+        self.known = Some(settings_map);
+        changed.min(self.changes.len())
+    }
+}
+
+/// The top level keys whose values differ between the two JSON objects, with
+/// the values before and after.
+fn diff<'a>(
+    before: &'a serde_json::Value,
+    after: &'a serde_json::Value,
+) -> Vec<(
+    &'a str,
+    Option<&'a serde_json::Value>,
+    Option<&'a serde_json::Value>,
+)> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+    let changed = after
+        .iter()
+        .filter(|&(key, value)| before.get(key) != Some(value))
+        .map(|(key, value)| (key.as_str(), before.get(key), Some(value)));
+    let removed = before
+        .iter()
+        .filter(|(key, _)| !after.contains_key(*key))
+        .map(|(key, value)| (key.as_str(), Some(value), None));
+    changed.chain(removed).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let before = serde_json::json!({ "a": true, "b": 1, "c": "x" });
+        let after = serde_json::json!({ "a": true, "b": 2, "d": [] });
+        let (one, two, x, empty) = (
+            serde_json::json!(1),
+            serde_json::json!(2),
+            serde_json::json!("x"),
+            serde_json::json!([]),
+        );
+
+        assert_eq!(
+            diff(&before, &after),
+            [
+                ("b", Some(&one), Some(&two)),
+                ("d", None, Some(&empty)),
+                ("c", Some(&x), None),
+            ],
+        );
+        assert!(diff(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use livesplit_auto_splitting::settings;
+
+/// Turns a settings map into its JSON representation, which is how the
+/// debugger saves and exposes it. Values JSON has no equivalent for become
+/// `null`.
+pub fn settings_map_to_json(settings_map: &settings::Map) -> serde_json::Value {
+    settings_map
+        .iter()
+        .map(|(key, value)| (key.to_owned(), value_to_json(value)))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+fn value_to_json(value: &settings::Value) -> serde_json::Value {
+    match value {
+        settings::Value::Map(v) => settings_map_to_json(v),
+        settings::Value::List(v) => v.iter().map(value_to_json).collect(),
+        settings::Value::Bool(v) => (*v).into(),
+        settings::Value::I64(v) => (*v).into(),
+        settings::Value::F64(v) => (*v).into(),
+        settings::Value::String(v) => (**v).into(),
+        _ => serde_json::Value::Null,
+    }
+}
+
+/// Turns the JSON representation of a settings map back into a settings map.
+/// Values that have no equivalent in the settings map, like `null`, are
+/// skipped.
+pub fn settings_map_from_json(json: &serde_json::Map<String, serde_json::Value>) -> settings::Map {
+    let mut settings_map = settings::Map::new();
+    for (key, value) in json {
+        if let Some(value) = value_from_json(value) {
+            settings_map.insert(Arc::from(key.as_str()), value);
+        }
+    }
+    settings_map
+}
+
+fn value_from_json(value: &serde_json::Value) -> Option<settings::Value> {
+    Some(match value {
+        serde_json::Value::Object(v) => settings::Value::Map(settings_map_from_json(v)),
+        serde_json::Value::Array(v) => {
+            let mut list = settings::List::new();
+            for value in v.iter().filter_map(value_from_json) {
+                list.push(value);
+            }
+            settings::Value::List(list)
+        }
+        serde_json::Value::Bool(v) => settings::Value::Bool(*v),
+        serde_json::Value::Number(v) => match v.as_i64() {
+            Some(v) => settings::Value::I64(v),
+            None => settings::Value::F64(v.as_f64()?),
+        },
+        serde_json::Value::String(v) => settings::Value::String(v.as_str().into()),
+        serde_json::Value::Null => return None,
+    })
+}
@@ -0,0 +1,142 @@
+use std::{
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use read_process_memory::ProcessHandle;
+
+/// How many bytes of a memory range are read at once.
+const CHUNK_SIZE: usize = 1 << 20;
+/// The scan stops after this many matches, as a signature that matches that
+/// often is useless anyway.
+const MAX_MATCHES: usize = 100;
+
+/// A byte pattern where `None` matches any byte.
+pub struct Signature(Vec<Option<u8>>);
+
+impl Signature {
+    /// Parses hexadecimal bytes separated by whitespace, with `?` or `??` as
+    /// wildcards, like `48 8B 05 ?? ?? ?? ??`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let bytes = text
+            .split_whitespace()
+            .map(|byte| match byte {
+                "?" | "??" => Some(None),
+                _ if byte.len() == 2 => u8::from_str_radix(byte, 16).ok().map(Some),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        // A signature of only wildcards matches everywhere.
+        bytes.iter().any(Option::is_some).then_some(Self(bytes))
+    }
+
+    fn matches_at(&self, haystack: &[u8]) -> bool {
+        self.0
+            .iter()
+            .zip(haystack)
+            .all(|(expected, actual)| expected.is_none_or(|expected| expected == *actual))
+    }
+
+    /// The offsets at which the signature matches.
+    fn find_all<'a>(&'a self, haystack: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        let len = self.0.len();
+        (0..(haystack.len() + 1).saturating_sub(len))
+            .filter(move |&offset| self.matches_at(&haystack[offset..offset + len]))
+    }
+}
+
+pub struct ScanResult {
+    pub matches: Vec<u64>,
+    pub scanned_bytes: u64,
+    pub duration: Duration,
+}
+
+/// Scans the given memory ranges of a process on a separate thread. Ranges
+/// that can't be read are skipped.
+pub fn start(
+    pid: u64,
+    signature: Signature,
+    ranges: Vec<(u64, u64)>,
+) -> JoinHandle<Result<ScanResult, String>> {
+    thread::spawn(move || {
+        let handle = ProcessHandle::try_from(pid as read_process_memory::Pid)
+            .map_err(|e| format!("Failed to open the process: {e}"))?;
+        let start = Instant::now();
+        let mut matches = Vec::new();
+        let mut scanned_bytes = 0;
+        let overlap = signature.0.len() as u64 - 1;
+
+        'ranges: for (range_start, range_size) in ranges {
+            let range_end = range_start + range_size;
+            let mut chunk_start = range_start;
+            while chunk_start < range_end {
+                // The chunks overlap by one byte less than the signature, so
+                // matches crossing them are found exactly once.
+                let chunk_end = (chunk_start + CHUNK_SIZE as u64 + overlap).min(range_end);
+                let Ok(chunk) = read_process_memory::copy_address(
+                    chunk_start as usize,
+                    (chunk_end - chunk_start) as usize,
+                    &handle,
+                ) else {
+                    continue 'ranges;
+                };
+                scanned_bytes += chunk.len() as u64;
+                for offset in signature.find_all(&chunk) {
+                    matches.push(chunk_start + offset as u64);
+                    if matches.len() >= MAX_MATCHES {
+                        break 'ranges;
+                    }
+                }
+                chunk_start += CHUNK_SIZE as u64;
+            }
+        }
+
+        Ok(ScanResult {
+            matches,
+            scanned_bytes,
+            duration: start.elapsed(),
+        })
+    })
+}
+
+/// The signature scan that is being set up in the Processes tab.
+#[derive(Default)]
+pub struct SigScan {
+    pub pid: Option<u64>,
+    /// The module to scan. The whole process gets scanned if it's empty.
+    pub module: String,
+    pub signature: String,
+    pub running: Option<JoinHandle<Result<ScanResult, String>>>,
+    pub result: Option<Result<ScanResult, String>>,
+}
+
+impl SigScan {
+    /// Takes the result of the scan once it finished.
+    pub fn poll(&mut self) {
+        if self.running.as_ref().is_some_and(JoinHandle::is_finished) {
+            self.result = self.running.take().map(|thread| {
+                thread
+                    .join()
+                    .unwrap_or_else(|_| Err("The scan panicked.".into()))
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_signature() {
+        let signature = Signature::parse("48 8B ?? ? 05").unwrap();
+        let haystack = [
+            0x00, 0x48, 0x8B, 0x12, 0x34, 0x05, 0x48, 0x8B, 0x00, 0x00, 0x05,
+        ];
+        assert_eq!(signature.find_all(&haystack).collect::<Vec<_>>(), [1, 6]);
+
+        assert!(Signature::parse("?? ??").is_none());
+        assert!(Signature::parse("48 8G").is_none());
+        assert!(Signature::parse("488B").is_none());
+    }
+}
@@ -0,0 +1,63 @@
+/// A transition between two states, caused by a specific action. Actions
+/// that don't change the state are transitions from a state to itself.
+pub struct Transition {
+    pub from: &'static str,
+    pub action: &'static str,
+    pub to: &'static str,
+    pub count: u64,
+}
+
+/// How often each of the transitions between the states of a state machine
+/// was observed.
+#[derive(Default)]
+pub struct Transitions {
+    pub edges: Vec<Transition>,
+}
+
+impl Transitions {
+    pub fn record(&mut self, from: &'static str, action: &'static str, to: &'static str) {
+        match self
+            .edges
+            .iter_mut()
+            .find(|edge| edge.from == from && edge.action == action && edge.to == to)
+        {
+            Some(edge) => edge.count += 1,
+            None => self.edges.push(Transition {
+                from,
+                action,
+                to,
+                count: 1,
+            }),
+        }
+    }
+
+    /// How often the state went from one state to the other, regardless of
+    /// the action.
+    pub fn between(&self, from: &str, to: &str) -> u64 {
+        self.edges
+            .iter()
+            .filter(|edge| edge.from == from && edge.to == to)
+            .map(|edge| edge.count)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut transitions = Transitions::default();
+        transitions.record("Not running", "start", "Running");
+        transitions.record("Running", "start", "Running");
+        transitions.record("Running", "start", "Running");
+        transitions.record("Running", "reset", "Not running");
+        transitions.record("Running", "split", "Running");
+
+        assert_eq!(transitions.edges.len(), 4);
+        assert_eq!(transitions.between("Running", "Running"), 3);
+        assert_eq!(transitions.between("Not running", "Running"), 1);
+        assert_eq!(transitions.between("Running", "Ended"), 0);
+    }
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// How many sessions are kept per auto splitter.
+const CAPACITY: usize = 100;
+
+/// The statistics of a single build of an auto splitter, measured after it
+/// warmed up.
+#[derive(Serialize, Deserialize)]
+pub struct Entry {
+    /// When the statistics were measured, as a Unix timestamp.
+    pub date: i64,
+    /// Distinguishes the builds of the auto splitter.
+    pub module_hash: u64,
+    pub p50: Duration,
+    pub p99: Duration,
+    pub peak_memory: u64,
+}
+
+pub fn record(history: &mut Vec<Entry>, entry: Entry) {
+    if history.len() >= CAPACITY {
+        history.remove(0);
+    }
+    history.push(entry);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_capacity() {
+        let mut history = Vec::new();
+        for date in 0..CAPACITY as i64 + 5 {
+            record(
+                &mut history,
+                Entry {
+                    date,
+                    module_hash: 0,
+                    p50: Duration::ZERO,
+                    p99: Duration::ZERO,
+                    peak_memory: 0,
+                },
+            );
+        }
+        assert_eq!(history.len(), CAPACITY);
+        assert_eq!(history[0].date, 5);
+    }
+}
@@ -0,0 +1,200 @@
+use livesplit_auto_splitting::{time, TimerState};
+
+/// How many attempts in a row need to be started right after being reset
+/// before it's reported. A single one may just be a quick restart by the
+/// runner.
+const RESET_START_REPEATS: u32 = 3;
+
+/// A timer action or a sequence of them that is likely a logic bug in the
+/// auto splitter.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Pattern {
+    SplitWhileNotRunning,
+    StartWhileRunning,
+    StartRightAfterReset,
+    UndoBeforeFirstSplit,
+    GameTimeDecreased,
+}
+
+pub const PATTERNS: [Pattern; 5] = [
+    Pattern::SplitWhileNotRunning,
+    Pattern::StartWhileRunning,
+    Pattern::StartRightAfterReset,
+    Pattern::UndoBeforeFirstSplit,
+    Pattern::GameTimeDecreased,
+];
+
+impl Pattern {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Pattern::SplitWhileNotRunning => "Split While Not Running",
+            Pattern::StartWhileRunning => "Start While Running",
+            Pattern::StartRightAfterReset => "Start Right After Reset",
+            Pattern::UndoBeforeFirstSplit => "Undo Before First Split",
+            Pattern::GameTimeDecreased => "Game Time Decreased",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Pattern::SplitWhileNotRunning => {
+                "The auto splitter split while the timer wasn't running."
+            }
+            Pattern::StartWhileRunning => {
+                "The auto splitter started the timer while it was already running."
+            }
+            Pattern::StartRightAfterReset => {
+                "The auto splitter repeatedly started the timer right after resetting it, so the start and reset conditions are likely both true."
+            }
+            Pattern::UndoBeforeFirstSplit => {
+                "The auto splitter undid a split before there was any split to undo."
+            }
+            Pattern::GameTimeDecreased => "The auto splitter set the game time to an earlier time.",
+        }
+    }
+}
+
+/// Counts the suspicious timer actions of the auto splitter.
+#[derive(Default)]
+pub struct SuspiciousActions {
+    counts: [u64; PATTERNS.len()],
+    last_ticks: [Option<u64>; PATTERNS.len()],
+    /// The tick in which the auto splitter last reset a run.
+    last_reset: Option<u64>,
+    /// How many of the last attempts in a row were started right after being
+    /// reset.
+    reset_starts: u32,
+}
+
+impl SuspiciousActions {
+    pub fn count(&self, pattern: Pattern) -> u64 {
+        self.counts[pattern as usize]
+    }
+
+    pub fn last_tick(&self, pattern: Pattern) -> Option<u64> {
+        self.last_ticks[pattern as usize]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn detected(&mut self, pattern: Pattern, tick: u64) -> Option<Pattern> {
+        self.counts[pattern as usize] += 1;
+        self.last_ticks[pattern as usize] = Some(tick);
+        Some(pattern)
+    }
+
+    pub fn start(&mut self, tick: u64, state: TimerState) -> Option<Pattern> {
+        if state != TimerState::NotRunning {
+            return self.detected(Pattern::StartWhileRunning, tick);
+        }
+        // Resetting and starting in the same or the very next tick over and
+        // over means the run can never stay reset.
+        if self
+            .last_reset
+            .is_some_and(|reset| tick.saturating_sub(reset) <= 1)
+        {
+            self.reset_starts += 1;
+            if self.reset_starts >= RESET_START_REPEATS {
+                return self.detected(Pattern::StartRightAfterReset, tick);
+            }
+        } else {
+            self.reset_starts = 0;
+        }
+        None
+    }
+
+    pub fn split(&mut self, tick: u64, state: TimerState) -> Option<Pattern> {
+        if state != TimerState::Running {
+            return self.detected(Pattern::SplitWhileNotRunning, tick);
+        }
+        None
+    }
+
+    pub fn undo_split(
+        &mut self,
+        tick: u64,
+        state: TimerState,
+        split_index: usize,
+    ) -> Option<Pattern> {
+        if state != TimerState::NotRunning && split_index == 0 {
+            return self.detected(Pattern::UndoBeforeFirstSplit, tick);
+        }
+        None
+    }
+
+    pub fn reset(&mut self, tick: u64, state: TimerState) {
+        if state != TimerState::NotRunning {
+            self.last_reset = Some(tick);
+        }
+    }
+
+    /// The game time before is only known if it was initialized.
+    pub fn set_game_time(
+        &mut self,
+        tick: u64,
+        before: Option<time::Duration>,
+        after: time::Duration,
+    ) -> Option<Pattern> {
+        if before.is_some_and(|before| after < before) {
+            return self.detected(Pattern::GameTimeDecreased, tick);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_patterns() {
+        let mut actions = SuspiciousActions::default();
+        assert_eq!(actions.start(1, TimerState::NotRunning), None);
+        assert_eq!(actions.split(2, TimerState::Running), None);
+        assert_eq!(
+            actions.split(3, TimerState::NotRunning),
+            Some(Pattern::SplitWhileNotRunning),
+        );
+        assert_eq!(
+            actions.start(4, TimerState::Running),
+            Some(Pattern::StartWhileRunning),
+        );
+        assert_eq!(
+            actions.undo_split(5, TimerState::Running, 0),
+            Some(Pattern::UndoBeforeFirstSplit),
+        );
+        assert_eq!(actions.undo_split(5, TimerState::NotRunning, 0), None);
+
+        actions.reset(10, TimerState::Running);
+        assert_eq!(actions.start(11, TimerState::NotRunning), None);
+        actions.reset(12, TimerState::Running);
+        assert_eq!(actions.start(12, TimerState::NotRunning), None);
+        actions.reset(13, TimerState::Running);
+        assert_eq!(
+            actions.start(14, TimerState::NotRunning),
+            Some(Pattern::StartRightAfterReset),
+        );
+        actions.reset(20, TimerState::Running);
+        assert_eq!(actions.start(30, TimerState::NotRunning), None);
+        actions.reset(31, TimerState::Running);
+        assert_eq!(actions.start(32, TimerState::NotRunning), None);
+        assert_eq!(actions.last_tick(Pattern::StartRightAfterReset), Some(14));
+
+        let second = time::Duration::SECOND;
+        assert_eq!(actions.set_game_time(40, None, second), None);
+        assert_eq!(actions.set_game_time(41, Some(second), 2 * second), None);
+        assert_eq!(
+            actions.set_game_time(42, Some(second), second / 2),
+            Some(Pattern::GameTimeDecreased),
+        );
+
+        assert_eq!(actions.total(), 5);
+        assert_eq!(actions.last_tick(Pattern::GameTimeDecreased), Some(42));
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::Context;
+use opentelemetry::{trace::TracerProvider as _, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace::TracerProvider, Resource};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// Exports the spans of the debugger to an OTLP collector until it is
+/// dropped.
+pub struct Telemetry {
+    provider: TracerProvider,
+    // The exporter sends the spans in the background on this runtime.
+    _runtime: tokio::runtime::Runtime,
+}
+
+impl Telemetry {
+    pub fn start(endpoint: &str) -> anyhow::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .context("Failed starting the runtime for the OTLP exporter.")?;
+        let _guard = runtime.enter();
+
+        let provider = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(endpoint),
+            )
+            .with_trace_config(opentelemetry_sdk::trace::Config::default().with_resource(
+                Resource::new([KeyValue::new("service.name", "asr-debugger")]),
+            ))
+            .install_batch(runtime::Tokio)
+            .context("Failed setting up the OTLP exporter.")?;
+
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("asr-debugger")))
+            .try_init()
+            .context("Failed registering the tracing subscriber.")?;
+
+        Ok(Self {
+            provider,
+            _runtime: runtime,
+        })
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        // Flushes the spans that haven't been exported yet.
+        let _ = self.provider.shutdown();
+    }
+}
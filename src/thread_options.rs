@@ -0,0 +1,29 @@
+use anyhow::Context;
+use thread_priority::ThreadPriority;
+
+/// How the operating system schedules the runtime thread. Raising its
+/// priority or pinning it to a core keeps the tick times from being skewed
+/// when the game saturates the CPU.
+#[derive(Copy, Clone, Default)]
+pub struct ThreadOptions {
+    pub high_priority: bool,
+    /// The index of the CPU core to run the thread on.
+    pub core: Option<usize>,
+}
+
+pub fn raise_priority() -> anyhow::Result<()> {
+    thread_priority::set_current_thread_priority(ThreadPriority::Max)
+        .map_err(|e| anyhow::anyhow!("{e:?}"))
+        .context("Failed raising the priority of the runtime thread.")
+}
+
+pub fn pin_to_core(index: usize) -> anyhow::Result<()> {
+    let core = core_affinity::get_core_ids()
+        .and_then(|cores| cores.into_iter().nth(index))
+        .with_context(|| format!("There is no CPU core {index} to pin the runtime thread to."))?;
+    anyhow::ensure!(
+        core_affinity::set_for_current(core),
+        "Failed pinning the runtime thread to CPU core {index}.",
+    );
+    Ok(())
+}
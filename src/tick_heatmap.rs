@@ -0,0 +1,107 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// The amount of tick time buckets. Each bucket covers twice the durations of
+/// the one below it, starting at 1 µs, so the last one covers everything
+/// above half a second.
+pub const BUCKETS: usize = 20;
+/// How long the wall clock time covered by a single column is.
+pub const COLUMN_DURATION: Duration = Duration::from_secs(1);
+/// How many columns are kept, so the last 10 minutes are shown.
+const CAPACITY: usize = 600;
+
+pub struct Column {
+    /// The index of the column, counted from when the heatmap got started.
+    pub index: u64,
+    pub counts: [u32; BUCKETS],
+}
+
+/// Counts the tick times per bucket over the wall clock time, so slowdowns
+/// that happen periodically show up as stripes.
+#[derive(Default)]
+pub struct TickHeatmap {
+    start: Option<Instant>,
+    pub columns: VecDeque<Column>,
+}
+
+impl TickHeatmap {
+    pub fn record(&mut self, now: Instant, tick_time: Duration) {
+        let start = *self.start.get_or_insert(now);
+        let index = (now.duration_since(start).as_nanos() / COLUMN_DURATION.as_nanos()) as u64;
+        if self.columns.back().map(|column| column.index) != Some(index) {
+            if self.columns.len() >= CAPACITY {
+                self.columns.pop_front();
+            }
+            self.columns.push_back(Column {
+                index,
+                counts: [0; BUCKETS],
+            });
+        }
+        let column = self.columns.back_mut().unwrap();
+        column.counts[bucket(tick_time)] += 1;
+    }
+
+    /// The highest count of any of the cells.
+    pub fn max_count(&self) -> u32 {
+        self.columns
+            .iter()
+            .flat_map(|column| column.counts)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+pub fn bucket(tick_time: Duration) -> usize {
+    let micros = tick_time.as_micros().max(1);
+    (micros.ilog2() as usize).min(BUCKETS - 1)
+}
+
+/// The lower bound of the tick times in the bucket.
+pub fn bucket_start(bucket: usize) -> Duration {
+    if bucket == 0 {
+        Duration::ZERO
+    } else {
+        Duration::from_micros(1 << bucket)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        assert_eq!(bucket(Duration::ZERO), 0);
+        assert_eq!(bucket(Duration::from_micros(3)), 1);
+        assert_eq!(bucket(Duration::from_millis(1)), 9);
+        assert_eq!(bucket(Duration::from_secs(10)), BUCKETS - 1);
+        assert_eq!(
+            bucket_start(bucket(Duration::from_millis(1))),
+            Duration::from_micros(512)
+        );
+
+        let mut heatmap = TickHeatmap::default();
+        let start = Instant::now();
+        for i in 0..20 {
+            heatmap.record(
+                start + Duration::from_millis(i * 100),
+                Duration::from_micros(100),
+            );
+        }
+        heatmap.record(start + Duration::from_secs(10), Duration::from_millis(50));
+
+        assert_eq!(heatmap.columns.len(), 3);
+        assert_eq!(
+            heatmap.columns[0].counts[bucket(Duration::from_micros(100))],
+            10
+        );
+        assert_eq!(heatmap.columns[2].index, 10);
+        assert_eq!(heatmap.max_count(), 10);
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The most time spent spinning before a tick. This keeps systems with a
+/// coarse timer resolution from spinning for most of the tick.
+const MAX_SPIN_DURATION: Duration = Duration::from_millis(4);
+
+/// What to do about the ticks that couldn't happen on time, because a tick or
+/// the whole system took too long.
+#[derive(Copy, Clone, PartialEq)]
+pub enum CatchUp {
+    /// Skips the missed ticks and continues with the next one.
+    Skip,
+    /// Runs all the missed ticks back to back.
+    All,
+    /// Runs the missed ticks back to back, but skips the ones that are more
+    /// than this many ticks behind.
+    Capped(u32),
+}
+
+/// Decides how to continue when the next tick is already overdue. Returns
+/// when the next tick should happen and how many ticks got skipped.
+pub fn catch_up(
+    policy: CatchUp,
+    next_tick: Instant,
+    now: Instant,
+    tick_rate: Duration,
+) -> (Instant, u32) {
+    let behind = now.saturating_duration_since(next_tick);
+    // The ticks that were due before now, besides the next one.
+    let overdue = (behind.as_nanos() / tick_rate.as_nanos().max(1)).min(u32::MAX as u128) as u32;
+    let skipped = match policy {
+        CatchUp::Skip => overdue,
+        CatchUp::All => 0,
+        CatchUp::Capped(max) => overdue.saturating_sub(max),
+    };
+    (next_tick + tick_rate * skipped, skipped)
+}
+
+/// Waits for the next tick more precisely than sleeping alone. The operating
+/// system usually wakes a sleeping thread up late, which at high tick rates
+/// adds up to a noticeable drift. So the scheduler learns by how much the
+/// sleeps overshoot, sleeps for less than that, and spins for the rest.
+pub struct TickScheduler {
+    oversleep: Duration,
+}
+
+impl Default for TickScheduler {
+    fn default() -> Self {
+        Self {
+            oversleep: Duration::from_millis(1),
+        }
+    }
+}
+
+impl TickScheduler {
+    pub fn sleep_until(&mut self, deadline: Instant) {
+        let start = Instant::now();
+        let Some(remaining) = deadline.checked_duration_since(start) else {
+            return;
+        };
+
+        let sleep_time = remaining.saturating_sub(self.oversleep);
+        if !sleep_time.is_zero() {
+            thread::sleep(sleep_time);
+            self.record_oversleep(start.elapsed().saturating_sub(sleep_time));
+        }
+
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+    }
+
+    /// Adapts to longer oversleeps right away, so the following ticks aren't
+    /// late, but only slowly to shorter ones, so a single sleep that happens
+    /// to be precise doesn't make the next ones late either.
+    fn record_oversleep(&mut self, oversleep: Duration) {
+        self.oversleep = if oversleep > self.oversleep {
+            oversleep
+        } else {
+            (self.oversleep * 31 + oversleep) / 32
+        }
+        .min(MAX_SPIN_DURATION);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record_oversleep() {
+        let mut scheduler = TickScheduler::default();
+
+        scheduler.record_oversleep(Duration::from_millis(2));
+        assert_eq!(scheduler.oversleep, Duration::from_millis(2));
+
+        scheduler.record_oversleep(Duration::ZERO);
+        assert!(scheduler.oversleep < Duration::from_millis(2));
+        assert!(scheduler.oversleep > Duration::from_millis(1));
+
+        scheduler.record_oversleep(Duration::from_millis(15));
+        assert_eq!(scheduler.oversleep, MAX_SPIN_DURATION);
+    }
+
+    #[test]
+    fn test_catch_up() {
+        let tick_rate = Duration::from_millis(10);
+        let next_tick = Instant::now();
+        let now = next_tick + Duration::from_millis(55);
+
+        assert_eq!(
+            catch_up(CatchUp::Skip, next_tick, now, tick_rate),
+            (next_tick + Duration::from_millis(50), 5),
+        );
+        assert_eq!(
+            catch_up(CatchUp::All, next_tick, now, tick_rate),
+            (next_tick, 0),
+        );
+        assert_eq!(
+            catch_up(CatchUp::Capped(2), next_tick, now, tick_rate),
+            (next_tick + Duration::from_millis(30), 3),
+        );
+        assert_eq!(
+            catch_up(CatchUp::Capped(10), next_tick, now, tick_rate),
+            (next_tick, 0),
+        );
+    }
+}
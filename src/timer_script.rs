@@ -0,0 +1,199 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+/// A timer action performed by the user instead of the auto splitter.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Start,
+    Split,
+    SkipSplit,
+    UndoSplit,
+    Reset,
+    PauseGameTime,
+    ResumeGameTime,
+    SetGameTime(time::Duration),
+    Wait(std::time::Duration),
+}
+
+impl Command {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Command::Start => "start",
+            Command::Split => "split",
+            Command::SkipSplit => "skip_split",
+            Command::UndoSplit => "undo_split",
+            Command::Reset => "reset",
+            Command::PauseGameTime => "pause_game_time",
+            Command::ResumeGameTime => "resume_game_time",
+            Command::SetGameTime(_) => "set_game_time",
+            Command::Wait(_) => "wait",
+        }
+    }
+}
+
+/// Writes the command as a line of a script.
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())?;
+        match self {
+            Command::SetGameTime(time) => write!(f, " {}s", time.as_seconds_f64()),
+            Command::Wait(duration) => write!(f, " {}s", duration.as_secs_f64()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parses a script with a command per line, like `set_game_time 1:23.456`
+/// or `wait 500ms`. Empty lines and lines starting with `#` are ignored.
+pub fn parse(script: &str) -> Result<Vec<Command>, String> {
+    let mut commands = Vec::new();
+    for (index, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, argument) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let argument = argument.trim();
+        let duration = || {
+            parse_duration(argument)
+                .ok_or_else(|| format!("Line {}: Invalid duration \"{argument}\".", index + 1))
+        };
+        let command = match name {
+            "start" => Command::Start,
+            "split" => Command::Split,
+            "skip_split" => Command::SkipSplit,
+            "undo_split" => Command::UndoSplit,
+            "reset" => Command::Reset,
+            "pause_game_time" => Command::PauseGameTime,
+            "resume_game_time" => Command::ResumeGameTime,
+            "set_game_time" => Command::SetGameTime(duration()?),
+            "wait" => Command::Wait(
+                duration()?
+                    .try_into()
+                    .map_err(|_| format!("Line {}: Can't wait a negative duration.", index + 1))?,
+            ),
+            _ => return Err(format!("Line {}: Unknown command \"{name}\".", index + 1)),
+        };
+        commands.push(command);
+    }
+    Ok(commands)
+}
+
+/// Parses durations like `500ms`, `1.5s` or `1:23.456`, where the parts
+/// separated by colons are hours, minutes and seconds.
+pub fn parse_duration(text: &str) -> Option<time::Duration> {
+    // Rounding to whole nanoseconds avoids durations like 83.455999999s.
+    let duration = |seconds: f64| {
+        seconds
+            .is_finite()
+            .then(|| time::Duration::nanoseconds((seconds * 1e9).round() as i64))
+    };
+    if let Some(ms) = text.strip_suffix("ms") {
+        return duration(ms.trim().parse::<f64>().ok()? / 1000.0);
+    }
+    let text = text.strip_suffix('s').unwrap_or(text);
+    let mut seconds = 0.0;
+    for part in text.split(':') {
+        seconds = seconds * 60.0 + part.trim().parse::<f64>().ok()?;
+    }
+    duration(seconds)
+}
+
+/// A script that is being executed on its own thread.
+pub struct Running {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl Running {
+    pub fn stop(&self) {
+        self.stop.store(true, atomic::Ordering::Relaxed);
+        self.thread.thread().unpark();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+}
+
+/// Executes the commands on a separate thread. The waits are measured from
+/// the start of the script, so they don't drift with the time the commands
+/// take.
+pub fn start(commands: Vec<Command>, mut apply: impl FnMut(&Command) + Send + 'static) -> Running {
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = thread::spawn({
+        let stop = stop.clone();
+        move || {
+            let mut deadline = Instant::now();
+            for command in &commands {
+                if let Command::Wait(duration) = command {
+                    deadline += *duration;
+                    while !stop.load(atomic::Ordering::Relaxed) {
+                        let now = Instant::now();
+                        if now >= deadline {
+                            break;
+                        }
+                        thread::park_timeout(deadline - now);
+                    }
+                    continue;
+                }
+                if stop.load(atomic::Ordering::Relaxed) {
+                    return;
+                }
+                apply(command);
+            }
+        }
+    });
+    Running { stop, thread }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let commands = parse(
+            "# Splits right as the game time is set.\n\
+             start\n\
+             \n\
+             set_game_time 1:23.456\n\
+             wait 500ms\n\
+             split",
+        )
+        .unwrap();
+        assert_eq!(
+            commands,
+            [
+                Command::Start,
+                Command::SetGameTime(time::Duration::milliseconds(83_456)),
+                Command::Wait(std::time::Duration::from_millis(500)),
+                Command::Split,
+            ],
+        );
+
+        assert_eq!(
+            parse_duration("1.5s"),
+            Some(time::Duration::milliseconds(1500))
+        );
+        assert_eq!(parse_duration("1:00:00"), Some(time::Duration::HOUR));
+        assert_eq!(
+            parse(&commands[1].to_string()).unwrap(),
+            [Command::SetGameTime(time::Duration::milliseconds(83_456))],
+        );
+        assert_eq!(commands[2].to_string(), "wait 0.5s");
+        assert!(parse("wait -1s").is_err());
+        assert!(parse("set_game_time soon").is_err());
+        assert_eq!(
+            parse("jump").unwrap_err(),
+            "Line 1: Unknown command \"jump\"."
+        );
+    }
+}
@@ -0,0 +1,124 @@
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TrapKind {
+    Unreachable,
+    OutOfBounds,
+    Interrupt,
+    OtherTrap,
+    HostError,
+}
+
+pub const KINDS: [TrapKind; 5] = [
+    TrapKind::Unreachable,
+    TrapKind::OutOfBounds,
+    TrapKind::Interrupt,
+    TrapKind::OtherTrap,
+    TrapKind::HostError,
+];
+
+impl TrapKind {
+    /// Categorizes a failed update based on its error message. Traps of the
+    /// WebAssembly code are reported as `wasm trap: <reason>`, while any other
+    /// error originates from the host.
+    pub fn classify(message: &str) -> Self {
+        let Some((_, reason)) = message.split_once("wasm trap: ") else {
+            return TrapKind::HostError;
+        };
+        let reason = reason.lines().next().unwrap_or_default();
+        if reason.contains("unreachable") {
+            TrapKind::Unreachable
+        } else if reason.contains("out of bounds") {
+            TrapKind::OutOfBounds
+        } else if reason.contains("interrupt") {
+            TrapKind::Interrupt
+        } else {
+            TrapKind::OtherTrap
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            TrapKind::Unreachable => "Unreachable",
+            TrapKind::OutOfBounds => "Out of Bounds",
+            TrapKind::Interrupt => "Interrupt",
+            TrapKind::OtherTrap => "Other Trap",
+            TrapKind::HostError => "Host Error",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LastTrap {
+    pub tick: u64,
+    pub message: Box<str>,
+}
+
+/// Counts the failed updates of the session by their kind.
+#[derive(Clone, Default)]
+pub struct TrapStats {
+    counts: [u64; KINDS.len()],
+    last: [Option<LastTrap>; KINDS.len()],
+}
+
+impl TrapStats {
+    pub fn record(&mut self, kind: TrapKind, tick: u64, message: Box<str>) {
+        self.counts[kind as usize] += 1;
+        self.last[kind as usize] = Some(LastTrap { tick, message });
+    }
+
+    pub fn count(&self, kind: TrapKind) -> u64 {
+        self.counts[kind as usize]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn last(&self, kind: TrapKind) -> Option<&LastTrap> {
+        self.last[kind as usize].as_ref()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let classify = |reason| {
+            TrapKind::classify(&format!(
+                "Failed executing the auto splitter.\n\nCaused by:\n    0: error while executing at wasm backtrace:\n    1: wasm trap: {reason}"
+            ))
+        };
+        assert_eq!(
+            classify("wasm `unreachable` instruction executed"),
+            TrapKind::Unreachable
+        );
+        assert_eq!(
+            classify("out of bounds memory access"),
+            TrapKind::OutOfBounds
+        );
+        assert_eq!(classify("interrupt"), TrapKind::Interrupt);
+        assert_eq!(classify("integer divide by zero"), TrapKind::OtherTrap);
+        assert_eq!(
+            TrapKind::classify("Failed executing the auto splitter.\n\nCaused by:\n    The process handle is invalid."),
+            TrapKind::HostError
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let mut stats = TrapStats::default();
+        stats.record(TrapKind::Interrupt, 3, "first".into());
+        stats.record(TrapKind::Interrupt, 7, "second".into());
+        assert_eq!(stats.count(TrapKind::Interrupt), 2);
+        assert_eq!(stats.count(TrapKind::Unreachable), 0);
+        let last = stats.last(TrapKind::Interrupt).unwrap();
+        assert_eq!(last.tick, 7);
+        assert_eq!(&*last.message, "second");
+        assert!(stats.last(TrapKind::HostError).is_none());
+    }
+}
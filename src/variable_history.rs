@@ -0,0 +1,144 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io::{self, Write},
+};
+
+use indexmap::IndexMap;
+use livesplit_auto_splitting::time::OffsetDateTime;
+
+/// How many samples are kept per variable. Once a variable has more, the
+/// oldest ones are dropped, so recording for a long time doesn't use up all
+/// the memory.
+pub const SAMPLE_CAPACITY: usize = 100_000;
+
+/// The value of a recorded variable at the end of a specific tick.
+#[derive(Clone)]
+pub struct Sample {
+    pub tick: u64,
+    pub time: OffsetDateTime,
+    pub value: String,
+}
+
+/// Adds a sample to the samples of a variable, dropping the oldest one if
+/// there are too many.
+pub fn record(samples: &mut VecDeque<Sample>, sample: Sample) {
+    if samples.len() == SAMPLE_CAPACITY {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+/// Writes the recorded samples of all the variables as CSV. Each row
+/// represents a single tick, with a column per variable. Variables that had
+/// no value during a tick are left empty.
+pub fn write_csv(
+    mut writer: impl Write,
+    history: &IndexMap<Box<str>, VecDeque<Sample>>,
+) -> io::Result<()> {
+    write!(writer, "Tick,Time")?;
+    for key in history.keys() {
+        write!(writer, ",")?;
+        write_field(&mut writer, key)?;
+    }
+    writeln!(writer)?;
+
+    let mut rows = BTreeMap::<u64, (OffsetDateTime, Vec<Option<&str>>)>::new();
+    for (index, samples) in history.values().enumerate() {
+        for sample in samples {
+            let (_, values) = rows
+                .entry(sample.tick)
+                .or_insert_with(|| (sample.time, vec![None; history.len()]));
+            values[index] = Some(&sample.value);
+        }
+    }
+
+    for (tick, (time, values)) in rows {
+        write!(writer, "{tick},")?;
+        write_time(&mut writer, time)?;
+        for value in values {
+            write!(writer, ",")?;
+            if let Some(value) = value {
+                write_field(&mut writer, value)?;
+            }
+        }
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+fn write_time(mut writer: impl Write, time: OffsetDateTime) -> io::Result<()> {
+    let (h, m, s, ms) = time.time().as_hms_milli();
+    write!(
+        writer,
+        "{}-{:02}-{:02}T{h:02}:{m:02}:{s:02}.{ms:03}",
+        time.year(),
+        time.month() as u8,
+        time.day(),
+    )
+}
+
+fn write_field(mut writer: impl Write, field: &str) -> io::Result<()> {
+    if field.contains([',', '"', '\n', '\r']) {
+        write!(writer, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        write!(writer, "{field}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_write_field() {
+        let escape = |field| {
+            let mut buf = Vec::new();
+            write_field(&mut buf, field).unwrap();
+            String::from_utf8(buf).unwrap()
+        };
+        assert_eq!(escape("foo"), "foo");
+        assert_eq!(escape("foo,bar"), "\"foo,bar\"");
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let time = OffsetDateTime::UNIX_EPOCH;
+        let sample = |tick, value: &str| Sample {
+            tick,
+            time,
+            value: value.into(),
+        };
+        let mut history = IndexMap::new();
+        history.insert("a".into(), VecDeque::from([sample(1, "1"), sample(2, "2")]));
+        history.insert("b,c".into(), VecDeque::from([sample(2, "x")]));
+
+        let mut buf = Vec::new();
+        write_csv(&mut buf, &history).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "Tick,Time,a,\"b,c\"\n\
+             1,1970-01-01T00:00:00.000,1,\n\
+             2,1970-01-01T00:00:00.000,2,x\n",
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let mut samples = VecDeque::new();
+        for tick in 0..SAMPLE_CAPACITY as u64 + 2 {
+            record(
+                &mut samples,
+                Sample {
+                    tick,
+                    time: OffsetDateTime::UNIX_EPOCH,
+                    value: String::new(),
+                },
+            );
+        }
+        assert_eq!(samples.len(), SAMPLE_CAPACITY);
+        assert_eq!(samples.front().unwrap().tick, 2);
+    }
+}